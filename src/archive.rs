@@ -0,0 +1,328 @@
+//! Archive extraction for `open`
+//!
+//! Streams zip, tar and gzip-compressed tar archives through the `zip`,
+//! `tar` and `flate2` crates. Entries that would be written outside the
+//! destination, links and special files are skipped, and extraction stops
+//! once an archive holds too many entries or expands too far.
+
+use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+
+/// Most entries read from one archive
+const MAX_ENTRIES: usize = 10_000;
+
+/// Most bytes written for one archive, so a decompression bomb cannot fill
+/// the disk
+const MAX_EXTRACTED_BYTES: u64 = 1 << 30;
+
+/// Archive formats `open` can extract
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveKind {
+    const EXTENSIONS: &[(&str, ArchiveKind)] = &[
+        (".zip", ArchiveKind::Zip),
+        (".tar.gz", ArchiveKind::TarGz),
+        (".tgz", ArchiveKind::TarGz),
+        (".tar", ArchiveKind::Tar),
+    ];
+
+    /// Detect the archive format from the file name
+    pub fn detect(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        Self::EXTENSIONS
+            .iter()
+            .find(|(ext, _)| name.ends_with(ext))
+            .map(|(_, kind)| *kind)
+    }
+
+    /// The file name of `path` without its archive extension
+    pub fn stem(path: &Path) -> String {
+        let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let lower = name.to_ascii_lowercase();
+        Self::EXTENSIONS
+            .iter()
+            .find(|(ext, _)| lower.ends_with(ext))
+            .map(|(ext, _)| name[..name.len() - ext.len()].to_string())
+            .unwrap_or(name)
+    }
+}
+
+/// A fresh path in the temporary directory to extract an archive named `stem` into
+pub fn temp_destination(stem: &str) -> PathBuf {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    std::env::temp_dir().join(format!("navr-{}-{}-{}", stem, std::process::id(), nanos))
+}
+
+/// Extract `archive` into `dest`, returning the number of files written
+///
+/// `dest` is created here and must not exist yet, so nothing placed at that
+/// path beforehand is written through. On Unix only the owner can read it.
+pub fn extract(archive: &Path, kind: ArchiveKind, dest: &Path) -> Result<usize> {
+    extract_with_limits(archive, kind, dest, MAX_ENTRIES, MAX_EXTRACTED_BYTES)
+}
+
+fn extract_with_limits(
+    archive: &Path,
+    kind: ArchiveKind,
+    dest: &Path,
+    max_entries: usize,
+    max_bytes: u64,
+) -> Result<usize> {
+    let file = File::open(archive)
+        .with_context(|| format!("Failed to read {}", archive.display()))?;
+    let mut builder = fs::DirBuilder::new();
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder
+        .create(dest)
+        .with_context(|| format!("Failed to create {}", dest.display()))?;
+
+    let mut out = Extractor {
+        dest,
+        max_entries,
+        max_bytes,
+        entries: 0,
+        bytes: 0,
+        files: 0,
+    };
+    match kind {
+        ArchiveKind::Zip => extract_zip(file, &mut out)?,
+        ArchiveKind::Tar => extract_tar(file, &mut out)?,
+        ArchiveKind::TarGz => extract_tar(GzDecoder::new(file), &mut out)?,
+    }
+    Ok(out.files)
+}
+
+fn extract_zip(file: File, out: &mut Extractor) -> Result<()> {
+    let mut zip = zip::ZipArchive::new(file).context("Not a valid zip archive")?;
+    for index in 0..zip.len() {
+        out.next_entry()?;
+        let mut entry = zip.by_index(index)?;
+        let name = entry.name().to_string();
+        if entry.is_dir() {
+            out.write(&name, None)?;
+        } else if entry.is_file() {
+            // Reading to the end also checks the entry's CRC32
+            out.write(&name, Some(&mut entry))?;
+        } else {
+            tracing::warn!("Skipping archive entry '{}': not a regular file", name);
+        }
+    }
+    Ok(())
+}
+
+fn extract_tar(reader: impl Read, out: &mut Extractor) -> Result<()> {
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries().context("Not a valid tar archive")? {
+        out.next_entry()?;
+        let mut entry = entry?;
+        // `path` includes GNU long names and pax `path` records
+        let name = entry.path()?.to_string_lossy().into_owned();
+        let kind = entry.header().entry_type();
+        if kind.is_dir() {
+            out.write(&name, None)?;
+        } else if kind.is_file() {
+            out.write(&name, Some(&mut entry))?;
+        } else {
+            tracing::warn!("Skipping archive entry '{}': not a regular file", name);
+        }
+    }
+    Ok(())
+}
+
+/// Writes entries under the destination while keeping count of the limits
+struct Extractor<'a> {
+    dest: &'a Path,
+    max_entries: usize,
+    max_bytes: u64,
+    entries: usize,
+    bytes: u64,
+    files: usize,
+}
+
+impl Extractor<'_> {
+    /// Count an entry, skipped or not, against the entry limit
+    fn next_entry(&mut self) -> Result<()> {
+        self.entries += 1;
+        if self.entries > self.max_entries {
+            anyhow::bail!("Archive has more than {} entries", self.max_entries);
+        }
+        Ok(())
+    }
+
+    /// Create directory `name`, or file `name` with the given contents
+    fn write(&mut self, name: &str, contents: Option<&mut dyn Read>) -> Result<()> {
+        let Some(path) = entry_path(self.dest, name) else {
+            tracing::warn!("Skipping unsafe archive entry '{}'", name);
+            return Ok(());
+        };
+        let Some(contents) = contents else {
+            fs::create_dir_all(&path)?;
+            return Ok(());
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = File::create(&path)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        let remaining = self.max_bytes - self.bytes;
+        let copied = io::copy(&mut contents.take(remaining + 1), &mut file)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        if copied > remaining {
+            anyhow::bail!("Archive expands to more than {} bytes", self.max_bytes);
+        }
+        self.bytes += copied;
+        self.files += 1;
+        Ok(())
+    }
+}
+
+/// Where entry `name` lands under `dest`, unless it would escape it
+fn entry_path(dest: &Path, name: &str) -> Option<PathBuf> {
+    let name = name.replace('\\', "/");
+    let mut path = dest.to_path_buf();
+    for component in Path::new(&name).components() {
+        match component {
+            Component::Normal(part) => path.push(part),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    (path != dest).then_some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const TAR_BLOCK: usize = 512;
+
+    /// A raw tar header, so names the `tar` builder refuses can be written
+    fn tar_header(name: &str, kind: u8, size: usize) -> Vec<u8> {
+        let mut header = vec![0u8; TAR_BLOCK];
+        header[..name.len()].copy_from_slice(name.as_bytes());
+        header[100..108].copy_from_slice(b"0000644\0");
+        header[124..135].copy_from_slice(format!("{:011o}", size).as_bytes());
+        header[156] = kind;
+        header[148..156].fill(b' ');
+        let checksum: u32 = header.iter().map(|&b| u32::from(b)).sum();
+        header[148..156].copy_from_slice(format!("{:06o}\0 ", checksum).as_bytes());
+        header
+    }
+
+    #[test]
+    fn test_extract_tar_skips_unsafe_entries() {
+        let mut data = Vec::new();
+        data.extend(tar_header("docs/", b'5', 0));
+        for name in ["docs/readme.txt", "../escape.txt", "/etc/navr.txt"] {
+            data.extend(tar_header(name, b'0', 4));
+            let mut body = b"navr".to_vec();
+            body.resize(TAR_BLOCK, 0);
+            data.extend(body);
+        }
+        data.extend(tar_header("docs/link", b'2', 0));
+        data.extend([0u8; TAR_BLOCK * 2]);
+
+        let root = std::env::temp_dir().join(format!("navr-archive-{}", std::process::id()));
+        let archive = root.join("docs.tar.gz");
+        let dest = root.join("out");
+        fs::create_dir_all(&root).unwrap();
+        let file = File::create(&archive).unwrap();
+        let mut gz = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+        gz.write_all(&data).unwrap();
+        gz.finish().unwrap();
+
+        assert_eq!(ArchiveKind::detect(&archive), Some(ArchiveKind::TarGz));
+        assert_eq!(ArchiveKind::stem(Path::new("/srv/Backup.TAR.GZ")), "Backup");
+        assert_eq!(extract(&archive, ArchiveKind::TarGz, &dest).unwrap(), 1);
+        assert_eq!(fs::read(dest.join("docs").join("readme.txt")).unwrap(), b"navr");
+        assert!(!root.join("escape.txt").exists());
+        assert!(!dest.join("etc").exists() && !dest.join("docs").join("link").exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    /// A zip archive holding `(name, contents)` entries; names ending in
+    /// `/` are directories
+    fn zip(path: &Path, entries: &[(&str, &[u8])]) {
+        let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+        for (name, contents) in entries {
+            if name.ends_with('/') {
+                let options = zip::write::SimpleFileOptions::default();
+                zip.add_directory(*name, options).unwrap();
+                continue;
+            }
+            let options = zip::write::SimpleFileOptions::default();
+            let options = if contents.len() > 4 {
+                options.compression_method(zip::CompressionMethod::Deflated)
+            } else {
+                options.compression_method(zip::CompressionMethod::Stored)
+            };
+            zip.start_file(*name, options).unwrap();
+            zip.write_all(contents).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn test_extract_zip_into_fresh_directory() {
+        let root = std::env::temp_dir().join(format!("navr-zip-{}", std::process::id()));
+        let archive = root.join("notes.zip");
+        let dest = root.join("out");
+        fs::create_dir_all(&root).unwrap();
+        zip(
+            &archive,
+            &[
+                ("notes/", b""),
+                ("notes/stored.txt", b"navr"),
+                ("notes/deflated.txt", b"navr navr navr jumps"),
+                ("../escape.txt", b"navr"),
+            ],
+        );
+
+        assert_eq!(extract(&archive, ArchiveKind::Zip, &dest).unwrap(), 2);
+        assert_eq!(fs::read(dest.join("notes").join("stored.txt")).unwrap(), b"navr");
+        assert_eq!(
+            fs::read(dest.join("notes").join("deflated.txt")).unwrap(),
+            b"navr navr navr jumps"
+        );
+        assert!(!root.join("escape.txt").exists());
+
+        // An existing destination is never reused
+        assert!(extract(&archive, ArchiveKind::Zip, &dest).is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_extract_stops_at_limits() {
+        let root = std::env::temp_dir().join(format!("navr-zip-limits-{}", std::process::id()));
+        let archive = root.join("bomb.zip");
+        fs::create_dir_all(&root).unwrap();
+        let zeros = vec![0u8; 64 * 1024];
+        zip(&archive, &[("a.bin", &zeros), ("b.bin", &zeros), ("c.bin", b"navr")]);
+
+        let extract = |dest: &str, entries, bytes| {
+            extract_with_limits(&archive, ArchiveKind::Zip, &root.join(dest), entries, bytes)
+        };
+        assert_eq!(extract("all", 3, 1 << 20).unwrap(), 3);
+        assert!(extract("entries", 2, 1 << 20).is_err());
+        let err = extract("bytes", 3, 100 * 1024).unwrap_err();
+        assert!(err.to_string().contains("more than 102400 bytes"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,190 @@
+//! Short-lived cache of whether shortcut targets exist
+//!
+//! Stat calls on unreachable network mounts can hang for a long time, so
+//! results are kept in the data directory for a short while and checks run
+//! on a small pool of worker threads with a bounded wait.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::config::paths::data_dir;
+use crate::platform::path_exists;
+
+/// How long a cached result is trusted
+const CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// How long to wait for all pending existence checks
+const CHECK_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Most worker threads started for one round of checks
+const MAX_WORKERS: usize = 8;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct CacheEntry {
+    exists: bool,
+    /// Seconds since the Unix epoch when the path was checked
+    checked: u64,
+}
+
+/// Existence results keyed by path
+pub struct ExistenceCache {
+    file: PathBuf,
+    entries: HashMap<String, CacheEntry>,
+    dirty: bool,
+}
+
+impl ExistenceCache {
+    /// Load the cache from the data directory, or start empty when `fresh`
+    pub fn load(fresh: bool) -> Result<Self> {
+        Ok(Self::at(data_dir()?.join("exists_cache.json"), fresh))
+    }
+
+    fn at(file: PathBuf, fresh: bool) -> Self {
+        let entries = if fresh {
+            HashMap::new()
+        } else {
+            std::fs::read_to_string(&file)
+                .ok()
+                .and_then(|content| serde_json::from_str(&content).ok())
+                .unwrap_or_default()
+        };
+
+        Self {
+            file,
+            entries,
+            dirty: false,
+        }
+    }
+
+    /// Make sure every path has a result no older than the TTL
+    ///
+    /// Paths whose check does not finish in time are recorded as missing.
+    pub fn check(&mut self, paths: &[&str], follow_symlinks: bool) {
+        let now = now_secs();
+        let mut stale: Vec<&str> = paths
+            .iter()
+            .copied()
+            .filter(|path| {
+                self.entries
+                    .get(*path)
+                    .is_none_or(|e| now.saturating_sub(e.checked) > CACHE_TTL.as_secs())
+            })
+            .collect();
+        stale.sort_unstable();
+        stale.dedup();
+
+        if stale.is_empty() {
+            return;
+        }
+
+        // Workers are detached so one stuck on a hung mount cannot hold up
+        // the listing; the rest keep draining the queue until the deadline
+        let queue: Vec<String> = stale.iter().map(|path| path.to_string()).collect();
+        let queue = Arc::new(Mutex::new(queue));
+        let (tx, rx) = mpsc::channel();
+        for _ in 0..stale.len().min(MAX_WORKERS) {
+            let tx = tx.clone();
+            let queue = Arc::clone(&queue);
+            std::thread::spawn(move || {
+                while let Some(path) = queue.lock().ok().and_then(|mut queue| queue.pop()) {
+                    let exists = path_exists(Path::new(&path), follow_symlinks);
+                    if tx.send((path, exists)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        let mut results: HashMap<String, bool> = HashMap::new();
+        let deadline = std::time::Instant::now() + CHECK_TIMEOUT;
+        while results.len() < stale.len() {
+            let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+            match rx.recv_timeout(remaining) {
+                Ok((path, exists)) => {
+                    results.insert(path, exists);
+                }
+                Err(_) => break,
+            }
+        }
+
+        for path in stale {
+            if !results.contains_key(path) {
+                tracing::debug!("Timed out checking {}", path);
+            }
+            let exists = results.get(path).copied().unwrap_or(false);
+            self.entries.insert(path.to_string(), CacheEntry { exists, checked: now });
+        }
+        self.dirty = true;
+    }
+
+    /// Cached result for a path; unchecked paths count as missing
+    pub fn exists(&self, path: &str) -> bool {
+        self.entries.get(path).is_some_and(|e| e.exists)
+    }
+
+    /// Write the cache back if anything changed, dropping expired entries
+    pub fn save(&self) -> Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+
+        if let Some(parent) = self.file.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let now = now_secs();
+        let live: HashMap<&String, &CacheEntry> = self
+            .entries
+            .iter()
+            .filter(|(_, e)| now.saturating_sub(e.checked) <= CACHE_TTL.as_secs())
+            .collect();
+        let content = serde_json::to_string(&live)?;
+        std::fs::write(&self.file, content)
+            .with_context(|| format!("Failed to write {:?}", self.file))
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cached_result_is_reused_within_ttl() {
+        let file = std::env::temp_dir().join(format!("navr-cache-{}.json", std::process::id()));
+        let missing = "/nonexistent/navr/cache";
+
+        // Pretend the missing path was seen to exist a moment ago
+        let mut cache = ExistenceCache::at(file.clone(), true);
+        cache.entries.insert(
+            missing.to_string(),
+            CacheEntry {
+                exists: true,
+                checked: now_secs(),
+            },
+        );
+        cache.check(&[missing], true);
+        assert!(cache.exists(missing));
+
+        // Once expired, the path is checked again
+        cache.entries.get_mut(missing).unwrap().checked = now_secs() - CACHE_TTL.as_secs() - 1;
+        cache.check(&[missing], true);
+        assert!(!cache.exists(missing));
+
+        cache.save().unwrap();
+        assert!(!ExistenceCache::at(file.clone(), false).exists(missing));
+        assert!(!ExistenceCache::at(file.clone(), true).entries.contains_key(missing));
+
+        std::fs::remove_file(&file).unwrap();
+    }
+}
@@ -0,0 +1,428 @@
+//! Browse command - Full-screen chooser behind `navr jump --interactive`
+//!
+//! Shortcuts and recent directories are listed side by side with the status
+//! of each target. Typing filters both panes fuzzily. The screen is drawn on
+//! stderr, leaving stdout for the chosen path.
+
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Color, Print, ResetColor, SetAttribute, SetForegroundColor};
+use crossterm::terminal::{self, ClearType};
+use crossterm::{cursor, execute, queue};
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use std::io::{IsTerminal, Stderr, Write};
+
+use crate::commands::jump::TargetStatus;
+use crate::config::AppConfig;
+use crate::history;
+
+/// Recent directories listed in the history pane
+const HISTORY_LIMIT: usize = 100;
+
+const HELP: &str = "↑↓ move  Tab switch pane  Enter jump  Del remove  Esc quit";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pane {
+    Shortcuts,
+    History,
+}
+
+impl Pane {
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    fn other(self) -> Self {
+        match self {
+            Pane::Shortcuts => Pane::History,
+            Pane::History => Pane::Shortcuts,
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Pane::Shortcuts => "Shortcuts",
+            Pane::History => "Recent",
+        }
+    }
+}
+
+/// A row of either pane
+#[derive(Debug, Clone)]
+pub struct Entry {
+    /// Shortcut name; `None` for history entries
+    pub name: Option<String>,
+    pub path: String,
+    status: TargetStatus,
+}
+
+impl Entry {
+    /// The text shown for the entry, which is also what the filter matches
+    fn label(&self) -> String {
+        match &self.name {
+            Some(name) => format!("{}  {}", name, self.path),
+            None => self.path.clone(),
+        }
+    }
+}
+
+/// What a key press asks the browser loop to do
+#[derive(Debug, PartialEq, Eq)]
+enum Action {
+    Continue,
+    Choose,
+    Remove(String),
+    Quit,
+}
+
+struct Browser {
+    panes: [Vec<Entry>; 2],
+    filter: String,
+    pane: Pane,
+    /// Selected row of each pane, counted in the filtered rows
+    selected: [usize; 2],
+    /// Shortcut waiting for a `y` before it is removed
+    pending_remove: Option<String>,
+    /// Shown in place of the key help until the next key press
+    message: Option<String>,
+    matcher: SkimMatcherV2,
+}
+
+impl Browser {
+    fn new(shortcuts: Vec<Entry>, history: Vec<Entry>, filter: Option<&str>) -> Self {
+        // Start where there is something to choose from
+        let pane = if shortcuts.is_empty() && !history.is_empty() {
+            Pane::History
+        } else {
+            Pane::Shortcuts
+        };
+        Self {
+            panes: [shortcuts, history],
+            filter: filter.unwrap_or_default().to_string(),
+            pane,
+            selected: [0, 0],
+            pending_remove: None,
+            message: None,
+            matcher: SkimMatcherV2::default(),
+        }
+    }
+
+    /// Entries of `pane` matching the filter, best match first
+    fn visible(&self, pane: Pane) -> Vec<&Entry> {
+        let entries = &self.panes[pane.index()];
+        if self.filter.is_empty() {
+            return entries.iter().collect();
+        }
+
+        let mut scored: Vec<(i64, &Entry)> = entries
+            .iter()
+            .filter_map(|entry| Some((self.matcher.fuzzy_match(&entry.label(), &self.filter)?, entry)))
+            .collect();
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(_, entry)| entry).collect()
+    }
+
+    fn selected_entry(&self) -> Option<&Entry> {
+        self.visible(self.pane).get(self.selected[self.pane.index()]).copied()
+    }
+
+    fn handle(&mut self, key: KeyEvent) -> Action {
+        if let Some(name) = self.pending_remove.take() {
+            self.message = None;
+            if matches!(key.code, KeyCode::Char('y' | 'Y')) {
+                return Action::Remove(name);
+            }
+            return Action::Continue;
+        }
+        self.message = None;
+
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => return Action::Quit,
+            KeyCode::Char('c') if ctrl => return Action::Quit,
+            KeyCode::Enter if self.selected_entry().is_some() => return Action::Choose,
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Left | KeyCode::Right => {
+                self.pane = self.pane.other();
+            }
+            KeyCode::Up => self.move_by(-1),
+            KeyCode::Char('p') if ctrl => self.move_by(-1),
+            KeyCode::Down => self.move_by(1),
+            KeyCode::Char('n') if ctrl => self.move_by(1),
+            KeyCode::Delete => self.ask_remove(),
+            KeyCode::Char('d') if ctrl => self.ask_remove(),
+            KeyCode::Backspace => {
+                self.filter.pop();
+                self.selected = [0, 0];
+            }
+            KeyCode::Char(c) if !ctrl => {
+                self.filter.push(c);
+                self.selected = [0, 0];
+            }
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    fn move_by(&mut self, delta: isize) {
+        let len = self.visible(self.pane).len();
+        let selected = &mut self.selected[self.pane.index()];
+        if len > 0 {
+            *selected = selected.saturating_add_signed(delta).min(len - 1);
+        }
+    }
+
+    fn ask_remove(&mut self) {
+        match self.selected_entry().and_then(|entry| entry.name.clone()) {
+            Some(name) => {
+                self.message = Some(format!("Remove shortcut '{}'? (y/n)", name));
+                self.pending_remove = Some(name);
+            }
+            None => self.message = Some("Only shortcuts can be removed".to_string()),
+        }
+    }
+
+    /// Drop a shortcut that was removed from the config
+    fn removed(&mut self, name: &str) {
+        self.panes[Pane::Shortcuts.index()].retain(|entry| entry.name.as_deref() != Some(name));
+        let len = self.visible(Pane::Shortcuts).len();
+        let selected = &mut self.selected[Pane::Shortcuts.index()];
+        *selected = (*selected).min(len.saturating_sub(1));
+        self.message = Some(format!("Removed shortcut '{}'", name));
+    }
+
+    fn draw(&self, out: &mut impl Write, width: u16, height: u16, color: bool) -> std::io::Result<()> {
+        let width = width as usize;
+        let half = width / 2;
+        let rows = (height as usize).saturating_sub(3);
+
+        queue!(out, terminal::Clear(ClearType::All), cursor::MoveTo(0, 0))?;
+        queue!(out, Print(fit(&format!("> {}", self.filter), width)))?;
+
+        for pane in [Pane::Shortcuts, Pane::History] {
+            let x = (pane.index() * half) as u16;
+            let visible = self.visible(pane);
+            let active = pane == self.pane;
+
+            let title = format!("{} ({})", pane.title(), visible.len());
+            queue!(out, cursor::MoveTo(x, 1))?;
+            if active {
+                queue!(out, SetAttribute(Attribute::Bold))?;
+            }
+            queue!(out, Print(fit(&title, half.saturating_sub(1))), SetAttribute(Attribute::Reset))?;
+
+            // Scroll just far enough to keep the selected row on screen
+            let selected = self.selected[pane.index()];
+            let offset = (selected + 1).saturating_sub(rows);
+            for (row, entry) in visible.iter().enumerate().skip(offset).take(rows) {
+                queue!(out, cursor::MoveTo(x, (2 + row - offset) as u16))?;
+                if color {
+                    queue!(out, SetForegroundColor(status_color(entry.status)))?;
+                }
+                queue!(out, Print(entry.status.glyph()), ResetColor, Print(" "))?;
+                if active && row == selected {
+                    queue!(out, SetAttribute(Attribute::Reverse))?;
+                }
+                queue!(
+                    out,
+                    Print(fit(&entry.label(), half.saturating_sub(3))),
+                    SetAttribute(Attribute::Reset)
+                )?;
+            }
+        }
+
+        let status = self.message.as_deref().unwrap_or(HELP);
+        queue!(
+            out,
+            cursor::MoveTo(0, height.saturating_sub(1)),
+            SetAttribute(Attribute::Dim),
+            Print(fit(status, width)),
+            SetAttribute(Attribute::Reset),
+            cursor::MoveTo((2 + self.filter.chars().count()).min(width) as u16, 0)
+        )?;
+        out.flush()
+    }
+}
+
+fn status_color(status: TargetStatus) -> Color {
+    match status {
+        TargetStatus::Directory => Color::Green,
+        TargetStatus::Symlink => Color::Yellow,
+        TargetStatus::File => Color::Blue,
+        TargetStatus::Missing => Color::Red,
+        TargetStatus::Remote => Color::DarkGrey,
+    }
+}
+
+/// `text` cut to `width` characters, ending in `…` when shortened
+fn fit(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut fitted: String = text.chars().take(width.saturating_sub(1)).collect();
+    if width > 0 {
+        fitted.push('…');
+    }
+    fitted
+}
+
+/// The terminal in raw mode on the alternate screen, restored when dropped
+struct Screen(Stderr);
+
+impl Screen {
+    fn enter() -> Result<Self> {
+        terminal::enable_raw_mode()?;
+        let mut err = std::io::stderr();
+        if let Err(e) = execute!(err, terminal::EnterAlternateScreen) {
+            let _ = terminal::disable_raw_mode();
+            return Err(e.into());
+        }
+        Ok(Self(err))
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = execute!(self.0, terminal::LeaveAlternateScreen, cursor::Show);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Shortcuts with their resolved targets, by name
+fn shortcut_entries(config: &AppConfig) -> Vec<Entry> {
+    let mut names: Vec<&String> = config.shortcuts.keys().collect();
+    names.sort();
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let path = config.get_shortcut(name).ok().flatten()?;
+            Some(Entry {
+                name: Some(name.clone()),
+                path: path.clone(),
+                status: TargetStatus::of_path(path),
+            })
+        })
+        .collect()
+}
+
+/// Distinct recent directories, most recent first
+fn history_entries(config: &AppConfig) -> Vec<Entry> {
+    if !config.shell.track_history {
+        return Vec::new();
+    }
+    let entries = history::read_history().unwrap_or_default();
+    history::recent_entries(&entries, true, Some(HISTORY_LIMIT))
+        .into_iter()
+        .map(|path| Entry {
+            name: None,
+            path: path.to_string(),
+            status: TargetStatus::of_path(path),
+        })
+        .collect()
+}
+
+/// Let the user choose a shortcut or recent directory, starting with
+/// `filter` typed in; `None` when they quit without choosing
+pub fn run(config: &mut AppConfig, filter: Option<&str>) -> Result<Option<Entry>> {
+    if !std::io::stdin().is_terminal() || !std::io::stderr().is_terminal() {
+        anyhow::bail!("--interactive needs a terminal; use 'navr jump --list' in scripts");
+    }
+
+    let mut browser = Browser::new(shortcut_entries(config), history_entries(config), filter);
+    if browser.panes.iter().all(Vec::is_empty) {
+        anyhow::bail!("No shortcuts or history to browse");
+    }
+    // stdout is captured by the shell wrapper, so only NO_COLOR decides
+    let color = std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty());
+
+    let mut screen = Screen::enter()?;
+    loop {
+        let (width, height) = terminal::size()?;
+        browser.draw(&mut screen.0, width, height, color)?;
+
+        // Anything but a key press, such as a resize, only needs a redraw
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match browser.handle(key) {
+            Action::Continue => {}
+            Action::Choose => return Ok(browser.selected_entry().cloned()),
+            Action::Remove(name) => match config.remove_shortcut(&name) {
+                Ok(_) => browser.removed(&name),
+                Err(e) => browser.message = Some(format!("Could not remove '{}': {:#}", name, e)),
+            },
+            Action::Quit => return Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: Option<&str>, path: &str) -> Entry {
+        Entry {
+            name: name.map(str::to_string),
+            path: path.to_string(),
+            status: TargetStatus::Directory,
+        }
+    }
+
+    fn press(browser: &mut Browser, code: KeyCode) -> Action {
+        browser.handle(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_browser_filters_moves_and_removes() {
+        let mut browser = Browser::new(
+            vec![entry(Some("docs"), "/home/u/docs"), entry(Some("proj"), "/srv/proj")],
+            vec![entry(None, "/srv/proj/src"), entry(None, "/tmp")],
+            None,
+        );
+        assert_eq!(browser.visible(Pane::Shortcuts).len(), 2);
+
+        // Typing filters both panes
+        for c in "proj".chars() {
+            press(&mut browser, KeyCode::Char(c));
+        }
+        assert_eq!(browser.visible(Pane::Shortcuts).len(), 1);
+        assert_eq!(browser.visible(Pane::History).len(), 1);
+        assert_eq!(browser.selected_entry().unwrap().name.as_deref(), Some("proj"));
+
+        // History entries cannot be removed
+        press(&mut browser, KeyCode::Tab);
+        press(&mut browser, KeyCode::Delete);
+        assert!(browser.pending_remove.is_none());
+        assert_eq!(press(&mut browser, KeyCode::Enter), Action::Choose);
+        assert_eq!(browser.selected_entry().unwrap().path, "/srv/proj/src");
+
+        // Removing a shortcut waits for a `y`
+        press(&mut browser, KeyCode::Tab);
+        press(&mut browser, KeyCode::Delete);
+        assert_eq!(press(&mut browser, KeyCode::Char('n')), Action::Continue);
+        press(&mut browser, KeyCode::Delete);
+        assert_eq!(press(&mut browser, KeyCode::Char('y')), Action::Remove("proj".to_string()));
+        browser.removed("proj");
+        assert!(browser.selected_entry().is_none());
+        assert_eq!(press(&mut browser, KeyCode::Enter), Action::Continue);
+
+        // Clearing the filter brings back the rest; moving stays in range
+        for _ in 0..4 {
+            press(&mut browser, KeyCode::Backspace);
+        }
+        press(&mut browser, KeyCode::Down);
+        press(&mut browser, KeyCode::Down);
+        assert_eq!(browser.selected_entry().unwrap().name.as_deref(), Some("docs"));
+        assert_eq!(press(&mut browser, KeyCode::Esc), Action::Quit);
+    }
+
+    #[test]
+    fn test_fit_marks_cut_text() {
+        assert_eq!(fit("short", 10), "short");
+        assert_eq!(fit("/a/long/path", 6), "/a/lo…");
+        assert_eq!(fit("abc", 0), "");
+    }
+}
@@ -0,0 +1,80 @@
+//! Complete command - Dynamic completion backend for shell scripts
+//!
+//! Invoked by the shell integration as `navr _complete <kind> [arg]`,
+//! printing one candidate per line.
+
+use anyhow::Result;
+use clap::ValueEnum;
+
+use crate::commands::jump::match_shortcuts;
+use crate::config::{find_config_key, AppConfig, ConfigValueKind, CONFIG_KEYS};
+
+/// Kind of candidates to produce
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CompleteKind {
+    /// Keys accepted by `config set`/`config get`
+    ConfigKeys,
+    /// Values accepted by the given config key
+    ConfigValues,
+    /// Shortcut names matching the given prefix per `shell.completion_style`
+    Shortcuts,
+}
+
+pub fn execute(config: &AppConfig, kind: CompleteKind, arg: Option<&str>) -> Result<()> {
+    if let CompleteKind::Shortcuts = kind {
+        for (name, _) in match_shortcuts(config, arg.unwrap_or_default()) {
+            println!("{}", name);
+        }
+        return Ok(());
+    }
+
+    for candidate in candidates(kind, arg) {
+        println!("{}", candidate);
+    }
+    Ok(())
+}
+
+fn candidates(kind: CompleteKind, arg: Option<&str>) -> Vec<&'static str> {
+    match kind {
+        CompleteKind::Shortcuts => Vec::new(),
+        CompleteKind::ConfigKeys => CONFIG_KEYS
+            .iter()
+            .filter(|k| !k.deprecated)
+            .map(|k| k.name)
+            .collect(),
+        CompleteKind::ConfigValues => match arg.and_then(find_config_key) {
+            Some(key) => match key.kind {
+                ConfigValueKind::Bool => vec!["true", "false"],
+                ConfigValueKind::Choice(values) => values.to_vec(),
+                _ => Vec::new(),
+            },
+            None => Vec::new(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_key_candidates() {
+        let keys = candidates(CompleteKind::ConfigKeys, None);
+        assert!(keys.contains(&"behavior.case_sensitive"));
+        assert!(keys.contains(&"shell.max_history"));
+    }
+
+    #[test]
+    fn test_config_value_candidates() {
+        assert_eq!(
+            candidates(CompleteKind::ConfigValues, Some("shell.enabled")),
+            vec!["true", "false"]
+        );
+        assert!(candidates(CompleteKind::ConfigValues, Some("shell.max_history")).is_empty());
+        assert!(candidates(CompleteKind::ConfigValues, None).is_empty());
+        assert_eq!(
+            candidates(CompleteKind::ConfigValues, Some("shell.completion_style")),
+            vec!["prefix", "fuzzy", "exact"]
+        );
+    }
+}
@@ -1,13 +1,25 @@
 //! Config command - Manage application configuration
 
-use anyhow::Result;
-use owo_colors::OwoColorize;
-use inquire::{Confirm, Select};
+use anyhow::{Context, Result};
 use clap::Subcommand;
+use inquire::{Confirm, Select};
+use owo_colors::{OwoColorize, Stream::Stdout, Style};
+use serde::Serialize;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 
-use crate::config::AppConfig;
 use crate::commands::open::list_file_managers;
-use crate::config::defaults::create_default_config;
+use crate::commands::profile::{self, ProfileSubCommand};
+use crate::commands::report::Report;
+use crate::config::defaults::{common_file_managers, create_default_config};
+use crate::config::shortcut::PLATFORMS;
+use crate::config::{
+    is_remote_uri, validate_shortcut_name, AppConfig, CompletionStyle, ResolutionOrder, ShellConfig,
+};
+use crate::config::{paths, schema};
+use crate::history;
+use crate::platform::file_manager::BUILTIN_FILE_MANAGERS;
+use crate::platform::path_exists;
 
 pub struct ConfigCommand {
     action: ConfigSubCommand,
@@ -18,7 +30,11 @@ pub enum ConfigSubCommand {
     /// Show current configuration
     Show,
     /// Edit configuration interactively
-    Edit,
+    Edit {
+        /// Open the TOML file in $VISUAL or $EDITOR instead
+        #[arg(long)]
+        raw: bool,
+    },
     /// Set configuration value
     Set {
         /// Configuration key (e.g., 'default_file_manager')
@@ -29,7 +45,12 @@ pub enum ConfigSubCommand {
     /// Get configuration value
     Get {
         /// Configuration key
-        key: String,
+        #[arg(required_unless_present = "all")]
+        key: Option<String>,
+
+        /// Print every key as `key = value`
+        #[arg(long, conflicts_with = "key")]
+        all: bool,
     },
     /// Reset configuration to defaults
     Reset,
@@ -37,6 +58,28 @@ pub enum ConfigSubCommand {
     SetFileManager {
         /// File manager command or 'auto' for system default
         manager: String,
+
+        /// Set `platform.<OS>.file_manager` instead of the global default
+        #[arg(long, value_name = "OS", value_parser = PLATFORMS.to_vec())]
+        platform: Option<String>,
+    },
+    /// Check a config file for problems without making it the active config
+    Validate {
+        /// Config file to check
+        path: PathBuf,
+    },
+    /// Print the config file, data directory and history file, one per line
+    Path {
+        /// Print the locations as a JSON object
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print a JSON Schema of the config file for editor completion
+    Schema,
+    /// Manage config profiles
+    Profile {
+        #[command(subcommand)]
+        action: ProfileSubCommand,
     },
 }
 
@@ -44,25 +87,47 @@ pub enum ConfigSubCommand {
 pub enum ShellSubCommand {
     /// Generate shell completion script
     Complete {
-        /// Shell type (bash, zsh, fish, powershell, elvish)
+        /// Shell type (bash, zsh, fish, powershell, elvish); detected if omitted
         #[arg(value_enum)]
-        shell: clap_complete::Shell,
+        shell: Option<clap_complete::Shell>,
+
+        /// Also complete shortcut names for jump and open (bash, zsh, fish)
+        #[arg(long)]
+        dynamic: bool,
+
+        /// Write the script into DIR (default: the shell's completion directory)
+        #[arg(short, long, value_name = "DIR", num_args = 0..=1, require_equals = true)]
+        output: Option<Option<PathBuf>>,
     },
     /// Install shell integration
     Install {
-        /// Shell type
+        /// Shell type; detected if omitted
         #[arg(value_enum)]
-        shell: clap_complete::Shell,
+        shell: Option<clap_complete::Shell>,
 
         /// Installation path
         #[arg(short, long)]
         path: Option<String>,
     },
+    /// Remove shell integration
+    Uninstall {
+        /// Shell type; detected if omitted
+        #[arg(value_enum)]
+        shell: Option<clap_complete::Shell>,
+
+        /// Startup file to remove it from
+        #[arg(short, long)]
+        path: Option<String>,
+    },
     /// Generate initialization script for shell integration
     Init {
-        /// Shell type
+        /// Shell type; detected if omitted
         #[arg(value_enum)]
-        shell: clap_complete::Shell,
+        shell: Option<clap_complete::Shell>,
+
+        /// Write the script into DIR (default: next to the startup file, or fish's conf.d)
+        #[arg(short, long, value_name = "DIR", num_args = 0..=1, require_equals = true)]
+        output: Option<Option<PathBuf>>,
     },
 }
 
@@ -74,162 +139,279 @@ impl ConfigCommand {
     pub fn execute(&self, config: &mut AppConfig) -> Result<()> {
         match &self.action {
             ConfigSubCommand::Show => self.show_config(config),
-            ConfigSubCommand::Edit => self.edit_interactive(config),
+            ConfigSubCommand::Edit { raw: false } => self.edit_interactive(config),
+            ConfigSubCommand::Edit { raw: true } => edit_file(&config.file_path()?),
             ConfigSubCommand::Set { key, value } => self.set_value(config, key, value),
-            ConfigSubCommand::Get { key } => self.get_value(config, key),
+            ConfigSubCommand::Get { key: Some(key), .. } => self.get_value(config, key),
+            ConfigSubCommand::Get { key: None, .. } => self.get_all_values(config),
             ConfigSubCommand::Reset => self.reset_config(config),
-            ConfigSubCommand::SetFileManager { manager } => {
-                self.set_file_manager(config, manager)
+            ConfigSubCommand::SetFileManager { manager, platform } => {
+                self.set_file_manager(config, manager, platform.as_deref())
             }
+            ConfigSubCommand::Validate { path } => validate_file(path),
+            ConfigSubCommand::Path { json } => print_paths(&config.file_path()?, *json),
+            ConfigSubCommand::Schema => print_schema(),
+            ConfigSubCommand::Profile { action } => profile::execute(action, config.is_dry_run()),
         }
     }
 
     fn show_config(&self, config: &AppConfig) -> Result<()> {
-        println!("{}", "Current Configuration:".bold().underline());
+        println!(
+            "{}",
+            "Current Configuration:"
+                .if_supports_color(Stdout, |t| t.style(Style::new().bold().underline()))
+        );
         println!();
 
         // General settings
-        println!("{}", "General:".bold());
+        println!("{}", "General:".if_supports_color(Stdout, |t| t.bold()));
         println!(
             "  {}: {}",
-            "Version".cyan(),
-            config.version.dimmed()
+            "Version".if_supports_color(Stdout, |t| t.cyan()),
+            config.version.if_supports_color(Stdout, |t| t.dimmed())
         );
         println!(
             "  {}: {}",
-            "Default File Manager".cyan(),
+            "Default File Manager".if_supports_color(Stdout, |t| t.cyan()),
             config
                 .default_file_manager
                 .as_deref()
                 .unwrap_or("auto-detect")
-                .yellow()
+                .if_supports_color(Stdout, |t| t.yellow())
         );
+        if let Some(opener) = &config.remote_opener {
+            println!(
+                "  {}: {}",
+                "Remote Opener".if_supports_color(Stdout, |t| t.cyan()),
+                opener.if_supports_color(Stdout, |t| t.yellow())
+            );
+        }
         println!();
 
         // Shortcuts
-        println!("{}", "Shortcuts:".bold());
-        println!("  {} shortcuts configured", config.shortcuts.len().to_string().cyan());
-        if !config.shortcuts.is_empty() {
-            let preview: Vec<_> = config.shortcuts.iter().take(5).collect();
-            for (name, path) in preview {
-                println!("  {} → {}", name.cyan(), path.dimmed());
-            }
-            if config.shortcuts.len() > 5 {
-                println!("  ... and {} more", config.shortcuts.len() - 5);
-            }
+        println!("{}", "Shortcuts:".if_supports_color(Stdout, |t| t.bold()));
+        println!(
+            "  {} shortcuts configured",
+            config.shortcuts.len().to_string().if_supports_color(Stdout, |t| t.cyan())
+        );
+        let (preview, more) = shortcut_preview(config);
+        let max_len = preview.iter().map(|(n, _)| n.len()).max().unwrap_or(0);
+        for (name, path) in preview {
+            let padding = " ".repeat(max_len - name.len());
+            println!(
+                "  {}{}  {} {}",
+                name.if_supports_color(Stdout, |t| t.cyan()),
+                padding,
+                "→".if_supports_color(Stdout, |t| t.dimmed()),
+                path.if_supports_color(Stdout, |t| t.dimmed())
+            );
+        }
+        if more > 0 {
+            println!("  ... and {} more", more);
         }
         println!();
 
         // Shell settings
-        println!("{}", "Shell Integration:".bold());
-        println!("  {}: {}", "Enabled".cyan(), format_bool(config.shell.enabled));
-        println!("  {}: {}", "Hook cd".cyan(), format_bool(config.shell.hook_cd));
+        println!("{}", "Shell Integration:".if_supports_color(Stdout, |t| t.bold()));
+        println!(
+            "  {}: {}",
+            "Enabled".if_supports_color(Stdout, |t| t.cyan()),
+            format_bool(config.shell.enabled)
+        );
         println!(
             "  {}: {}",
-            "Track History".cyan(),
+            "Hook cd".if_supports_color(Stdout, |t| t.cyan()),
+            format_bool(config.shell.hook_cd)
+        );
+        println!(
+            "  {}: {}",
+            "Track History".if_supports_color(Stdout, |t| t.cyan()),
             format_bool(config.shell.track_history)
         );
         println!(
             "  {}: {}",
-            "Max History".cyan(),
-            config.shell.max_history.to_string().yellow()
+            "Max History".if_supports_color(Stdout, |t| t.cyan()),
+            config.shell.max_history.to_string().if_supports_color(Stdout, |t| t.yellow())
+        );
+        println!(
+            "  {}: {}",
+            "Max Stack".if_supports_color(Stdout, |t| t.cyan()),
+            config.shell.max_stack.to_string().if_supports_color(Stdout, |t| t.yellow())
         );
+        if !config.shell.ignore_patterns.is_empty() {
+            println!(
+                "  {}: {}",
+                "Ignore Patterns".if_supports_color(Stdout, |t| t.cyan()),
+                config.shell.ignore_patterns.join(", ")
+            );
+        }
         println!();
 
         // Behavior settings
-        println!("{}", "Behavior:".bold());
+        println!("{}", "Behavior:".if_supports_color(Stdout, |t| t.bold()));
         println!(
             "  {}: {}",
-            "Confirm Overwrite".cyan(),
+            "Confirm Overwrite".if_supports_color(Stdout, |t| t.cyan()),
             format_bool(config.behavior.confirm_overwrite)
         );
         println!(
             "  {}: {}",
-            "Create Missing".cyan(),
+            "Create Missing".if_supports_color(Stdout, |t| t.cyan()),
             format_bool(config.behavior.create_missing)
         );
         println!(
             "  {}: {}",
-            "Follow Symlinks".cyan(),
+            "Follow Symlinks".if_supports_color(Stdout, |t| t.cyan()),
             format_bool(config.behavior.follow_symlinks)
         );
         println!(
             "  {}: {}",
-            "Case Sensitive".cyan(),
+            "Case Sensitive".if_supports_color(Stdout, |t| t.cyan()),
             format_bool(config.behavior.case_sensitive)
         );
+        println!(
+            "  {}: {}",
+            "Allow File Shortcuts".if_supports_color(Stdout, |t| t.cyan()),
+            format_bool(config.behavior.allow_file_shortcuts)
+        );
+        println!(
+            "  {}: {}",
+            "Allow Privileged Mkdir".if_supports_color(Stdout, |t| t.cyan()),
+            format_bool(config.behavior.allow_privileged_mkdir)
+        );
+        println!(
+            "  {}: {}",
+            "Reuse Window".if_supports_color(Stdout, |t| t.cyan()),
+            format_bool(config.behavior.reuse_window)
+        );
+        println!(
+            "  {}: {}",
+            "Open Archives".if_supports_color(Stdout, |t| t.cyan()),
+            format_bool(config.behavior.open_archives)
+        );
+        println!(
+            "  {}: {}",
+            "Detach Children".if_supports_color(Stdout, |t| t.cyan()),
+            format_bool(config.behavior.detach_children)
+        );
+        println!(
+            "  {}: {}",
+            "Resolution Order".if_supports_color(Stdout, |t| t.cyan()),
+            config.behavior.resolution_order.if_supports_color(Stdout, |t| t.yellow())
+        );
+        if let Some(sigil) = &config.behavior.shortcut_sigil {
+            println!(
+                "  {}: {}",
+                "Shortcut Sigil".if_supports_color(Stdout, |t| t.cyan()),
+                sigil.if_supports_color(Stdout, |t| t.yellow())
+            );
+        }
+        println!(
+            "  {}: {}",
+            "Max Suggestions".if_supports_color(Stdout, |t| t.cyan()),
+            config.behavior.max_suggestions.to_string().if_supports_color(Stdout, |t| t.yellow())
+        );
+        if config.behavior.spawn_timeout_secs > 0 {
+            println!(
+                "  {}: {}s",
+                "Spawn Timeout".if_supports_color(Stdout, |t| t.cyan()),
+                config.behavior.spawn_timeout_secs.to_string()
+                    .if_supports_color(Stdout, |t| t.yellow())
+            );
+        }
+        if let Some(mode) = config.behavior.create_missing_mode {
+            println!(
+                "  {}: {:04o}",
+                "Create Missing Mode".if_supports_color(Stdout, |t| t.cyan()),
+                mode
+            );
+        }
         println!();
 
         // Platform settings
         #[cfg(target_os = "windows")]
         {
-            println!("{}", "Windows Settings:".bold());
+            println!("{}", "Windows Settings:".if_supports_color(Stdout, |t| t.bold()));
             println!(
                 "  {}: {}",
-                "Use Windows Terminal".cyan(),
+                "Use Windows Terminal".if_supports_color(Stdout, |t| t.cyan()),
                 format_bool(config.platform.windows.use_windows_terminal)
             );
             println!(
                 "  {}: {}",
-                "PowerShell Aliases".cyan(),
+                "PowerShell Aliases".if_supports_color(Stdout, |t| t.cyan()),
                 format_bool(config.platform.windows.use_powershell_aliases)
             );
+            println!(
+                "  {}: {}",
+                "Use ShellExecute".if_supports_color(Stdout, |t| t.cyan()),
+                format_bool(config.platform.windows.use_shell_execute)
+            );
         }
 
         #[cfg(target_os = "macos")]
         {
-            println!("{}", "macOS Settings:".bold());
+            println!("{}", "macOS Settings:".if_supports_color(Stdout, |t| t.bold()));
             println!(
                 "  {}: {}",
-                "Use Finder".cyan(),
+                "Use Finder".if_supports_color(Stdout, |t| t.cyan()),
                 format_bool(config.platform.macos.use_finder)
             );
             println!(
                 "  {}: {}",
-                "Prefer iTerm2".cyan(),
+                "Prefer iTerm2".if_supports_color(Stdout, |t| t.cyan()),
                 format_bool(config.platform.macos.prefer_iterm2)
             );
         }
 
         #[cfg(target_os = "linux")]
         {
-            println!("{}", "Linux Settings:".bold());
+            println!("{}", "Linux Settings:".if_supports_color(Stdout, |t| t.bold()));
             println!(
                 "  {}: {}",
-                "Desktop Environment".cyan(),
+                "Desktop Environment".if_supports_color(Stdout, |t| t.cyan()),
                 config
                     .platform
                     .linux
                     .desktop_env
                     .as_deref()
                     .unwrap_or("auto-detect")
-                    .yellow()
+                    .if_supports_color(Stdout, |t| t.yellow())
             );
             println!(
                 "  {}: {}",
-                "File Manager".cyan(),
+                "File Manager".if_supports_color(Stdout, |t| t.cyan()),
                 config
                     .platform
                     .linux
                     .file_manager
                     .as_deref()
                     .unwrap_or("auto-detect")
-                    .yellow()
+                    .if_supports_color(Stdout, |t| t.yellow())
+            );
+            println!(
+                "  {}: {}",
+                "WSL Interop".if_supports_color(Stdout, |t| t.cyan()),
+                format_bool(config.platform.linux.wsl_interop)
             );
         }
 
         println!();
         println!(
             "{} Config file: {}",
-            "ℹ".blue(),
-            AppConfig::config_path()?.display().to_string().dimmed()
+            "ℹ".if_supports_color(Stdout, |t| t.blue()),
+            config.file_path()?.display().to_string().if_supports_color(Stdout, |t| t.dimmed())
         );
 
         Ok(())
     }
 
     fn edit_interactive(&self, config: &mut AppConfig) -> Result<()> {
-        println!("{}", "Interactive Configuration Editor".bold().underline());
+        println!(
+            "{}",
+            "Interactive Configuration Editor"
+                .if_supports_color(Stdout, |t| t.style(Style::new().bold().underline()))
+        );
         println!();
 
         // Edit shell settings
@@ -279,7 +461,7 @@ impl ConfigCommand {
         config.save()?;
         
         println!();
-        println!("{} Configuration saved!", "✓".green());
+        println!("{} Configuration saved!", "✓".if_supports_color(Stdout, |t| t.green()));
 
         Ok(())
     }
@@ -288,16 +470,31 @@ impl ConfigCommand {
         config.set_value(key, value)?;
         println!(
             "{} Set {} = {}",
-            "✓".green(),
-            key.cyan(),
-            value.yellow()
+            "✓".if_supports_color(Stdout, |t| t.green()),
+            key.if_supports_color(Stdout, |t| t.cyan()),
+            value.if_supports_color(Stdout, |t| t.yellow())
         );
         Ok(())
     }
 
     fn get_value(&self, config: &AppConfig, key: &str) -> Result<()> {
         let value = config.get_value(key)?;
-        println!("{} = {}", key.cyan(), value.yellow());
+        println!(
+            "{} = {}",
+            key.if_supports_color(Stdout, |t| t.cyan()),
+            value.if_supports_color(Stdout, |t| t.yellow())
+        );
+        Ok(())
+    }
+
+    fn get_all_values(&self, config: &AppConfig) -> Result<()> {
+        for (key, value) in config.all_values()? {
+            println!(
+                "{} = {}",
+                key.if_supports_color(Stdout, |t| t.cyan()),
+                value.if_supports_color(Stdout, |t| t.yellow())
+            );
+        }
         Ok(())
     }
 
@@ -309,40 +506,392 @@ impl ConfigCommand {
         .prompt()?;
 
         if confirm {
-            *config = create_default_config();
+            config.replace_settings(create_default_config());
             config.save()?;
-            println!("{} Configuration reset to defaults", "✓".green());
+            println!(
+                "{} Configuration reset to defaults",
+                "✓".if_supports_color(Stdout, |t| t.green())
+            );
         } else {
-            println!("{} Cancelled", "✗".red());
+            println!("{} Cancelled", "✗".if_supports_color(Stdout, |t| t.red()));
         }
 
         Ok(())
     }
 
-    fn set_file_manager(&self, config: &mut AppConfig, manager: &str) -> Result<()> {
+    fn set_file_manager(
+        &self,
+        config: &mut AppConfig,
+        manager: &str,
+        platform: Option<&str>,
+    ) -> Result<()> {
         let manager = if manager == "auto" {
             None
         } else {
             Some(manager.to_string())
         };
 
+        if let Some(os) = platform {
+            return set_platform_file_manager(config, os, manager);
+        }
+
         config.default_file_manager = manager.clone();
         config.save()?;
 
         println!(
             "{} Default file manager set to: {}",
-            "✓".green(),
-            manager.as_deref().unwrap_or("auto-detect").cyan()
+            "✓".if_supports_color(Stdout, |t| t.green()),
+            manager.as_deref().unwrap_or("auto-detect").if_supports_color(Stdout, |t| t.cyan())
         );
 
         Ok(())
     }
 }
 
+/// Set `platform.<os>.file_manager`, warning about names navr does not know for `os`
+fn set_platform_file_manager(config: &mut AppConfig, os: &str, manager: Option<String>) -> Result<()> {
+    if let Some(name) = &manager {
+        let program = name.split_whitespace().next().unwrap_or_default();
+        if !common_file_managers(os).contains(&program) && !config.file_managers.contains_key(name) {
+            println!(
+                "{} '{}' is not a known {} file manager; saving it anyway",
+                "!".if_supports_color(Stdout, |t| t.yellow()),
+                name,
+                os
+            );
+        }
+    }
+
+    let key = format!("platform.{}.file_manager", os);
+    config.set_value(&key, manager.as_deref().unwrap_or_default())?;
+    config.save()?;
+
+    println!(
+        "{} {} file manager set to: {}",
+        "✓".if_supports_color(Stdout, |t| t.green()),
+        os,
+        manager.as_deref().unwrap_or("auto-detect").if_supports_color(Stdout, |t| t.cyan())
+    );
+    if let Some(global) = &config.default_file_manager {
+        println!(
+            "{} default_file_manager '{}' still takes precedence; \
+             run 'navr config set-file-manager auto' to clear it",
+            "ℹ".if_supports_color(Stdout, |t| t.blue()),
+            global
+        );
+    }
+    Ok(())
+}
+
+/// Print the JSON Schema describing `config.toml`
+pub fn print_schema() -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(&schema::schema())?);
+    Ok(())
+}
+
+/// Where navr keeps its files, as printed by `config path`
+#[derive(Debug, Serialize)]
+struct Locations {
+    config: PathBuf,
+    data_dir: PathBuf,
+    history: PathBuf,
+}
+
+/// Print the locations of the config file and data files
+///
+/// Works without loading the config, so it also helps when the file is broken.
+pub fn print_paths(config_file: &Path, json: bool) -> Result<()> {
+    let locations = Locations {
+        config: config_file.to_path_buf(),
+        data_dir: paths::data_dir()?,
+        history: history::history_path()?,
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&locations)?);
+    } else {
+        println!("{}", locations.config.display());
+        println!("{}", locations.data_dir.display());
+        println!("{}", locations.history.display());
+    }
+    Ok(())
+}
+
+/// The first shortcuts by name with their resolved paths, and how many were left out
+///
+/// Aliases show the path they resolve to. `behavior.max_suggestions` sets
+/// how many are shown.
+fn shortcut_preview(config: &AppConfig) -> (Vec<(&str, &str)>, usize) {
+    let mut names: Vec<&String> = config.shortcuts.keys().collect();
+    names.sort();
+
+    let preview = names
+        .iter()
+        .take(config.behavior.max_suggestions)
+        .map(|name| {
+            let path = config
+                .get_shortcut(name)
+                .ok()
+                .flatten()
+                .unwrap_or(config.shortcuts[*name].current_path());
+            (name.as_str(), path.as_str())
+        })
+        .collect();
+    (preview, names.len().saturating_sub(config.behavior.max_suggestions))
+}
+
+/// Editors tried when neither `$VISUAL` nor `$EDITOR` is set
+const FALLBACK_EDITORS: &[&str] = if cfg!(windows) { &["notepad"] } else { &["nano", "vi"] };
+
+/// The editor command: `$VISUAL`, then `$EDITOR`, then the first installed fallback
+fn editor_command(
+    var: impl Fn(&str) -> Option<String>,
+    installed: impl Fn(&str) -> bool,
+) -> Option<String> {
+    ["VISUAL", "EDITOR"]
+        .into_iter()
+        .filter_map(var)
+        .map(|value| value.trim().to_string())
+        .find(|value| !value.is_empty())
+        .or_else(|| FALLBACK_EDITORS.iter().find(|e| installed(e)).map(|e| e.to_string()))
+}
+
+/// Open the config file in an editor, offering to reopen it until it is valid
+pub fn edit_file(path: &Path) -> Result<()> {
+    if !path.exists() {
+        let mut config = AppConfig::default();
+        config.set_file_path(path);
+        config.save()?;
+    }
+
+    let editor = editor_command(|key| std::env::var(key).ok(), |e| which::which(e).is_ok())
+        .context("No editor found; set $EDITOR")?;
+    // `$EDITOR` may carry arguments, as in "code --wait"
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().context("Empty editor command")?;
+    let args: Vec<&str> = parts.collect();
+
+    loop {
+        let status = std::process::Command::new(program)
+            .args(&args)
+            .arg(path)
+            .status()
+            .with_context(|| format!("Failed to run editor '{}'", editor))?;
+        if !status.success() {
+            anyhow::bail!("Editor '{}' exited with {}", editor, status);
+        }
+
+        let mut report = Report::default();
+        println!("{} {}", "Validating".if_supports_color(Stdout, |t| t.bold()), path.display());
+        match AppConfig::load_from_path(path) {
+            Ok(config) => validate_config(&config, &mut report),
+            Err(e) => report.fail("Syntax", &format!("{:#}", e)),
+        }
+        if report.failed == 0 {
+            println!(
+                "{} Config is valid ({} warnings)",
+                "✓".if_supports_color(Stdout, |t| t.green()),
+                report.warned
+            );
+            return Ok(());
+        }
+
+        let reopen = std::io::stdin().is_terminal()
+            && Confirm::new(&format!("{} errors found. Reopen the editor?", report.failed))
+                .with_default(true)
+                .prompt()?;
+        if !reopen {
+            anyhow::bail!("{} errors left in {}", report.failed, path.display());
+        }
+    }
+}
+
+/// Lint a config file without loading it as the active config
+///
+/// The file is only read; nothing is ever saved back.
+pub fn validate_file(path: &Path) -> Result<()> {
+    let mut report = Report::default();
+
+    println!("{} {}", "Validating".if_supports_color(Stdout, |t| t.bold()), path.display());
+
+    match AppConfig::load_from_path(path) {
+        Ok(config) => {
+            report.pass("Syntax", "file parses");
+            validate_config(&config, &mut report);
+        }
+        Err(e) => report.fail("Syntax", &format!("{:#}", e)),
+    }
+
+    println!();
+    if report.failed > 0 {
+        anyhow::bail!("{} errors found in {}", report.failed, path.display());
+    }
+    println!(
+        "{} Config is valid ({} warnings)",
+        "✓".if_supports_color(Stdout, |t| t.green()),
+        report.warned
+    );
+    Ok(())
+}
+
+/// Check the values of a parsed config
+///
+/// Settings that make navr misbehave are errors; paths and programs that
+/// are missing on this machine are only warnings, since the file may be
+/// meant for another one.
+fn validate_config(config: &AppConfig, report: &mut Report) {
+    let mut names: Vec<&String> = config.shortcuts.keys().collect();
+    names.sort();
+    for name in names {
+        let label = format!("Shortcut '{}'", name);
+        if let Err(e) = validate_shortcut_name(name) {
+            report.fail(&label, &e.to_string());
+            continue;
+        }
+        for os in config.shortcuts[name].platform_overrides.keys() {
+            if !PLATFORMS.contains(&os.as_str()) {
+                report.warn(&label, &format!("unknown platform override '{}'", os));
+            }
+        }
+        match config.get_shortcut(name) {
+            Ok(Some(target)) if is_remote_uri(target) => {}
+            Ok(Some(target)) if path_exists(Path::new(target), config.behavior.follow_symlinks) => {}
+            Ok(Some(target)) => report.warn(&label, &format!("{} does not exist", target)),
+            Ok(None) => {}
+            Err(e) => report.fail(&label, &e.to_string()),
+        }
+    }
+
+    let mut file_managers: Vec<(String, &String)> = [
+        ("default_file_manager", config.default_file_manager.as_ref()),
+        ("platform.windows.file_manager", config.platform.windows.file_manager.as_ref()),
+        ("platform.macos.file_manager", config.platform.macos.file_manager.as_ref()),
+        ("platform.linux.file_manager", config.platform.linux.file_manager.as_ref()),
+    ]
+    .into_iter()
+    .filter_map(|(key, fm)| Some((key.to_string(), fm?)))
+    .collect();
+    let mut aliases: Vec<_> = config.file_managers.iter().collect();
+    aliases.sort();
+    file_managers.extend(aliases.into_iter().map(|(alias, fm)| (format!("file_managers.{}", alias), fm)));
+
+    for (key, fm) in file_managers {
+        let program = fm.split_whitespace().next().unwrap_or_default();
+        if program.is_empty() {
+            report.fail(&key, "file manager command is empty");
+        } else if !BUILTIN_FILE_MANAGERS.contains(&program) && which::which(program).is_err() {
+            report.warn(&key, &format!("unknown file manager '{}'", fm));
+        }
+    }
+
+    for (key, value) in [
+        ("shell.max_history", config.shell.max_history),
+        ("shell.max_stack", config.shell.max_stack),
+    ] {
+        if value == 0 {
+            report.fail(key, "must be at least 1");
+        }
+    }
+
+    for pattern in &config.shell.ignore_patterns {
+        if let Err(e) = ShellConfig::ignore_glob(pattern) {
+            report.fail("shell.ignore_patterns", &e.to_string());
+        }
+    }
+
+    if let Err(e) = config.shell.completion_style.parse::<CompletionStyle>() {
+        report.fail("shell.completion_style", &e.to_string());
+    }
+    if let Err(e) = config.behavior.resolution_order.parse::<ResolutionOrder>() {
+        report.fail("behavior.resolution_order", &e.to_string());
+    }
+}
+
 fn format_bool(value: bool) -> String {
     if value {
-        "true".green().to_string()
+        "true".if_supports_color(Stdout, |t| t.green()).to_string()
     } else {
-        "false".red().to_string()
+        "false".if_supports_color(Stdout, |t| t.red()).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Shortcut;
+
+    #[test]
+    fn test_editor_command() {
+        let pick = |vars: &[(&str, &str)], installed: bool| {
+            let var = |key: &str| vars.iter().find(|(k, _)| *k == key).map(|(_, v)| v.to_string());
+            editor_command(var, |_| installed)
+        };
+
+        let both = [("VISUAL", "code --wait"), ("EDITOR", "vim")];
+        assert_eq!(pick(&both, true).as_deref(), Some("code --wait"));
+        assert_eq!(pick(&[("VISUAL", " "), ("EDITOR", "vim")], true).as_deref(), Some("vim"));
+        assert_eq!(pick(&[], true).as_deref(), Some(FALLBACK_EDITORS[0]));
+        assert_eq!(pick(&[], false), None);
+    }
+
+    #[test]
+    fn test_shortcut_preview_is_sorted() {
+        let mut config = AppConfig::default();
+        config.shortcuts.clear();
+        for name in ["zeta", "beta", "eta", "alpha", "theta", "delta", "gamma"] {
+            config.shortcuts.insert(name.to_string(), Shortcut::new(format!("/srv/{}", name)));
+        }
+        config.shortcuts.insert("ab".to_string(), Shortcut::alias_of("alpha"));
+
+        let (preview, more) = shortcut_preview(&config);
+        let names: Vec<&str> = preview.iter().map(|(n, _)| *n).collect();
+        assert_eq!(names, ["ab", "alpha", "beta", "delta", "eta"]);
+        assert_eq!(preview[0].1, "/srv/alpha");
+        assert_eq!(more, 3);
+        assert_eq!(shortcut_preview(&config), (preview, more));
+    }
+
+    #[test]
+    fn test_set_file_manager_for_platform() {
+        let dir = std::env::temp_dir().join(format!("navr-set-fm-{}", std::process::id()));
+        let mut config = AppConfig::default();
+        config.set_file_path(dir.join("config.toml"));
+        config.default_file_manager = Some("dolphin".to_string());
+
+        let cmd = ConfigCommand::new(ConfigSubCommand::Show);
+        cmd.set_file_manager(&mut config, "thunar", Some("linux")).unwrap();
+        assert_eq!(config.platform.linux.file_manager.as_deref(), Some("thunar"));
+        assert_eq!(config.default_file_manager.as_deref(), Some("dolphin"));
+        assert_eq!(config.platform.macos.file_manager, None);
+
+        // Unknown names only warn
+        cmd.set_file_manager(&mut config, "my-fm --flag", Some("macos")).unwrap();
+        assert_eq!(config.platform.macos.file_manager.as_deref(), Some("my-fm --flag"));
+
+        cmd.set_file_manager(&mut config, "auto", Some("linux")).unwrap();
+        assert_eq!(config.platform.linux.file_manager, None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_validate_config() {
+        let mut config = AppConfig::default();
+        config.shortcuts.clear();
+        config.shortcuts.insert("tmp".to_string(), Shortcut::new(std::env::temp_dir().to_string_lossy()));
+
+        let mut report = Report::default();
+        validate_config(&config, &mut report);
+        assert_eq!((report.warned, report.failed), (0, 0));
+
+        config.shortcuts.insert("gone".to_string(), Shortcut::new("/nonexistent/navr/validate"));
+        config.shortcuts.insert("loop".to_string(), Shortcut::alias_of("loop"));
+        config.default_file_manager = Some("no-such-file-manager --flag".to_string());
+        config.shell.max_history = 0;
+        config.shell.completion_style = "sorted".to_string();
+
+        let mut report = Report::default();
+        validate_config(&config, &mut report);
+        assert_eq!((report.warned, report.failed), (2, 3));
     }
 }
@@ -0,0 +1,171 @@
+//! Doctor command - Diagnose the navr installation
+//!
+//! Runs before the configuration is loaded so a broken config file is
+//! reported instead of aborting the command.
+
+use anyhow::Result;
+use owo_colors::{OwoColorize, Stream::Stdout, Style};
+use std::path::{Path, PathBuf};
+
+use crate::commands::report::Report;
+use crate::config::{paths, AppConfig, FileManagerSource};
+use crate::platform::shell_config_path;
+use crate::shell::is_integration_installed;
+
+/// Shells whose startup files are checked for the integration snippet
+const SHELLS: &[&str] = &["bash", "zsh", "fish", "powershell"];
+
+pub fn execute(config_path: Option<&str>) -> Result<()> {
+    let mut report = Report::default();
+
+    println!(
+        "{}",
+        "Navr Doctor".if_supports_color(Stdout, |t| t.style(Style::new().bold().underline()))
+    );
+    println!("  Version: {}", env!("CARGO_PKG_VERSION").if_supports_color(Stdout, |t| t.dimmed()));
+    println!();
+
+    let config = check_config(&mut report, config_path)?;
+    check_directories(&mut report, &config)?;
+    check_file_manager(&mut report, &config);
+    check_shell_integration(&mut report);
+
+    println!();
+    if report.failed > 0 {
+        anyhow::bail!("{} checks failed", report.failed);
+    }
+    println!(
+        "{} No problems found ({} warnings)",
+        "✓".if_supports_color(Stdout, |t| t.green()),
+        report.warned
+    );
+    Ok(())
+}
+
+fn check_config(report: &mut Report, config_path: Option<&str>) -> Result<AppConfig> {
+    let path = match config_path {
+        Some(path) => PathBuf::from(path),
+        None => AppConfig::config_path()?,
+    };
+
+    if !path.exists() {
+        report.warn(
+            "Config file",
+            &format!("{} does not exist yet; defaults will be used", path.display()),
+        );
+        let mut config = AppConfig::default();
+        config.set_file_path(path);
+        return Ok(config);
+    }
+
+    match AppConfig::load_from_path(&path) {
+        Ok(config) => {
+            report.pass("Config file", &format!("{} parses", path.display()));
+            Ok(config)
+        }
+        Err(e) => {
+            report.fail("Config file", &format!("{:#}", e));
+            let mut config = AppConfig::default();
+            config.set_file_path(path);
+            Ok(config)
+        }
+    }
+}
+
+fn check_directories(report: &mut Report, config: &AppConfig) -> Result<()> {
+    let config_file = config.file_path()?;
+    let dirs = [
+        ("Config directory", config_file.parent().map(Path::to_path_buf)),
+        ("Data directory", paths::data_dir().ok()),
+    ];
+
+    for (name, dir) in dirs {
+        match dir {
+            Some(dir) => match probe_writable(&dir) {
+                Ok(()) if dir.is_dir() => {
+                    report.pass(name, &format!("{} is writable", dir.display()))
+                }
+                Ok(()) => report.pass(name, &format!("{} can be created", dir.display())),
+                Err(e) => report.fail(name, &format!("{} is not writable: {}", dir.display(), e)),
+            },
+            None => report.fail(name, "could not be determined"),
+        }
+    }
+
+    Ok(())
+}
+
+/// Check from metadata alone that `dir`, or the closest ancestor that
+/// exists, is a directory that is not read-only. Nothing is created.
+fn probe_writable(dir: &Path) -> std::io::Result<()> {
+    let existing = dir.ancestors().find(|d| d.exists()).unwrap_or(dir);
+    let metadata = std::fs::metadata(existing)?;
+    if !metadata.is_dir() {
+        return Err(std::io::Error::other(format!(
+            "{} is not a directory",
+            existing.display()
+        )));
+    }
+    if metadata.permissions().readonly() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            format!("{} is read-only", existing.display()),
+        ));
+    }
+    Ok(())
+}
+
+fn check_file_manager(report: &mut Report, config: &AppConfig) {
+    match config.resolve_file_manager() {
+        Ok(FileManagerSource::Configured(fm)) => {
+            report.pass("File manager", &format!("'{}' is installed", fm))
+        }
+        Ok(FileManagerSource::Detected(fm)) => {
+            let program = fm.split_whitespace().next().unwrap_or_default();
+            if which::which(program).is_ok() {
+                report.pass("File manager", &format!("auto-detected '{}'", fm));
+            } else {
+                report.fail("File manager", &format!("auto-detected '{}' is not installed", fm));
+            }
+        }
+        Ok(FileManagerSource::Fallback { missing, name }) => report.warn(
+            "File manager",
+            &format!("'{}' is not installed; '{}' will be used instead", missing, name),
+        ),
+        Err(e) => report.warn(
+            "File manager",
+            &format!("{}; '{}' will be used instead", e, config.get_file_manager()),
+        ),
+    }
+}
+
+fn check_shell_integration(report: &mut Report) {
+    for shell in SHELLS {
+        let name = format!("{} integration", shell);
+        // Only shells the user has set up are worth reporting on
+        let Ok(path) = shell_config_path(shell) else {
+            continue;
+        };
+        if !path.exists() {
+            continue;
+        }
+
+        let installed = std::fs::read_to_string(&path)
+            .map(|content| is_integration_installed(&content))
+            .unwrap_or(false);
+        if installed {
+            report.pass(&name, &format!("installed in {}", path.display()));
+        } else {
+            report.warn(&name, &format!("not found in {}", path.display()));
+        }
+    }
+
+    if std::env::var_os("NAVR_ACTIVE").is_some() {
+        report.pass("Active shell", "navr integration is loaded");
+    } else {
+        report.warn(
+            "Active shell",
+            "NAVR_ACTIVE is not set; run 'navr shell install <shell>' and restart the shell",
+        );
+    }
+}
@@ -1,18 +1,78 @@
 //! Export command - Export configuration to various formats
 
 use anyhow::{Context, Result};
-use owo_colors::OwoColorize;
+use clap::ValueEnum;
+use owo_colors::{OwoColorize, Stream::{Stderr, Stdout}};
 use std::path::PathBuf;
 
 use crate::config::AppConfig;
 
-pub fn execute(config: &AppConfig, format: &str, output: Option<&str>) -> Result<()> {
-    let content = match format.to_lowercase().as_str() {
-        "json" => config.to_json()?,
-        "toml" => toml::to_string_pretty(config)?,
-        _ => anyhow::bail!("Unsupported format: {}. Use json or toml.", format),
+/// Top-level configuration section selectable with `export --only`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportSection {
+    /// Directory shortcuts
+    Shortcuts,
+    /// Custom file managers and the default file manager
+    FileManagers,
+    /// Behavior settings
+    Behavior,
+    /// Shell integration settings
+    Shell,
+    /// Platform-specific settings
+    Platform,
+}
+
+impl ExportSection {
+    /// Check whether a top-level config key belongs to this section
+    pub(crate) fn includes(self, key: &str) -> bool {
+        match self {
+            ExportSection::Shortcuts => key == "shortcuts",
+            ExportSection::FileManagers => {
+                matches!(key, "file_managers" | "default_file_manager" | "remote_opener")
+            }
+            ExportSection::Behavior => key == "behavior",
+            ExportSection::Shell => key == "shell",
+            ExportSection::Platform => key == "platform",
+        }
+    }
+}
+
+pub fn execute(
+    config: &AppConfig,
+    format: &str,
+    output: Option<&str>,
+    only: &[ExportSection],
+) -> Result<()> {
+    let content = if only.is_empty() {
+        match format.to_lowercase().as_str() {
+            "json" => config.to_json()?,
+            "toml" => toml::to_string_pretty(config)?,
+            _ => anyhow::bail!("Unsupported format: {}. Use json or toml.", format),
+        }
+    } else {
+        let table = partial_table(config, only)?;
+        match format.to_lowercase().as_str() {
+            "json" => serde_json::to_string_pretty(&table)?,
+            "toml" => toml::to_string_pretty(&table)?,
+            _ => anyhow::bail!("Unsupported format: {}. Use json or toml.", format),
+        }
     };
 
+    // `-` pipes the content alone to stdout; the summary goes to stderr
+    if output == Some("-") {
+        print!("{}", content);
+        if !content.ends_with('\n') {
+            println!();
+        }
+        eprintln!(
+            "{} Exported {} bytes of {} to stdout",
+            "✓".if_supports_color(Stderr, |t| t.green()),
+            content.len(),
+            format.if_supports_color(Stderr, |t| t.yellow())
+        );
+        return Ok(());
+    }
+
     let output_path = match output {
         Some(path) => PathBuf::from(path),
         None => {
@@ -21,20 +81,79 @@ pub fn execute(config: &AppConfig, format: &str, output: Option<&str>) -> Result
         }
     };
 
+    if config.skip_for_dry_run(|| format!("write {} bytes to {}", content.len(), output_path.display())) {
+        return Ok(());
+    }
+
     std::fs::write(&output_path, &content)
         .with_context(|| format!("Failed to write to {:?}", output_path))?;
 
     println!(
         "{} Configuration exported to: {}",
-        "✓".green(),
-        output_path.display().to_string().cyan()
+        "✓".if_supports_color(Stdout, |t| t.green()),
+        output_path.display().to_string().if_supports_color(Stdout, |t| t.cyan())
     );
     
     println!(
         "  Format: {}, Size: {} bytes",
-        format.yellow(),
-        content.len().to_string().dimmed()
+        format.if_supports_color(Stdout, |t| t.yellow()),
+        content.len().to_string().if_supports_color(Stdout, |t| t.dimmed())
     );
 
     Ok(())
 }
+
+/// Serialize the configuration keeping only the requested sections
+///
+/// The format version is always kept so the snippet can be imported.
+pub(crate) fn partial_table(config: &AppConfig, only: &[ExportSection]) -> Result<toml::Table> {
+    let toml::Value::Table(mut table) = toml::Value::try_from(config)? else {
+        anyhow::bail!("Configuration did not serialize to a table");
+    };
+
+    table.retain(|key, _| key == "version" || only.iter().any(|s| s.includes(key)));
+    Ok(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{MergeStrategy, Shortcut};
+
+    #[test]
+    fn test_partial_export_keeps_only_requested_sections() {
+        let mut config = AppConfig::default();
+        config.shortcuts.insert("work".to_string(), Shortcut::new("/home/user/work"));
+        config.default_file_manager = Some("thunar".to_string());
+
+        let table = partial_table(&config, &[ExportSection::Shortcuts]).unwrap();
+        let mut keys: Vec<_> = table.keys().map(String::as_str).collect();
+        keys.sort();
+        assert_eq!(keys, vec!["shortcuts", "version"]);
+
+        let table = partial_table(&config, &[ExportSection::FileManagers]).unwrap();
+        assert!(table.contains_key("default_file_manager"));
+        assert!(!table.contains_key("shortcuts"));
+    }
+
+    #[test]
+    fn test_merging_partial_export_leaves_other_sections() {
+        let mut source = AppConfig::default();
+        source.shortcuts.insert("work".to_string(), Shortcut::new("/home/user/work"));
+        let table = partial_table(&source, &[ExportSection::Shortcuts]).unwrap();
+        let snippet = toml::to_string_pretty(&table).unwrap();
+
+        let mut config = AppConfig::default();
+        config.behavior.case_sensitive = true;
+        config.shell.max_history = 10;
+        config.default_file_manager = Some("thunar".to_string());
+
+        let incoming = toml::from_str(&snippet).unwrap();
+        config.merge(incoming, MergeStrategy::KeepExisting, false).unwrap();
+
+        assert!(config.shortcuts.contains_key("work"));
+        assert!(config.behavior.case_sensitive);
+        assert_eq!(config.shell.max_history, 10);
+        assert_eq!(config.default_file_manager.as_deref(), Some("thunar"));
+    }
+}
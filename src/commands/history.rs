@@ -0,0 +1,177 @@
+//! History command - Show and manage visited-directory history
+
+use anyhow::Result;
+use clap::Subcommand;
+use inquire::Confirm;
+use owo_colors::{OwoColorize, Stream::Stdout, Style};
+use std::path::{Path, PathBuf};
+
+use crate::config::AppConfig;
+use crate::history;
+use crate::platform;
+
+pub struct HistoryCommand {
+    action: HistorySubCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum HistorySubCommand {
+    /// Show visited directories, most recent first
+    Show {
+        /// Maximum number of entries to show
+        #[arg(short, long, value_name = "N")]
+        limit: Option<usize>,
+
+        /// Collapse repeated visits to the same directory
+        #[arg(short, long)]
+        unique: bool,
+    },
+    /// Clear all recorded history
+    Clear,
+    /// Record a directory visit (defaults to the current directory)
+    Add {
+        /// Directory to record
+        path: Option<String>,
+
+        /// Directory that was left, remembered for `navr jump -`
+        #[arg(long, value_name = "DIR")]
+        from: Option<String>,
+    },
+}
+
+impl HistoryCommand {
+    pub fn new(action: HistorySubCommand) -> Self {
+        Self { action }
+    }
+
+    pub fn execute(&self, config: &AppConfig) -> Result<()> {
+        match &self.action {
+            HistorySubCommand::Show { limit, unique } => self.show_history(*limit, *unique),
+            HistorySubCommand::Clear => self.clear_history(config),
+            HistorySubCommand::Add { path, from } => {
+                self.add_history(config, path.as_deref(), from.as_deref())
+            }
+        }
+    }
+
+    fn show_history(&self, limit: Option<usize>, unique: bool) -> Result<()> {
+        let entries = history::read_history()?;
+
+        if entries.is_empty() {
+            println!("{} No history recorded", "ℹ".if_supports_color(Stdout, |t| t.blue()));
+            return Ok(());
+        }
+
+        println!(
+            "{}",
+            "Navigation History"
+                .if_supports_color(Stdout, |t| t.style(Style::new().bold().underline()))
+        );
+        for (i, entry) in history::recent_entries(&entries, unique, limit).iter().enumerate() {
+            println!(
+                "  {:>4}  {}",
+                (i + 1).to_string().if_supports_color(Stdout, |t| t.dimmed()),
+                entry
+            );
+        }
+
+        Ok(())
+    }
+
+    fn clear_history(&self, config: &AppConfig) -> Result<()> {
+        let confirm = Confirm::new("Are you sure you want to clear all history?")
+            .with_default(false)
+            .prompt()?;
+
+        if confirm {
+            if config.skip_for_dry_run(|| "clear all history".to_string()) {
+                return Ok(());
+            }
+            history::clear_history()?;
+            println!("{} History cleared", "✓".if_supports_color(Stdout, |t| t.green()));
+        } else {
+            println!("{} Cancelled", "✗".if_supports_color(Stdout, |t| t.red()));
+        }
+
+        Ok(())
+    }
+
+    fn add_history(&self, config: &AppConfig, path: Option<&str>, from: Option<&str>) -> Result<()> {
+        // Called from shell hooks on every directory change, so stay silent:
+        // a history that could not be written must not break `cd`
+        if let Some(from) = from.filter(|from| !from.is_empty())
+            && !config.skip_for_dry_run(|| format!("remember {} for 'jump -'", from))
+            && let Err(e) = history::set_last_dir(Path::new(from))
+        {
+            tracing::warn!("Could not remember {} for 'jump -': {:#}", from, e);
+        }
+
+        if !config.shell.track_history {
+            return Ok(());
+        }
+
+        let Some(path) = visited_dir(path, std::env::current_dir) else {
+            tracing::debug!("Not recording a visit: the directory is unknown");
+            return Ok(());
+        };
+        if let Err(e) = record_visit(config, &path) {
+            tracing::warn!("Could not record {} in the history: {:#}", path.display(), e);
+        }
+        Ok(())
+    }
+}
+
+/// The directory `history add` records: `path` when given, else the current
+/// directory; `None` when that is empty or cannot be determined
+///
+/// `path` comes from the shell, which has already expanded it, so `$` and
+/// `~` in it are part of the directory name.
+fn visited_dir(
+    path: Option<&str>,
+    current_dir: impl FnOnce() -> std::io::Result<PathBuf>,
+) -> Option<PathBuf> {
+    let dir = match path {
+        Some(path) => PathBuf::from(path),
+        None => current_dir().ok()?,
+    };
+    (!dir.as_os_str().is_empty()).then_some(dir)
+}
+
+/// Record a visit to `path`, or to the directory holding it when it is a
+/// file, if `shell.track_history` is on and the directory is not ignored
+pub fn record_visit(config: &AppConfig, path: &Path) -> Result<()> {
+    if !config.shell.track_history {
+        return Ok(());
+    }
+
+    let path = platform::normalize_path(path, config.behavior.follow_symlinks);
+    let dir = match path.parent() {
+        Some(parent) if path.is_file() => parent,
+        _ => &path,
+    };
+    if config.shell.ignores(dir) {
+        tracing::debug!("Not recording ignored directory {}", dir.display());
+        return Ok(());
+    }
+    if config.skip_for_dry_run(|| format!("record {} in the history", dir.display())) {
+        return Ok(());
+    }
+
+    history::add_history(dir, config.shell.max_history)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_cwd_records_nothing() {
+        let gone = || Err(std::io::Error::from(std::io::ErrorKind::NotFound));
+        assert_eq!(visited_dir(None, gone), None);
+        // A hook run from a deleted directory can pass an empty $PWD
+        assert_eq!(visited_dir(Some(""), gone), None);
+        assert_eq!(visited_dir(None, || Ok(PathBuf::from("/tmp"))), Some(PathBuf::from("/tmp")));
+        assert_eq!(visited_dir(Some("/srv"), gone), Some(PathBuf::from("/srv")));
+        assert_eq!(visited_dir(Some("/tmp/dir$HOMEx"), gone), Some(PathBuf::from("/tmp/dir$HOMEx")));
+    }
+}
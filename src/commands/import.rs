@@ -1,61 +1,478 @@
 //! Import command - Import configuration from various formats
 
 use anyhow::{Context, Result};
-use owo_colors::OwoColorize;
-use std::path::PathBuf;
+use clap::ValueEnum;
+use inquire::Confirm;
+use owo_colors::{OwoColorize, Stream::Stdout, Style};
+use std::collections::{BTreeSet, HashMap};
+use std::io::{IsTerminal, Read};
+use std::path::{Path, PathBuf};
+use std::process::Command;
 
-use crate::config::AppConfig;
+use crate::commands::export::ExportSection;
+use crate::commands::plural;
+use crate::config::{shortcut_name_for, AppConfig, MergeStrategy, Shortcut};
+use crate::history;
 
-pub fn execute(config: &mut AppConfig, input: &str, merge: bool) -> Result<()> {
-    let input_path = PathBuf::from(input);
-    
-    if !input_path.exists() {
-        anyhow::bail!("Input file not found: {}", input);
-    }
+/// Other tools whose data can be imported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ImportSource {
+    /// zoxide's directory database
+    Zoxide,
+}
 
-    let content = std::fs::read_to_string(&input_path)
-        .with_context(|| format!("Failed to read {:?}", input_path))?;
+/// What replacing one configuration with another would change
+#[derive(Debug, Default, PartialEq)]
+struct ConfigDiff {
+    added: Vec<String>,
+    removed: Vec<String>,
+    changed: Vec<String>,
+    /// Settings whose value differs, as (key, current, incoming)
+    settings: Vec<(String, String, String)>,
+}
 
-    // Detect format from extension
-    let extension = input_path
-        .extension()
-        .and_then(|e| e.to_str())
-        .unwrap_or("toml")
-        .to_lowercase();
+impl ConfigDiff {
+    fn between(current: &AppConfig, incoming: &AppConfig) -> Result<Self> {
+        let mut diff = Self::default();
+        for (name, shortcut) in &incoming.shortcuts {
+            match current.shortcuts.get(name) {
+                None => diff.added.push(name.clone()),
+                Some(existing) if existing != shortcut => diff.changed.push(name.clone()),
+                Some(_) => {}
+            }
+        }
+        diff.removed = current
+            .shortcuts
+            .keys()
+            .filter(|name| !incoming.shortcuts.contains_key(*name))
+            .cloned()
+            .collect();
+        diff.added.sort();
+        diff.removed.sort();
+        diff.changed.sort();
 
-    let imported_config = match extension.as_str() {
-        "json" => AppConfig::from_json(&content)?,
-        "toml" => toml::from_str(&content)
-            .with_context(|| "Failed to parse TOML configuration")?,
-        "yaml" | "yml" => {
-            anyhow::bail!("YAML format not yet implemented. Use json or toml.")
+        let old: HashMap<String, String> = current.all_values()?.into_iter().collect();
+        let new: HashMap<String, String> = incoming.all_values()?.into_iter().collect();
+        let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+        for key in keys {
+            let (old, new) = (old.get(key), new.get(key));
+            if old != new {
+                let value = |v: Option<&String>| v.cloned().unwrap_or_default();
+                diff.settings.push((key.clone(), value(old), value(new)));
+            }
         }
-        _ => {
-            // Try to detect format from content
-            if content.trim().starts_with('{') {
-                AppConfig::from_json(&content)?
-            } else {
-                toml::from_str(&content)
-                    .with_context(|| "Failed to parse configuration")?
+
+        Ok(diff)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
+            && self.settings.is_empty()
+    }
+
+    fn print(&self) {
+        println!(
+            "{}",
+            "Replacing the configuration would:".if_supports_color(Stdout, |t| t.bold())
+        );
+        for (sign, color, verb, names) in [
+            ("+", Style::new().green(), "add", &self.added),
+            ("-", Style::new().red(), "remove", &self.removed),
+            ("~", Style::new().yellow(), "change", &self.changed),
+        ] {
+            if !names.is_empty() {
+                println!(
+                    "  {} {} {} shortcut(s): {}",
+                    sign.if_supports_color(Stdout, |t| t.style(color)),
+                    verb,
+                    names.len(),
+                    names.join(", ")
+                );
             }
         }
+        for (key, old, new) in &self.settings {
+            println!(
+                "  {} {}: {} → {}",
+                "~".if_supports_color(Stdout, |t| t.yellow()),
+                key.if_supports_color(Stdout, |t| t.cyan()),
+                old.if_supports_color(Stdout, |t| t.dimmed()),
+                new
+            );
+        }
+        println!();
+    }
+}
+
+/// Show what a full replace discards and ask before doing it
+///
+/// Without a terminal to ask on, replacing requires `--force`.
+fn confirm_replace(config: &AppConfig, incoming: &AppConfig, force: bool) -> Result<bool> {
+    let diff = ConfigDiff::between(config, incoming)?;
+    if diff.is_empty() {
+        println!(
+            "{} The imported configuration matches the current one",
+            "ℹ".if_supports_color(Stdout, |t| t.blue())
+        );
+        return Ok(false);
+    }
+
+    diff.print();
+    if force || config.is_dry_run() {
+        return Ok(true);
+    }
+    if !std::io::stdin().is_terminal() {
+        anyhow::bail!(
+            "Importing without --merge replaces the whole configuration; pass --force to confirm"
+        );
+    }
+
+    let confirm = Confirm::new("Replace the current configuration?")
+        .with_default(false)
+        .prompt()?;
+    if !confirm {
+        println!("{} Cancelled", "✗".if_supports_color(Stdout, |t| t.red()));
+    }
+    Ok(confirm)
+}
+
+pub fn execute(
+    config: &mut AppConfig,
+    input: &str,
+    merge: bool,
+    overwrite: bool,
+    force: bool,
+) -> Result<()> {
+    let (mut imported_config, sections) = if input == "-" {
+        let mut content = String::new();
+        std::io::stdin()
+            .read_to_string(&mut content)
+            .context("Failed to read configuration from stdin")?;
+        parse_config(&content, sniff_format(&content))?
+    } else {
+        let input_path = PathBuf::from(input);
+
+        if !input_path.exists() {
+            anyhow::bail!("Input file not found: {}", input);
+        }
+
+        let content = std::fs::read_to_string(&input_path)
+            .with_context(|| format!("Failed to read {:?}", input_path))?;
+
+        // Detect format from extension
+        let extension = input_path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("toml")
+            .to_lowercase();
+        parse_config(&content, &extension)?
     };
 
+    // A partial export leaves the sections it does not include untouched
+    keep_missing_sections(&mut imported_config, config, &sections);
+
     if merge {
-        config.merge(imported_config);
-        println!("{} Configuration merged successfully", "✓".green());
+        let strategy = if overwrite {
+            MergeStrategy::PreferIncoming
+        } else {
+            MergeStrategy::KeepExisting
+        };
+        let refused = config.merge(imported_config, strategy, force)?;
+        for name in &refused {
+            println!(
+                "  {} Kept existing shortcut '{}'; pass --force to replace it",
+                "!".if_supports_color(Stdout, |t| t.yellow()),
+                name
+            );
+        }
+        println!(
+            "{} Configuration merged successfully",
+            "✓".if_supports_color(Stdout, |t| t.green())
+        );
     } else {
-        *config = imported_config;
-        println!("{} Configuration imported successfully", "✓".green());
+        if !confirm_replace(config, &imported_config, force)? {
+            return Ok(());
+        }
+        config.replace_settings(imported_config);
+        println!(
+            "{} Configuration imported successfully",
+            "✓".if_supports_color(Stdout, |t| t.green())
+        );
     }
 
     config.save()?;
 
     println!(
         "  Shortcuts: {}, File managers: {}",
-        config.shortcuts.len().to_string().cyan(),
-        config.file_managers.len().to_string().cyan()
+        config.shortcuts.len().to_string().if_supports_color(Stdout, |t| t.cyan()),
+        config.file_managers.len().to_string().if_supports_color(Stdout, |t| t.cyan())
+    );
+
+    Ok(())
+}
+
+/// Parse an imported document written in `format` (a file extension),
+/// along with the sections it contains
+fn parse_config(content: &str, format: &str) -> Result<(AppConfig, Vec<ExportSection>)> {
+    let (mut config, keys): (AppConfig, Vec<String>) = match format {
+        "json" => {
+            let value: serde_json::Value = serde_json::from_str(content)?;
+            let keys = value.as_object().map(|o| o.keys().cloned().collect()).unwrap_or_default();
+            (AppConfig::from_json(content)?, keys)
+        }
+        "toml" => {
+            let table: toml::Table =
+                toml::from_str(content).with_context(|| "Failed to parse TOML configuration")?;
+            let keys = table.keys().cloned().collect();
+            (table.try_into().with_context(|| "Failed to parse TOML configuration")?, keys)
+        }
+        "yaml" | "yml" => {
+            anyhow::bail!("YAML format not yet implemented. Use json or toml.")
+        }
+        _ => return parse_config(content, sniff_format(content)),
+    };
+    config.upgrade();
+
+    let sections = ExportSection::value_variants()
+        .iter()
+        .copied()
+        .filter(|section| keys.iter().any(|key| section.includes(key)))
+        .collect();
+    Ok((config, sections))
+}
+
+/// Copy every section missing from an imported document over from `current`
+fn keep_missing_sections(incoming: &mut AppConfig, current: &AppConfig, present: &[ExportSection]) {
+    for section in ExportSection::value_variants() {
+        if present.contains(section) {
+            continue;
+        }
+        match section {
+            ExportSection::Shortcuts => incoming.shortcuts = current.shortcuts.clone(),
+            ExportSection::FileManagers => {
+                incoming.file_managers = current.file_managers.clone();
+                incoming.default_file_manager = current.default_file_manager.clone();
+                incoming.remote_opener = current.remote_opener.clone();
+            }
+            ExportSection::Behavior => incoming.behavior = current.behavior.clone(),
+            ExportSection::Shell => incoming.shell = current.shell.clone(),
+            ExportSection::Platform => incoming.platform = current.platform.clone(),
+        }
+    }
+}
+
+/// Guess the format of a document without a file extension
+fn sniff_format(content: &str) -> &'static str {
+    let content = content.trim_start();
+    if content.starts_with('{') {
+        "json"
+    } else if content.starts_with("---") {
+        "yaml"
+    } else {
+        "toml"
+    }
+}
+
+/// Import visited directories from another tool into the history,
+/// optionally creating shortcuts for the `shortcuts` highest-ranked ones
+pub fn import_from(config: &mut AppConfig, source: ImportSource, shortcuts: usize) -> Result<()> {
+    let ranked = match source {
+        ImportSource::Zoxide => query_zoxide()?,
+    };
+
+    if ranked.is_empty() {
+        println!("{} No directories to import", "ℹ".if_supports_color(Stdout, |t| t.blue()));
+        return Ok(());
+    }
+
+    // Oldest first, so the highest-ranked directories end up most recent
+    let paths: Vec<String> = ranked
+        .iter()
+        .rev()
+        .map(|(_, path)| path.clone())
+        .filter(|path| !config.shell.ignores(Path::new(path)))
+        .collect();
+    if !config.skip_for_dry_run(|| format!("import {} directories into the history", paths.len())) {
+        history::import_history(&paths, config.shell.max_history)?;
+    }
+    println!(
+        "{} Imported {} directories into the history",
+        "✓".if_supports_color(Stdout, |t| t.green()),
+        paths.len().to_string().if_supports_color(Stdout, |t| t.cyan())
     );
 
+    let mut added = Vec::new();
+    for (_, path) in ranked.iter().take(shortcuts) {
+        if config.shortcuts.values().any(|s| s.path == *path) {
+            continue;
+        }
+        let Some(name) = shortcut_name_for(Path::new(path)) else {
+            continue;
+        };
+        if config.find_shortcut_key(&name).is_some() {
+            println!(
+                "  {} Skipped {}: shortcut '{}' already exists",
+                "!".if_supports_color(Stdout, |t| t.yellow()),
+                path,
+                name
+            );
+            continue;
+        }
+        config.shortcuts.insert(name.clone(), Shortcut::created_now(path.as_str()));
+        added.push((name, path));
+    }
+
+    if !added.is_empty() {
+        config.save()?;
+        println!(
+            "{} Added {} shortcut{}:",
+            "✓".if_supports_color(Stdout, |t| t.green()),
+            added.len().to_string().if_supports_color(Stdout, |t| t.cyan()),
+            plural(added.len())
+        );
+        for (name, path) in added {
+            println!(
+                "  {} → {}",
+                name.if_supports_color(Stdout, |t| t.cyan()),
+                path.if_supports_color(Stdout, |t| t.dimmed())
+            );
+        }
+    }
+
     Ok(())
 }
+
+/// Location of zoxide's database, honoring `$_ZO_DATA_DIR`
+fn zoxide_database() -> Option<PathBuf> {
+    let dir = match std::env::var_os("_ZO_DATA_DIR") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => dirs::data_local_dir()?.join("zoxide"),
+    };
+    Some(dir.join("db.zo"))
+}
+
+/// Ask zoxide for its ranked directories, highest score first
+///
+/// The database itself is a private binary format, so this goes through
+/// `zoxide query --list --score` instead of reading it directly.
+fn query_zoxide() -> Result<Vec<(f64, String)>> {
+    let database = zoxide_database();
+    if let Some(database) = &database
+        && !database.exists()
+    {
+        anyhow::bail!("zoxide database not found at {}", database.display());
+    }
+
+    let output = Command::new("zoxide")
+        .args(["query", "--list", "--score"])
+        .output()
+        .context("Failed to run zoxide. Is it installed?")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        // An empty database is reported as a query without matches
+        if stderr.contains("no match") {
+            return Ok(Vec::new());
+        }
+        anyhow::bail!("zoxide query failed: {}", stderr.trim());
+    }
+
+    Ok(parse_zoxide_scores(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse `zoxide query --list --score` output (`<score> <path>` per line)
+fn parse_zoxide_scores(output: &str) -> Vec<(f64, String)> {
+    let mut ranked: Vec<(f64, String)> = output
+        .lines()
+        .filter_map(|line| {
+            let (score, path) = line.trim_start().split_once(' ')?;
+            let path = path.trim_start();
+            if path.is_empty() {
+                return None;
+            }
+            Some((score.parse().ok()?, path.to_string()))
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| b.0.total_cmp(&a.0));
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::export::partial_table;
+
+    #[test]
+    fn test_config_diff() {
+        let mut current = AppConfig::default();
+        current.shortcuts.insert("work".to_string(), Shortcut::new("/srv/work"));
+        current.shortcuts.insert("docs".to_string(), Shortcut::new("/srv/docs"));
+        current.shortcuts.insert("tmp".to_string(), Shortcut::new("/tmp"));
+
+        let mut incoming = AppConfig::default();
+        incoming.shortcuts.insert("work".to_string(), Shortcut::new("/srv/work"));
+        incoming.shortcuts.insert("docs".to_string(), Shortcut::new("/home/me/docs"));
+        incoming.shortcuts.insert("music".to_string(), Shortcut::new("/srv/music"));
+        incoming.behavior.create_missing = true;
+        incoming.file_managers.insert("fm".to_string(), "thunar".to_string());
+
+        let diff = ConfigDiff::between(&current, &incoming).unwrap();
+        assert_eq!(diff.added, ["music"]);
+        assert_eq!(diff.removed, ["tmp"]);
+        assert_eq!(diff.changed, ["docs"]);
+        assert_eq!(
+            diff.settings,
+            [
+                ("behavior.create_missing".to_string(), "false".to_string(), "true".to_string()),
+                ("file_managers.fm".to_string(), String::new(), "thunar".to_string()),
+            ]
+        );
+
+        assert!(ConfigDiff::between(&current, &current.clone()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_sniff_format() {
+        assert_eq!(sniff_format("  {\"shortcuts\": {}}"), "json");
+        assert_eq!(sniff_format("---\nshortcuts: {}\n"), "yaml");
+        assert_eq!(sniff_format("[shortcuts]\n"), "toml");
+        assert!(parse_config("---\n", "-").is_err());
+    }
+
+    #[test]
+    fn test_partial_import_keeps_missing_sections() {
+        let mut source = AppConfig::default();
+        source.shortcuts.insert("work".to_string(), Shortcut::new("/srv/work"));
+        source.behavior.create_missing = true;
+        let table = partial_table(&source, &[ExportSection::Shortcuts]).unwrap();
+        let snippet = toml::to_string_pretty(&table).unwrap();
+
+        let mut current = AppConfig::default();
+        current.shortcuts.insert("tmp".to_string(), Shortcut::new("/tmp"));
+        current.shell.max_history = 10;
+        current.default_file_manager = Some("thunar".to_string());
+
+        let (mut incoming, sections) = parse_config(&snippet, "toml").unwrap();
+        assert_eq!(sections, [ExportSection::Shortcuts]);
+        keep_missing_sections(&mut incoming, &current, &sections);
+        current.replace_settings(incoming);
+
+        assert!(current.shortcuts.contains_key("work"));
+        assert!(!current.shortcuts.contains_key("tmp"));
+        assert!(!current.behavior.create_missing);
+        assert_eq!(current.shell.max_history, 10);
+        assert_eq!(current.default_file_manager.as_deref(), Some("thunar"));
+    }
+
+    #[test]
+    fn test_parse_zoxide_scores() {
+        let output = "  12.0 /home/user/b\n 120.5 /home/user/my project\nnot a score\n   4.0 /tmp\n";
+        let ranked = parse_zoxide_scores(output);
+
+        let paths: Vec<&str> = ranked.iter().map(|(_, p)| p.as_str()).collect();
+        assert_eq!(paths, ["/home/user/my project", "/home/user/b", "/tmp"]);
+        assert_eq!(ranked[0].0, 120.5);
+    }
+}
@@ -1,17 +1,681 @@
 //! Jump command - Navigate to directories using shortcuts
 
 use anyhow::{Context, Result};
-use owo_colors::OwoColorize;
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use inquire::Select;
+use owo_colors::{OwoColorize, Stream::{Stderr, Stdout}, Style};
+use serde::Serialize;
+use std::collections::HashSet;
 use std::env;
-use std::path::PathBuf;
+use std::fmt;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
 
-use crate::config::AppConfig;
+use crate::cache::ExistenceCache;
+#[cfg(feature = "interactive")]
+use crate::commands::browse;
+use crate::commands::history::record_visit;
+use crate::commands::plural;
+use crate::config::defaults::{DEVELOPMENT_GROUP, SYSTEM_GROUP};
+use crate::config::shortcut::DEFAULT_GROUP;
+use crate::config::{
+    is_remote_uri, numbered_name, shortcut_name_for, validate_shortcut_name, AppConfig,
+    CompletionStyle, ResolutionOrder, SetOutcome, Shortcut,
+};
+use crate::error::NavrError;
+use crate::history;
+use crate::platform::{self, clipboard, resolve_symlinked_file};
+use crate::session;
 
 pub struct JumpCommand {
     target: Option<String>,
     list: bool,
     add: Option<String>,
+    auto_add: bool,
     remove: Option<String>,
+    rename: Option<(String, String)>,
+    back: Option<usize>,
+    alias_of: Option<String>,
+    add_path: Option<String>,
+    git_root: bool,
+    temp: bool,
+    add_from: Option<PathBuf>,
+    resolve: bool,
+    pick: bool,
+    interactive: bool,
+    menu: bool,
+    verbose: bool,
+    copy: bool,
+    track: bool,
+    which: bool,
+    list_options: ListOptions,
+}
+
+/// A listed shortcut and its name
+type ListEntry<'a> = (&'a str, &'a Shortcut);
+
+/// Split listed shortcuts into their groups: the built-in groups first,
+/// then user groups alphabetically, then `DEFAULT_GROUP`
+fn group_entries<'a>(
+    entries: Vec<(&'a str, &'a Shortcut)>,
+) -> Vec<(&'a str, Vec<(&'a str, &'a Shortcut)>)> {
+    let mut groups: Vec<(&str, Vec<(&str, &Shortcut)>)> = Vec::new();
+    for entry in entries {
+        let group = entry.1.group();
+        match groups.iter_mut().find(|(name, _)| name.eq_ignore_ascii_case(group)) {
+            Some((_, members)) => members.push(entry),
+            None => groups.push((group, vec![entry])),
+        }
+    }
+
+    let rank = |group: &str| match group {
+        SYSTEM_GROUP => 0,
+        DEVELOPMENT_GROUP => 1,
+        DEFAULT_GROUP => 3,
+        _ => 2,
+    };
+    groups.sort_by(|(a, _), (b, _)| rank(a).cmp(&rank(b)).then_with(|| a.cmp(b)));
+    groups
+}
+
+/// Ask before replacing shortcut `name`, if `confirm_overwrite` is set
+fn confirm_overwrite(config: &AppConfig, name: &str) -> Result<bool> {
+    if !config.behavior.confirm_overwrite {
+        return Ok(true);
+    }
+
+    print!(
+        "{} Shortcut '{}' already exists. Overwrite? [y/N] ",
+        "?".if_supports_color(Stdout, |t| t.yellow()),
+        name
+    );
+    std::io::stdout().flush()?;
+
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+
+    if !input.trim().eq_ignore_ascii_case("y") {
+        println!("{} Cancelled", "✗".if_supports_color(Stdout, |t| t.red()));
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+/// Score by which the best fuzzy match must beat the runner-up to be jumped
+/// to without asking; skim gives roughly 16 points per matched character
+const CLEAR_WINNER_GAP: i64 = 20;
+
+/// Most candidates listed when a jump target is ambiguous
+const MENU_LIMIT: usize = 9;
+
+/// First stdout line of an ambiguous `jump --menu`, followed by the count
+pub const JUMP_MENU_MARKER: &str = "NAVR_JUMP_MENU:";
+
+/// Recent history entries offered by the picker alongside shortcuts
+const PICK_HISTORY_LIMIT: usize = 10;
+
+/// Filters and format for `jump --list` output
+#[derive(Debug, Default)]
+pub struct ListOptions {
+    /// Only show shortcuts carrying this tag
+    pub tag: Option<String>,
+    /// Only show shortcuts in this group
+    pub group: Option<String>,
+    /// Only show shortcuts whose name or path contains this substring
+    pub filter: Option<String>,
+    /// Emit a JSON array instead of the grouped view
+    pub json: bool,
+    /// Print only target paths, one per line
+    pub paths_only: bool,
+    /// Print only shortcut names, one per line
+    pub names_only: bool,
+    /// Ignore cached existence checks
+    pub no_cache: bool,
+    /// Order of the listed shortcuts
+    pub sort: ListSort,
+    /// Only show shortcuts whose target cannot be jumped to
+    pub broken: bool,
+    /// List every shortcut in one section instead of grouping them
+    pub flat: bool,
+    /// Mark each shortcut with the status of its target
+    pub check: bool,
+}
+
+/// Orderings accepted by `jump --list --sort`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ListSort {
+    /// Grouped by category, alphabetical within each group
+    #[default]
+    Name,
+    /// Most recently used first; never-used shortcuts last
+    Recent,
+    /// Alphabetical by target path
+    Path,
+}
+
+impl ListSort {
+    fn sort(self, entries: &mut [(&str, &Shortcut)]) {
+        match self {
+            ListSort::Name => entries.sort_by_key(|(name, _)| *name),
+            ListSort::Recent => entries.sort_by(|(a_name, a), (b_name, b)| {
+                b.last_used.cmp(&a.last_used).then_with(|| a_name.cmp(b_name))
+            }),
+            ListSort::Path => entries.sort_by(|(a_name, a), (b_name, b)| {
+                a.current_path().cmp(b.current_path()).then_with(|| a_name.cmp(b_name))
+            }),
+        }
+    }
+}
+
+/// Find shortcuts matching a partial name per `shell.completion_style`
+///
+/// Fuzzy matches are ranked best first; prefix and exact matches are sorted
+/// by name.
+pub fn match_shortcuts<'a>(config: &'a AppConfig, query: &str) -> Vec<(&'a String, &'a String)> {
+    let case_sensitive = config.behavior.case_sensitive;
+    let shortcuts = config.shortcuts.iter().map(|(name, s)| (name, s.current_path()));
+
+    let mut matches: Vec<_> = match config.shell.completion_style() {
+        CompletionStyle::Prefix => shortcuts
+            .filter(|(name, _)| {
+                if case_sensitive {
+                    name.starts_with(query)
+                } else {
+                    name.to_lowercase().starts_with(&query.to_lowercase())
+                }
+            })
+            .collect(),
+        CompletionStyle::Exact => shortcuts
+            .filter(|(name, _)| {
+                if case_sensitive {
+                    name.as_str() == query
+                } else {
+                    name.eq_ignore_ascii_case(query)
+                }
+            })
+            .collect(),
+        CompletionStyle::Fuzzy => {
+            return fuzzy_matches(config, query)
+                .into_iter()
+                .map(|(_, name, path)| (name, path))
+                .collect();
+        }
+    };
+
+    matches.sort();
+    matches
+}
+
+/// Fuzzy matches with their scores, highest first
+fn fuzzy_matches<'a>(config: &'a AppConfig, query: &str) -> Vec<(i64, &'a String, &'a String)> {
+    let matcher = if config.behavior.case_sensitive {
+        SkimMatcherV2::default().respect_case()
+    } else {
+        SkimMatcherV2::default().ignore_case()
+    };
+
+    let mut scored: Vec<_> = config
+        .shortcuts
+        .iter()
+        .filter_map(|(name, s)| {
+            matcher.fuzzy_match(name, query).map(|score| (score, name, s.current_path()))
+        })
+        .collect();
+
+    // Highest score first, ties broken alphabetically for stable output
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+    scored
+}
+
+/// What a query naming neither a shortcut nor a path comes closest to
+enum Closest<'a> {
+    Nothing,
+    /// A single match, or a fuzzy match clearly ahead of the rest
+    One(&'a String),
+    /// Matches too close to choose between, best first
+    Several(Vec<(&'a String, &'a String)>),
+}
+
+/// The matches offered after a failed jump, capped by `behavior.max_suggestions`
+fn suggestions<'a, T>(config: &AppConfig, matches: &'a [T]) -> &'a [T] {
+    &matches[..matches.len().min(config.behavior.max_suggestions)]
+}
+
+fn closest_shortcuts<'a>(config: &'a AppConfig, query: &str) -> Closest<'a> {
+    if config.shell.completion_style() != CompletionStyle::Fuzzy {
+        let mut matches = match_shortcuts(config, query);
+        return match matches.as_slice() {
+            [] => Closest::Nothing,
+            [(name, _)] => Closest::One(name),
+            _ => {
+                matches.truncate(MENU_LIMIT);
+                Closest::Several(matches)
+            }
+        };
+    }
+
+    let scored = fuzzy_matches(config, query);
+    match scored.as_slice() {
+        [] => Closest::Nothing,
+        [(_, name, _)] => Closest::One(name),
+        [(best, name, _), (second, ..), ..] if best - second >= CLEAR_WINNER_GAP => {
+            Closest::One(name)
+        }
+        [(best, ..), ..] => Closest::Several(
+            scored
+                .iter()
+                .take_while(|(score, ..)| best - score < CLEAR_WINNER_GAP)
+                .take(MENU_LIMIT)
+                .map(|(_, name, path)| (*name, *path))
+                .collect(),
+        ),
+    }
+}
+
+/// Offer ambiguous matches to the shell integration
+///
+/// Prints `NAVR_JUMP_MENU:<count>`, then one `<name>\t<path>` line per
+/// candidate with aliases resolved. The wrapper numbers them, reads a
+/// choice and changes to that path.
+fn print_menu(config: &AppConfig, candidates: &[(&String, &String)]) {
+    println!("{}{}", JUMP_MENU_MARKER, candidates.len());
+    for (name, path) in candidates {
+        let path = config.get_shortcut(name).ok().flatten().unwrap_or(path);
+        println!("{}\t{}", name, path);
+    }
+}
+
+/// Stamp a shortcut as used; failing to save must not block the jump
+fn record_use(config: &mut AppConfig, name: &str) {
+    if let Err(e) = config.mark_shortcut_used(name) {
+        tracing::debug!("Failed to record use of shortcut '{}': {}", name, e);
+    }
+}
+
+/// An entry offered by the interactive picker
+enum PickChoice {
+    Shortcut { name: String, path: String },
+    History(String),
+}
+
+impl PickChoice {
+    fn path(&self) -> &str {
+        match self {
+            PickChoice::Shortcut { path, .. } | PickChoice::History(path) => path,
+        }
+    }
+}
+
+impl fmt::Display for PickChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PickChoice::Shortcut { name, path } => write!(f, "{}  → {}", name, path),
+            PickChoice::History(path) => write!(f, "{}  (recent)", path),
+        }
+    }
+}
+
+/// List the session-only shortcuts, if there are any
+fn print_session_shortcuts(config: &AppConfig) {
+    let mut shortcuts: Vec<_> = config.session_shortcuts().iter().collect();
+    if shortcuts.is_empty() {
+        return;
+    }
+    shortcuts.sort();
+
+    println!("{}", "Session:".if_supports_color(Stdout, |t| t.bold()));
+    let max_len = shortcuts.iter().map(|(n, _)| n.len()).max().unwrap_or(0);
+    for (name, path) in shortcuts {
+        let padding = " ".repeat(max_len - name.len());
+        println!(
+            "  {}{}  {} {}",
+            name.if_supports_color(Stdout, |t| t.style(Style::new().cyan().bold())),
+            padding,
+            "→".if_supports_color(Stdout, |t| t.dimmed()),
+            path.if_supports_color(Stdout, |t| t.dimmed())
+        );
+    }
+    println!();
+}
+
+/// Shortcuts added from an `--add-from` file and the lines that were left out
+#[derive(Debug, Default)]
+struct ShortcutList {
+    /// Names with their paths as stored
+    added: Vec<(String, String)>,
+    /// Line numbers with the reason each was skipped
+    skipped: Vec<(usize, String)>,
+}
+
+/// Add the shortcuts on `name = path` or `name<TAB>path` lines to `config`
+/// without saving, skipping blank lines and `#` comments
+///
+/// Existing shortcuts are only replaced as `AppConfig::insert_shortcut`
+/// allows; refused names are skipped like invalid lines.
+fn add_shortcut_list(content: &str, config: &mut AppConfig) -> Result<ShortcutList> {
+    let mut list = ShortcutList::default();
+    for (number, line) in content.lines().enumerate().map(|(i, line)| (i + 1, line.trim())) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (name, path) = match check_list_entry(line, config, &list.added) {
+            Ok(entry) => entry,
+            Err(reason) => {
+                list.skipped.push((number, reason));
+                continue;
+            }
+        };
+        let shortcut = Shortcut::created_now(path.as_str());
+        if config.insert_shortcut(&name, shortcut, false)? == SetOutcome::Refused {
+            list.skipped.push((number, format!("shortcut '{}' already exists", name)));
+            continue;
+        }
+        list.added.push((name, path));
+    }
+    Ok(list)
+}
+
+fn check_list_entry(
+    line: &str,
+    config: &AppConfig,
+    added: &[(String, String)],
+) -> std::result::Result<(String, String), String> {
+    let (name, path) = line
+        .split_once('\t')
+        .or_else(|| line.split_once('='))
+        .map(|(name, path)| (name.trim(), path.trim().trim_matches('"')))
+        .ok_or("expected 'name = path'")?;
+    validate_shortcut_name(name).map_err(|e| e.to_string())?;
+    if added.iter().any(|(n, _)| n.eq_ignore_ascii_case(name)) {
+        return Err(format!("shortcut '{}' is listed twice", name));
+    }
+
+    let stored = config.stored_path(path).map_err(|e| e.to_string())?;
+    if !is_remote_uri(&stored) {
+        let target = Path::new(&stored);
+        if !target.exists() {
+            return Err(format!("{} does not exist", stored));
+        }
+        if !target.is_dir() && !config.behavior.allow_file_shortcuts {
+            return Err(format!("{} is not a directory", stored));
+        }
+    }
+    Ok((name.to_string(), stored))
+}
+
+/// Add every valid shortcut listed in `file`, saving the config once
+fn add_from_file(config: &mut AppConfig, file: &Path) -> Result<()> {
+    let content = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    let list = add_shortcut_list(&content, config)?;
+
+    for (name, path) in &list.added {
+        println!(
+            "  {} {} → {}",
+            "+".if_supports_color(Stdout, |t| t.green()),
+            name.if_supports_color(Stdout, |t| t.cyan()),
+            path.if_supports_color(Stdout, |t| t.dimmed())
+        );
+    }
+    for (number, reason) in &list.skipped {
+        println!(
+            "  {} Skipped line {}: {}",
+            "!".if_supports_color(Stdout, |t| t.yellow()),
+            number,
+            reason
+        );
+    }
+    if !list.added.is_empty() {
+        config.save()?;
+    }
+
+    println!(
+        "{} Added {} shortcut{}, skipped {}",
+        "✓".if_supports_color(Stdout, |t| t.green()),
+        list.added.len().to_string().if_supports_color(Stdout, |t| t.cyan()),
+        plural(list.added.len()),
+        list.skipped.len()
+    );
+    Ok(())
+}
+
+/// Add a shortcut that lasts only for the current shell session
+fn add_session_shortcut(config: &AppConfig, name: &str, dir: &Path) -> Result<()> {
+    let path = dir.to_string_lossy();
+    if !config.skip_for_dry_run(|| format!("add session shortcut {} → {}", name, path)) {
+        session::add_shortcut(name, &path)?;
+    }
+    println!(
+        "{} Added session shortcut: {} → {}",
+        "✓".if_supports_color(Stdout, |t| t.green()),
+        name.if_supports_color(Stdout, |t| t.cyan()),
+        path
+    );
+    Ok(())
+}
+
+/// The nearest directory at or above `dir` that holds a `.git` entry
+///
+/// `.git` is a file in worktrees and submodules, so any kind counts.
+fn find_git_root(dir: &Path) -> Option<&Path> {
+    dir.ancestors().find(|d| d.join(".git").exists())
+}
+
+/// The detected home directory, falling back to `HOME` or `USERPROFILE`
+///
+/// Detection can fail in minimal containers even though the variables are set.
+fn home_dir_or_env(
+    detected: Option<PathBuf>,
+    var: impl Fn(&str) -> Option<std::ffi::OsString>,
+) -> Option<PathBuf> {
+    if detected.is_some() {
+        return detected;
+    }
+
+    tracing::warn!("Could not detect the home directory; trying HOME and USERPROFILE");
+    ["HOME", "USERPROFILE"]
+        .into_iter()
+        .filter_map(var)
+        .find(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Why a shortcut cannot be jumped to, or `None` when it can
+fn broken_reason(config: &AppConfig, name: &str) -> Option<&'static str> {
+    let Ok(Some(path)) = config.get_shortcut(name) else {
+        return Some("alias does not resolve");
+    };
+    if is_remote_uri(path) {
+        return None;
+    }
+    let path = Path::new(path);
+
+    let metadata = if config.behavior.follow_symlinks {
+        std::fs::metadata(path)
+    } else {
+        std::fs::symlink_metadata(path)
+    };
+    match metadata {
+        Ok(m) if m.is_dir() => match std::fs::read_dir(path) {
+            Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => Some("permission denied"),
+            _ => None,
+        },
+        Ok(m) if m.file_type().is_symlink() => None,
+        Ok(_) if config.behavior.allow_file_shortcuts => None,
+        Ok(_) => Some("is a file"),
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => Some("permission denied"),
+        Err(_) => Some("missing"),
+    }
+}
+
+/// What a shortcut's target turned out to be, for `jump --list --check`
+/// and the `jump --interactive` browser
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetStatus {
+    Directory,
+    Symlink,
+    File,
+    Missing,
+    /// A remote location, which cannot be checked from here
+    Remote,
+}
+
+impl TargetStatus {
+    /// Stat the target of `name`; unresolvable aliases count as missing
+    pub fn of(config: &AppConfig, name: &str) -> Self {
+        match config.get_shortcut(name) {
+            Ok(Some(path)) => Self::of_path(path),
+            _ => TargetStatus::Missing,
+        }
+    }
+
+    /// Stat `path`, a stored shortcut target or history entry
+    pub fn of_path(path: &str) -> Self {
+        if is_remote_uri(path) {
+            return TargetStatus::Remote;
+        }
+        let path = Path::new(path);
+
+        match (std::fs::symlink_metadata(path), std::fs::metadata(path)) {
+            (Ok(link), Ok(_)) if link.file_type().is_symlink() => TargetStatus::Symlink,
+            (_, Ok(m)) if m.is_dir() => TargetStatus::Directory,
+            (_, Ok(_)) => TargetStatus::File,
+            (_, Err(_)) => TargetStatus::Missing,
+        }
+    }
+
+    pub fn glyph(self) -> &'static str {
+        match self {
+            TargetStatus::Directory => "✓",
+            TargetStatus::Symlink => "↪",
+            TargetStatus::File => "▪",
+            TargetStatus::Missing => "✗",
+            TargetStatus::Remote => "?",
+        }
+    }
+
+    fn marker(self) -> String {
+        let glyph = self.glyph();
+        match self {
+            TargetStatus::Directory => glyph.if_supports_color(Stdout, |t| t.green()).to_string(),
+            TargetStatus::Symlink => glyph.if_supports_color(Stdout, |t| t.yellow()).to_string(),
+            TargetStatus::File => glyph.if_supports_color(Stdout, |t| t.blue()).to_string(),
+            TargetStatus::Missing => glyph.if_supports_color(Stdout, |t| t.red()).to_string(),
+            TargetStatus::Remote => glyph.if_supports_color(Stdout, |t| t.dimmed()).to_string(),
+        }
+    }
+}
+
+/// Where a target's directory was found, as reported by `navr which`
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TargetSource {
+    Shortcut(String),
+    SessionShortcut(String),
+    /// An alias and the shortcut it points at
+    Alias { name: String, of: String },
+    /// A path inside the directory of a shortcut, as in `proj/src`
+    BelowShortcut(String),
+    /// The single close match for a name that is not a shortcut
+    ClosestMatch(String),
+    Path,
+}
+
+impl TargetSource {
+    /// The source of a target that is the name of shortcut `name`
+    fn of_shortcut(config: &AppConfig, name: &str) -> Self {
+        if let Some(key) = config.find_session_key(name) {
+            return TargetSource::SessionShortcut(key.clone());
+        }
+        let Some(key) = config.find_shortcut_key(name) else {
+            return TargetSource::Shortcut(name.to_string());
+        };
+        match config.shortcuts[key].alias_target() {
+            Some(of) => TargetSource::Alias {
+                name: key.clone(),
+                of: of.to_string(),
+            },
+            None => TargetSource::Shortcut(key.clone()),
+        }
+    }
+
+    /// The shortcut a jump through this source counts as a use of
+    ///
+    /// Session shortcuts live only in the environment, so a jump through
+    /// one leaves the persistent shortcut it shadows untouched.
+    fn used_shortcut(&self) -> Option<&str> {
+        match self {
+            TargetSource::Shortcut(name)
+            | TargetSource::Alias { name, .. }
+            | TargetSource::BelowShortcut(name)
+            | TargetSource::ClosestMatch(name) => Some(name),
+            TargetSource::SessionShortcut(_) | TargetSource::Path => None,
+        }
+    }
+}
+
+impl fmt::Display for TargetSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TargetSource::Shortcut(name) => write!(f, "shortcut '{}'", name),
+            TargetSource::SessionShortcut(name) => write!(f, "session shortcut '{}'", name),
+            TargetSource::Alias { name, of } => write!(f, "alias '{}' of '{}'", name, of),
+            TargetSource::BelowShortcut(name) => write!(f, "below shortcut '{}'", name),
+            TargetSource::ClosestMatch(name) => write!(f, "closest match '{}'", name),
+            TargetSource::Path => write!(f, "path"),
+        }
+    }
+}
+
+/// A shortcut as emitted by `jump --list --json`
+#[derive(Debug, Serialize)]
+struct ShortcutEntry<'a> {
+    name: &'a str,
+    path: &'a str,
+    group: &'a str,
+    /// Unknown when the existence cache is unavailable
+    exists: Option<bool>,
+    created_at: Option<DateTime<Utc>>,
+    last_used: Option<DateTime<Utc>>,
+}
+
+impl ListOptions {
+    fn is_filtered(&self) -> bool {
+        self.tag.is_some() || self.group.is_some() || self.filter.is_some()
+    }
+
+    /// Whether the list is split into group sections
+    fn is_grouped(&self) -> bool {
+        self.sort == ListSort::Name && !self.flat
+    }
+
+    /// Check whether a shortcut passes all filters
+    fn matches(&self, name: &str, shortcut: &Shortcut, case_sensitive: bool) -> bool {
+        if let Some(tag) = &self.tag
+            && !shortcut.has_tag(tag)
+        {
+            return false;
+        }
+        if let Some(group) = &self.group
+            && !shortcut.group().eq_ignore_ascii_case(group)
+        {
+            return false;
+        }
+
+        match &self.filter {
+            Some(filter) if case_sensitive => {
+                name.contains(filter.as_str()) || shortcut.current_path().contains(filter.as_str())
+            }
+            Some(filter) => {
+                let filter = filter.to_lowercase();
+                name.to_lowercase().contains(&filter)
+                    || shortcut.current_path().to_lowercase().contains(&filter)
+            }
+            None => true,
+        }
+    }
 }
 
 impl JumpCommand {
@@ -25,10 +689,129 @@ impl JumpCommand {
             target,
             list,
             add,
+            auto_add: false,
             remove,
+            rename: None,
+            back: None,
+            alias_of: None,
+            add_path: None,
+            git_root: false,
+            temp: false,
+            resolve: false,
+            pick: false,
+            interactive: false,
+            menu: false,
+            verbose: false,
+            copy: false,
+            track: true,
+            which: false,
+            add_from: None,
+            list_options: ListOptions::default(),
         }
     }
 
+    /// Add a shortcut named after the bookmarked directory
+    pub fn with_auto_add(mut self, auto_add: bool) -> Self {
+        self.auto_add = auto_add;
+        self
+    }
+
+    /// Rename a shortcut from the first name to the second
+    pub fn with_rename(mut self, rename: Option<(String, String)>) -> Self {
+        self.rename = rename;
+        self
+    }
+
+    /// Jump back this many directories on the back stack
+    pub fn with_back(mut self, back: Option<usize>) -> Self {
+        self.back = back;
+        self
+    }
+
+    /// Make `--add` create an alias of this shortcut instead of using the current directory
+    pub fn with_alias_of(mut self, alias_of: Option<String>) -> Self {
+        self.alias_of = alias_of;
+        self
+    }
+
+    /// Make `--add` bookmark this directory instead of the current one
+    pub fn with_add_path(mut self, add_path: Option<String>) -> Self {
+        self.add_path = add_path;
+        self
+    }
+
+    /// Also report the destination on stderr, which the shell wrappers leave visible
+    pub fn with_verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
+    /// Add the shortcuts listed in this file, one `name = path` line each
+    pub fn with_add_from(mut self, add_from: Option<PathBuf>) -> Self {
+        self.add_from = add_from;
+        self
+    }
+
+    /// Make `--add` create a shortcut for the current shell session only
+    pub fn with_temp(mut self, temp: bool) -> Self {
+        self.temp = temp;
+        self
+    }
+
+    /// Make `--add` bookmark the root of the enclosing git repository
+    pub fn with_git_root(mut self, git_root: bool) -> Self {
+        self.git_root = git_root;
+        self
+    }
+
+    /// Print the resolved target path instead of jumping to it
+    pub fn with_resolve(mut self, resolve: bool) -> Self {
+        self.resolve = resolve;
+        self
+    }
+
+    /// Always choose the destination from an interactive picker
+    pub fn with_pick(mut self, pick: bool) -> Self {
+        self.pick = pick;
+        self
+    }
+
+    /// Report what the target resolves to instead of jumping, for `navr which`
+    pub fn with_which(mut self, which: bool) -> Self {
+        self.which = which;
+        self
+    }
+
+    /// Choose the destination in the full-screen browser, with the target as its filter
+    pub fn with_interactive(mut self, interactive: bool) -> Self {
+        self.interactive = interactive;
+        self
+    }
+
+    /// Print ambiguous matches with `JUMP_MENU_MARKER` for the shell to choose from
+    pub fn with_menu(mut self, menu: bool) -> Self {
+        self.menu = menu;
+        self
+    }
+
+    /// Copy the resolved target path to the clipboard instead of jumping to it
+    pub fn with_copy(mut self, copy: bool) -> Self {
+        self.copy = copy;
+        self
+    }
+
+    /// Record jump destinations in the history when `shell.track_history` is on
+    pub fn with_track(mut self, track: bool) -> Self {
+        self.track = track;
+        self
+    }
+
+    /// Restrict `--list` output to shortcuts passing these filters
+    pub fn with_list_options(mut self, list_options: ListOptions) -> Self {
+        self.list_options = list_options;
+        self
+    }
+
     pub fn execute(&self, config: &mut AppConfig) -> Result<()> {
         // Handle list flag
         if self.list {
@@ -36,27 +819,78 @@ impl JumpCommand {
         }
 
         // Handle add flag
+        if let Some(file) = &self.add_from {
+            return add_from_file(config, file);
+        }
         if let Some(name) = &self.add {
             return self.add_shortcut(config, name);
         }
+        if self.auto_add {
+            return self.add_auto_named(config);
+        }
 
         // Handle remove flag
         if let Some(name) = &self.remove {
             return self.remove_shortcut(config, name);
         }
 
+        // Handle rename flag
+        if let Some((old, new)) = &self.rename {
+            return self.rename_shortcut(config, old, new);
+        }
+
+        // Handle back flag
+        if let Some(count) = self.back {
+            let path = if config.is_dry_run() {
+                history::peek_stack(count)?
+            } else {
+                history::pop_stack(count)?
+            };
+            self.output_path(config, &PathBuf::from(path));
+            return Ok(());
+        }
+
+        // Handle resolve flag
+        if self.resolve {
+            let target = self.target.as_deref().context("No target to resolve")?;
+            let path = self.resolve_target(config, target, false)?;
+            self.output_path(config, &path);
+            return Ok(());
+        }
+
+        if self.which {
+            let target = self.target.as_deref().context("No target to look up")?;
+            return self.which_target(config, target);
+        }
+
+        // Handle pick flag
+        if self.pick {
+            return self.pick_target(config);
+        }
+        if self.interactive {
+            return self.browse_target(config);
+        }
+
+        // Handle copy flag
+        if self.copy {
+            let target = self.target.as_deref().context("No target to copy")?;
+            return self.copy_target(config, target);
+        }
+
         // Handle jump to target
         match &self.target {
             Some(target) => self.jump_to(config, target),
             None => {
-                // No target - jump to home or list shortcuts
+                // No target - jump to home, or pick/list shortcuts
                 if config.behavior.default_to_home {
-                    if let Some(home) = dirs::home_dir() {
-                        self.output_path(&home);
-                        Ok(())
-                    } else {
-                        self.list_shortcuts(config)
-                    }
+                    let home = home_dir_or_env(dirs::home_dir(), |key| std::env::var_os(key))
+                        .context(
+                            "Could not determine the home directory; set HOME or pass a target",
+                        )?;
+                    self.jump_output(config, &home);
+                    Ok(())
+                } else if std::io::stdin().is_terminal() {
+                    self.pick_target(config)
                 } else {
                     self.list_shortcuts(config)
                 }
@@ -64,180 +898,748 @@ impl JumpCommand {
         }
     }
 
-    fn jump_to(&self, config: &AppConfig, target: &str) -> Result<()> {
-        // First, try to resolve as shortcut
-        if let Some(path) = config.get_shortcut(target) {
-            self.output_path(&PathBuf::from(path));
-            return Ok(());
+    fn jump_to(&self, config: &mut AppConfig, target: &str) -> Result<()> {
+        // `-` returns to the directory left last, like `cd -`
+        if target == "-" {
+            let previous = history::last_dir()?.context("No previous directory to return to")?;
+            self.jump_output(config, &PathBuf::from(previous));
+            return Ok(());
+        }
+
+        let path = match self.resolve_jump(config, target) {
+            Ok(path) => path,
+            Err(e) if self.menu && matches!(e.downcast_ref(), Some(NavrError::PathNotFound(_))) => {
+                if let Closest::Several(candidates) = closest_shortcuts(config, target) {
+                    print_menu(config, &candidates);
+                    return Ok(());
+                }
+                return Err(e);
+            }
+            Err(e) => return Err(e),
+        };
+        let path = platform::normalize_path(&path, config.behavior.follow_symlinks);
+        self.jump_output(config, &path);
+        Ok(())
+    }
+
+    /// Print the directory `target` resolves to and where it was found
+    fn which_target(&self, config: &AppConfig, target: &str) -> Result<()> {
+        let (path, source) = self.resolve_source(config, target, true)?;
+        let path = platform::normalize_path(&path, config.behavior.follow_symlinks);
+        println!(
+            "{}  {}",
+            self.path_string(config, &path),
+            format!("({})", source).if_supports_color(Stdout, |t| t.dimmed())
+        );
+        Ok(())
+    }
+
+    /// Copy the path `target` resolves to, printing it when there is no clipboard
+    fn copy_target(&self, config: &AppConfig, target: &str) -> Result<()> {
+        let path = self.copy_text(config, target)?;
+        if config.skip_for_dry_run(|| format!("copy {} to the clipboard", path)) {
+            return Ok(());
+        }
+
+        match clipboard::copy(&path) {
+            Ok(()) => eprintln!(
+                "{} Copied {} to the clipboard",
+                "✓".if_supports_color(Stderr, |t| t.green()),
+                path.if_supports_color(Stderr, |t| t.cyan())
+            ),
+            Err(e) => {
+                eprintln!(
+                    "{} {:#}; printing the path instead",
+                    "!".if_supports_color(Stderr, |t| t.yellow()),
+                    e
+                );
+                println!("{}", path);
+            }
+        }
+        Ok(())
+    }
+
+    /// The absolute path `jump` would go to for `target`
+    fn copy_text(&self, config: &AppConfig, target: &str) -> Result<String> {
+        let path = self.resolve_target(config, target, true)?;
+        let path = platform::normalize_path(&path, config.behavior.follow_symlinks);
+        Ok(self.path_string(config, &path))
+    }
+
+    /// Resolve a jump target and record the use of the shortcut it goes through
+    fn resolve_jump(&self, config: &mut AppConfig, target: &str) -> Result<PathBuf> {
+        let (path, source) = self.resolve_source(config, target, !self.menu)?;
+
+        // `proj/sub` counts as a use of `proj`
+        if let Some(name) = source.used_shortcut() {
+            record_use(config, name);
+        }
+        Ok(path)
+    }
+
+    /// Resolve a shortcut or path to the directory `jump` would enter
+    ///
+    /// Nothing is written to stdout; when `suggest` is set, close shortcut
+    /// names are listed on stderr before failing.
+    fn resolve_target(&self, config: &AppConfig, target: &str, suggest: bool) -> Result<PathBuf> {
+        Ok(self.resolve_source(config, target, suggest)?.0)
+    }
+
+    /// `resolve_target`, also telling where the directory was found
+    fn resolve_source(
+        &self,
+        config: &AppConfig,
+        target: &str,
+        suggest: bool,
+    ) -> Result<(PathBuf, TargetSource)> {
+        if is_remote_uri(target) {
+            anyhow::bail!("Cannot jump to remote location {}; use 'navr open {}'", target, target);
+        }
+
+        // A sigil forces shortcut lookup; otherwise an existing path may win
+        let (target, forced) = match config.strip_shortcut_sigil(target) {
+            Some(name) => (name, true),
+            None => (target, false),
+        };
+        if !forced && config.behavior.resolution_order() == ResolutionOrder::PathFirst {
+            let path = PathBuf::from(shellexpand::full(target)?.to_string());
+            if path.exists() {
+                return Ok((self.resolve_directory(config, path, target)?, TargetSource::Path));
+            }
+        }
+
+        // First, try to resolve as shortcut
+        if let Some(path) = config.get_shortcut(target)? {
+            if is_remote_uri(path) {
+                anyhow::bail!(
+                    "Shortcut '{}' points at remote location {}; use 'navr open {}'",
+                    target,
+                    path,
+                    target
+                );
+            }
+            let path = PathBuf::from(path);
+            let path = resolve_symlinked_file(&path, &config.behavior)?.unwrap_or(path);
+            return Ok((path, TargetSource::of_shortcut(config, target)));
+        }
+
+        // Then as a path below a shortcut (`proj/sub/dir`)
+        if let Some((name, rest)) = target.split_once(std::path::is_separator)
+            && let Some(base) = config.get_shortcut(name)?
+        {
+            if is_remote_uri(base) {
+                anyhow::bail!("Shortcut '{}' points at remote location {}; use 'navr open'", name, base);
+            }
+            let path = PathBuf::from(base).join(rest);
+            if !path.exists() && !config.behavior.create_missing {
+                return Err(NavrError::PathNotFound(path.display().to_string()).into());
+            }
+            let source = TargetSource::BelowShortcut(name.to_string());
+            return Ok((self.resolve_directory(config, path, target)?, source));
+        }
+
+        if forced {
+            return Err(NavrError::ShortcutNotFound(target.to_string()).into());
+        }
+
+        // Try as direct path
+        let expanded = shellexpand::full(target)?.to_string();
+        let path = PathBuf::from(&expanded);
+
+        // `navr which` reports what exists; it never counts on creating it
+        if path.exists() || (config.behavior.create_missing && !self.which) {
+            Ok((self.resolve_directory(config, path, target)?, TargetSource::Path))
+        } else {
+            // Use a clear best match, else suggest the close ones
+            match closest_shortcuts(config, target) {
+                Closest::One(name) => {
+                    eprintln!(
+                        "{} Using closest match '{}'",
+                        "→".if_supports_color(Stderr, |t| t.blue()),
+                        name.if_supports_color(Stderr, |t| t.cyan())
+                    );
+                    let (path, _) = self.resolve_source(config, name, suggest)?;
+                    return Ok((path, TargetSource::ClosestMatch(name.to_string())));
+                }
+                Closest::Several(matches) if suggest && config.behavior.max_suggestions > 0 => {
+                    eprintln!("{} Did you mean:", "?".if_supports_color(Stderr, |t| t.yellow()));
+                    for (name, path) in suggestions(config, &matches) {
+                        eprintln!(
+                            "  {} -> {}",
+                            name.if_supports_color(Stderr, |t| t.cyan()),
+                            path.if_supports_color(Stderr, |t| t.dimmed())
+                        );
+                    }
+                }
+                _ => {}
+            }
+            Err(NavrError::PathNotFound(target.to_string()).into())
+        }
+    }
+
+    /// Check that `path` is a directory, creating it if missing
+    fn resolve_directory(&self, config: &AppConfig, path: PathBuf, target: &str) -> Result<PathBuf> {
+        if path.exists() {
+            if path.is_dir() {
+                Ok(path)
+            } else if let Some(parent) = resolve_symlinked_file(&path, &config.behavior)? {
+                Ok(parent)
+            } else {
+                Err(NavrError::NotADirectory(target.to_string()).into())
+            }
+        } else {
+            // `navr which` only reports directories that exist
+            if self.which {
+                return Err(NavrError::PathNotFound(target.to_string()).into());
+            }
+            if config.skip_for_dry_run(|| format!("create directory {}", path.display())) {
+                return Ok(path);
+            }
+
+            // Create the directory if it doesn't exist
+            platform::create_dir_all(&path, &config.behavior)
+                .with_context(|| format!("Failed to create directory: {}", target))?;
+            eprintln!(
+                "{} Created directory: {}",
+                "✓".if_supports_color(Stderr, |t| t.green()),
+                path.display()
+            );
+            Ok(path)
+        }
+    }
+
+    /// Choose a shortcut or recent directory interactively and jump to it
+    fn pick_target(&self, config: &mut AppConfig) -> Result<()> {
+        let mut shortcuts: Vec<_> = config.shortcuts.iter().collect();
+        shortcuts.sort_by_key(|(name, _)| *name);
+
+        let mut choices: Vec<PickChoice> = shortcuts
+            .into_iter()
+            .filter_map(|(name, _)| {
+                let path = config.get_shortcut(name).ok().flatten()?;
+                Some(PickChoice::Shortcut {
+                    name: name.clone(),
+                    path: path.clone(),
+                })
+            })
+            .collect();
+
+        if config.shell.track_history {
+            let entries = history::read_history().unwrap_or_default();
+            for path in history::recent_entries(&entries, true, Some(PICK_HISTORY_LIMIT)) {
+                if !choices.iter().any(|c| c.path() == path) {
+                    choices.push(PickChoice::History(path.to_string()));
+                }
+            }
+        }
+
+        if choices.is_empty() {
+            anyhow::bail!("No shortcuts or history to pick from");
+        }
+
+        // The prompt renders on stderr, leaving stdout for the chosen path
+        let mut select = Select::new("Jump to:", choices);
+        if let Some(target) = &self.target {
+            select = select.with_starting_filter_input(target);
+        }
+        let choice = select.prompt()?;
+        if let PickChoice::Shortcut { name, .. } = &choice {
+            record_use(config, name);
+        }
+
+        let path = PathBuf::from(choice.path());
+        let path = resolve_symlinked_file(&path, &config.behavior)?.unwrap_or(path);
+        self.jump_output(config, &path);
+        Ok(())
+    }
+
+    /// Choose a shortcut or recent directory in the full-screen browser
+    #[cfg(feature = "interactive")]
+    fn browse_target(&self, config: &mut AppConfig) -> Result<()> {
+        let entry = browse::run(config, self.target.as_deref())?.context("Cancelled")?;
+        if let Some(name) = &entry.name {
+            record_use(config, name);
+        }
+
+        let path = PathBuf::from(&entry.path);
+        let path = resolve_symlinked_file(&path, &config.behavior)?.unwrap_or(path);
+        self.jump_output(config, &path);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "interactive"))]
+    fn browse_target(&self, _config: &mut AppConfig) -> Result<()> {
+        anyhow::bail!("--interactive needs navr built with the interactive feature")
+    }
+
+    fn list_shortcuts(&self, config: &AppConfig) -> Result<()> {
+        if self.list_options.json {
+            return self.list_shortcuts_json(config);
+        }
+        if self.list_options.paths_only || self.list_options.names_only {
+            return self.list_bare(config);
+        }
+
+        if config.shortcuts.is_empty() && config.session_shortcuts().is_empty() {
+            println!("{} No shortcuts configured", "ℹ".if_supports_color(Stdout, |t| t.blue()));
+            println!("Use 'navr jump --add <name>' to add the current directory");
+            return Ok(());
+        }
+
+        let entries = self.listed_shortcuts(config);
+        if self.list_options.broken {
+            return self.list_broken(config, &entries);
+        }
+        if entries.is_empty() && self.list_options.is_filtered() {
+            anyhow::bail!("No shortcuts match the given filters");
+        }
+
+        let sections = self.list_sections(entries);
+        let names: Vec<&str> = sections
+            .iter()
+            .flat_map(|(_, entries)| entries.iter().map(|(n, _)| *n))
+            .collect();
+        let existing = self.existing_targets(config, &names);
+
+        println!(
+            "{}",
+            "Configured Shortcuts:"
+                .if_supports_color(Stdout, |t| t.style(Style::new().bold().underline()))
+        );
+        println!();
+
+        for (group, entries) in &sections {
+            if let Some(group) = group {
+                println!("{}", format!("{}:", group).if_supports_color(Stdout, |t| t.bold()));
+            }
+            self.print_shortcut_list(config, entries, &existing);
+        }
+        print_session_shortcuts(config);
+
+        if self.list_options.is_grouped() {
+            println!();
+        }
+        println!(
+            "{} Use 'navr jump <name>' to navigate",
+            "→".if_supports_color(Stdout, |t| t.dimmed())
+        );
+
+        Ok(())
+    }
+
+    /// Split listed shortcuts into titled group sections, or a single
+    /// untitled section for the flat and sorted views
+    fn list_sections<'a>(
+        &self,
+        entries: Vec<ListEntry<'a>>,
+    ) -> Vec<(Option<&'a str>, Vec<ListEntry<'a>>)> {
+        if !self.list_options.is_grouped() {
+            return vec![(None, entries)];
+        }
+        group_entries(entries)
+            .into_iter()
+            .map(|(group, entries)| (Some(group), entries))
+            .collect()
+    }
+
+    /// Shortcuts passing the list filters, in the requested order
+    fn listed_shortcuts<'a>(&self, config: &'a AppConfig) -> Vec<(&'a str, &'a Shortcut)> {
+        let mut entries: Vec<_> = config
+            .shortcuts
+            .iter()
+            .filter(|(name, shortcut)| {
+                self.list_options.matches(name, shortcut, config.behavior.case_sensitive)
+            })
+            .filter(|(name, _)| {
+                !self.list_options.broken || broken_reason(config, name).is_some()
+            })
+            .map(|(name, shortcut)| (name.as_str(), shortcut))
+            .collect();
+        self.list_options.sort.sort(&mut entries);
+        entries
+    }
+
+    /// Print only names or target paths, one per line, for other programs
+    ///
+    /// Aliases are listed by the path they resolve to, and each path once.
+    fn list_bare(&self, config: &AppConfig) -> Result<()> {
+        let entries = self.listed_shortcuts(config);
+        if entries.is_empty() && self.list_options.is_filtered() {
+            anyhow::bail!("No shortcuts match the given filters");
         }
 
-        // Try as direct path
-        let expanded = shellexpand::full(target)?.to_string();
-        let path = PathBuf::from(&expanded);
-
-        if path.exists() {
-            if path.is_dir() {
-                self.output_path(&path);
-                Ok(())
+        let mut seen = HashSet::new();
+        for (name, shortcut) in entries {
+            let line = if self.list_options.names_only {
+                name
             } else {
-                anyhow::bail!("'{}' is a file, not a directory", target)
-            }
-        } else if config.behavior.create_missing {
-            // Create the directory if it doesn't exist
-            std::fs::create_dir_all(&path)
-                .with_context(|| format!("Failed to create directory: {}", target))?;
-            println!("{} Created directory: {}", "✓".green(), path.display());
-            self.output_path(&path);
-            Ok(())
-        } else {
-            // Try fuzzy matching on shortcuts
-            let matches = self.fuzzy_find_shortcuts(config, target);
-            if !matches.is_empty() {
-                println!("{} Did you mean:", "?".yellow());
-                for (name, path) in matches.iter().take(5) {
-                    println!("  {} -> {}", name.cyan(), path.dimmed());
-                }
+                config.get_shortcut(name).ok().flatten().unwrap_or(shortcut.current_path())
+            };
+            if seen.insert(line) {
+                println!("{}", line);
             }
-            anyhow::bail!("Directory not found: {}", target)
         }
+        Ok(())
     }
 
-    fn list_shortcuts(&self, config: &AppConfig) -> Result<()> {
-        if config.shortcuts.is_empty() {
-            println!("{} No shortcuts configured", "ℹ".blue());
-            println!("Use 'navr jump --add <name>' to add the current directory");
+    /// Print broken shortcuts with the reason each one cannot be used
+    fn list_broken(&self, config: &AppConfig, entries: &[(&str, &Shortcut)]) -> Result<()> {
+        if entries.is_empty() {
+            println!("{} No broken shortcuts", "✓".if_supports_color(Stdout, |t| t.green()));
             return Ok(());
         }
 
-        println!("{}", "Configured Shortcuts:".bold().underline());
+        println!(
+            "{}",
+            "Broken Shortcuts:"
+                .if_supports_color(Stdout, |t| t.style(Style::new().bold().underline()))
+        );
+        println!();
+        let max_len = entries.iter().map(|(n, _)| n.len()).max().unwrap_or(0);
+        for (name, shortcut) in entries {
+            let padding = " ".repeat(max_len - name.len());
+            println!(
+                "  {}{}  {} {}  ({})",
+                name.if_supports_color(Stdout, |t| t.style(Style::new().red().bold())),
+                padding,
+                "→".if_supports_color(Stdout, |t| t.dimmed()),
+                shortcut.current_path().if_supports_color(Stdout, |t| t.red()),
+                broken_reason(config, name).unwrap_or_default()
+            );
+        }
         println!();
+        println!(
+            "{} Use 'navr prune' to remove missing shortcuts",
+            "→".if_supports_color(Stdout, |t| t.dimmed())
+        );
+        Ok(())
+    }
 
-        // Group shortcuts by category
-        let mut system = Vec::new();
-        let mut dev = Vec::new();
-        let mut custom = Vec::new();
+    fn list_shortcuts_json(&self, config: &AppConfig) -> Result<()> {
+        let mut entries: Vec<_> = self
+            .listed_shortcuts(config)
+            .into_iter()
+            .map(|(name, shortcut)| ShortcutEntry {
+                name,
+                // Report aliases by the path they resolve to
+                path: config.get_shortcut(name).ok().flatten().unwrap_or(shortcut.current_path()),
+                group: shortcut.group(),
+                exists: None,
+                created_at: shortcut.created_at,
+                last_used: shortcut.last_used,
+            })
+            .collect();
+        if entries.is_empty() && self.list_options.is_filtered() {
+            anyhow::bail!("No shortcuts match the given filters");
+        }
 
-        for (name, path) in &config.shortcuts {
-            let entry = (name.as_str(), path.as_str());
-            match name.as_str() {
-                "home" | "~" | "h" | "desktop" | "desk" | "docs" | "documents" 
-                | "downloads" | "dl" | "pictures" | "pics" | "music" | "videos" 
-                | "config" | "cfg" => system.push(entry),
-                "dev" | "projects" | "proj" | "workspace" | "ws" | "repos" 
-                | "github" | "gh" => dev.push(entry),
-                _ => custom.push(entry),
+        let names: Vec<&str> = entries.iter().map(|e| e.name).collect();
+        if let Some(existing) = self.existing_targets(config, &names) {
+            for entry in &mut entries {
+                entry.exists = Some(existing.contains(entry.name));
             }
         }
 
-        // Print system shortcuts
-        if !system.is_empty() {
-            println!("{}", "System:".bold());
-            self.print_shortcut_list(&system);
-        }
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        Ok(())
+    }
 
-        // Print dev shortcuts
-        if !dev.is_empty() {
-            println!("{}", "Development:".bold());
-            self.print_shortcut_list(&dev);
-        }
+    /// Names of shortcuts whose target exists, checked through the existence cache
+    ///
+    /// Without a data directory for the cache, no status is reported.
+    fn existing_targets<'a>(&self, config: &AppConfig, names: &[&'a str]) -> Option<HashSet<&'a str>> {
+        let resolved: Vec<(&str, &str)> = names
+            .iter()
+            .filter_map(|name| {
+                let path = config.get_shortcut(name).ok().flatten()?;
+                Some((*name, path.as_str()))
+            })
+            .collect();
 
-        // Print custom shortcuts
-        if !custom.is_empty() {
-            println!("{}", "Custom:".bold());
-            self.print_shortcut_list(&custom);
+        let mut cache = match ExistenceCache::load(self.list_options.no_cache) {
+            Ok(cache) => cache,
+            Err(e) => {
+                tracing::debug!("Skipping existence checks: {}", e);
+                return None;
+            }
+        };
+        // Remote locations cannot be checked from here, so count them as present
+        let paths: Vec<&str> = resolved
+            .iter()
+            .map(|(_, path)| *path)
+            .filter(|path| !is_remote_uri(path))
+            .collect();
+        cache.check(&paths, config.behavior.follow_symlinks);
+        if let Err(e) = cache.save() {
+            tracing::debug!("Failed to save existence cache: {}", e);
         }
 
-        println!();
-        println!(
-            "{} Use 'navr jump <name>' to navigate",
-            "→".dimmed()
-        );
-
-        Ok(())
+        Some(
+            resolved
+                .into_iter()
+                .filter(|(_, path)| is_remote_uri(path) || cache.exists(path))
+                .map(|(name, _)| name)
+                .collect(),
+        )
     }
 
-    fn print_shortcut_list(&self, shortcuts: &[(&str, &str)]) {
+    fn print_shortcut_list(
+        &self,
+        config: &AppConfig,
+        shortcuts: &[(&str, &Shortcut)],
+        existing: &Option<HashSet<&str>>,
+    ) {
         let max_len = shortcuts.iter().map(|(n, _)| n.len()).max().unwrap_or(0);
         
-        for (name, path) in shortcuts {
+        for (name, shortcut) in shortcuts {
             let padding = " ".repeat(max_len - name.len());
-            println!(
-                "  {}{}  {} {}",
-                name.cyan().bold(),
+            let status = if self.list_options.check {
+                format!("{} ", TargetStatus::of(config, name).marker())
+            } else {
+                String::new()
+            };
+            let mut line = format!(
+                "  {}{}{}  {} {}",
+                status,
+                name.if_supports_color(Stdout, |t| t.style(Style::new().cyan().bold())),
                 padding,
-                "→".dimmed(),
-                path.dimmed()
+                "→".if_supports_color(Stdout, |t| t.dimmed()),
+                shortcut.current_path().if_supports_color(Stdout, |t| t.dimmed())
             );
+            if let Some(description) = &shortcut.description {
+                line.push_str(&format!(
+                    "  {}",
+                    description.if_supports_color(Stdout, |t| t.italic())
+                ));
+            }
+            if !shortcut.tags.is_empty() {
+                line.push_str(&format!(
+                    "  [{}]",
+                    shortcut.tags.join(", ").if_supports_color(Stdout, |t| t.yellow())
+                ));
+            }
+            if self.list_options.sort == ListSort::Recent {
+                let used = shortcut
+                    .last_used
+                    .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string());
+                let used = format!("used {}", used.as_deref().unwrap_or("never"));
+                line.push_str(&format!("  {}", used.if_supports_color(Stdout, |t| t.dimmed())));
+            }
+            if !self.list_options.check
+                && existing.as_ref().is_some_and(|existing| !existing.contains(name))
+            {
+                line.push_str(&format!("  {}", "(missing)".if_supports_color(Stdout, |t| t.red())));
+            }
+            println!("{}", line);
         }
         println!();
     }
 
     fn add_shortcut(&self, config: &mut AppConfig, name: &str) -> Result<()> {
-        let current_dir = env::current_dir()
-            .context("Failed to get current directory")?;
-
-        // Check if shortcut already exists
-        if config.shortcuts.contains_key(name) && config.behavior.confirm_overwrite {
-            print!(
-                "{} Shortcut '{}' already exists. Overwrite? [y/N] ",
-                "?".yellow(),
-                name
+        validate_shortcut_name(name)?;
+
+        let target_dir = self.add_target(config)?;
+        if self.temp {
+            return add_session_shortcut(config, name, &target_dir);
+        }
+
+        if let Some(target) = &self.alias_of {
+            if config.shortcuts.contains_key(name) && !confirm_overwrite(config, name)? {
+                return Ok(());
+            }
+            config.set_alias(name, target)?;
+            println!(
+                "{} Added alias: {} → {}",
+                "✓".if_supports_color(Stdout, |t| t.green()),
+                name.if_supports_color(Stdout, |t| t.cyan()),
+                config.shortcuts[name].alias_target().unwrap_or_default()
             );
-            
-            let mut input = String::new();
-            std::io::stdin().read_line(&mut input)?;
-            
-            if !input.trim().eq_ignore_ascii_case("y") {
-                println!("{} Cancelled", "✗".red());
+            return Ok(());
+        }
+
+        let path = target_dir.to_string_lossy();
+        if config.set_shortcut(name, &path, false)? == SetOutcome::Refused {
+            if !confirm_overwrite(config, name)? {
                 return Ok(());
             }
+            config.set_shortcut(name, &path, true)?;
         }
 
-        config.set_shortcut(name, current_dir.to_str().unwrap())?;
-        
         println!(
             "{} Added shortcut: {} → {}",
-            "✓".green(),
-            name.cyan(),
-            current_dir.display()
+            "✓".if_supports_color(Stdout, |t| t.green()),
+            name.if_supports_color(Stdout, |t| t.cyan()),
+            config.shortcuts[name].path
         );
 
         Ok(())
     }
 
+    /// Add the directory under its own lowercased name, numbered on clashes
+    fn add_auto_named(&self, config: &mut AppConfig) -> Result<()> {
+        if self.alias_of.is_some() {
+            anyhow::bail!("--alias-of needs an explicit shortcut name for --add");
+        }
+
+        let target_dir = self.add_target(config)?;
+        if self.temp {
+            let name = shortcut_name_for(&target_dir).with_context(|| {
+                format!("Cannot derive a shortcut name from {}; pass one to --add", target_dir.display())
+            })?;
+            return add_session_shortcut(config, &name, &target_dir);
+        }
+        let path = target_dir.to_string_lossy();
+        if let Some((name, _)) = config.shortcuts.iter().find(|(_, s)| s.path == path) {
+            println!(
+                "{} Already bookmarked as {}",
+                "ℹ".if_supports_color(Stdout, |t| t.blue()),
+                name.if_supports_color(Stdout, |t| t.cyan())
+            );
+            return Ok(());
+        }
+
+        let base = shortcut_name_for(&target_dir).with_context(|| {
+            format!("Cannot derive a shortcut name from {}; pass one to --add", path)
+        })?;
+        let name = numbered_name(&base, |name| config.find_shortcut_key(name).is_some());
+        config.set_shortcut(&name, &path, false)?;
+
+        println!(
+            "{} Added shortcut: {} → {}",
+            "✓".if_supports_color(Stdout, |t| t.green()),
+            name.if_supports_color(Stdout, |t| t.cyan()),
+            config.shortcuts[&name].path
+        );
+        Ok(())
+    }
+
+    /// Directory bookmarked by `--add`: `--add-path` if given, else the current
+    /// directory, moved up to its repository root with `--git-root`
+    fn add_target(&self, config: &AppConfig) -> Result<PathBuf> {
+        let dir = self.add_dir(config)?;
+        if !self.git_root {
+            return Ok(dir);
+        }
+        if is_remote_uri(&dir.to_string_lossy()) {
+            anyhow::bail!("--git-root cannot be used with a remote location");
+        }
+        find_git_root(&dir)
+            .map(Path::to_path_buf)
+            .with_context(|| format!("No git repository found at or above {}", dir.display()))
+    }
+
+    fn add_dir(&self, config: &AppConfig) -> Result<PathBuf> {
+        let Some(path) = &self.add_path else {
+            return env::current_dir().context("Failed to get current directory");
+        };
+        if is_remote_uri(path) {
+            return Ok(PathBuf::from(path));
+        }
+
+        let expanded = PathBuf::from(shellexpand::full(path)?.to_string());
+        if !expanded.exists() {
+            if !config.behavior.create_missing {
+                anyhow::bail!("Directory does not exist: {}", expanded.display());
+            }
+            if !config.skip_for_dry_run(|| format!("create directory {}", expanded.display())) {
+                platform::create_dir_all(&expanded, &config.behavior)
+                    .with_context(|| format!("Failed to create directory: {}", path))?;
+                eprintln!(
+                    "{} Created directory: {}",
+                    "✓".if_supports_color(Stderr, |t| t.green()),
+                    expanded.display()
+                );
+            }
+        }
+
+        Ok(platform::normalize_path(&expanded, config.behavior.follow_symlinks))
+    }
+
     fn remove_shortcut(&self, config: &mut AppConfig, name: &str) -> Result<()> {
         if config.remove_shortcut(name)? {
-            println!("{} Removed shortcut: {}", "✓".green(), name.cyan());
+            println!(
+                "{} Removed shortcut: {}",
+                "✓".if_supports_color(Stdout, |t| t.green()),
+                name.if_supports_color(Stdout, |t| t.cyan())
+            );
         } else {
-            println!("{} Shortcut '{}' not found", "✗".red(), name);
+            println!(
+                "{} Shortcut '{}' not found",
+                "✗".if_supports_color(Stdout, |t| t.red()),
+                name
+            );
         }
         Ok(())
     }
 
-    fn fuzzy_find_shortcuts<'a>(&self, config: &'a AppConfig, target: &str) -> Vec<(&'a String, &'a String)> {
-        let target_lower = target.to_lowercase();
-        
-        config
-            .shortcuts
-            .iter()
-            .filter(|(name, _)| {
-                let name_lower = name.to_lowercase();
-                name_lower.contains(&target_lower) || 
-                target_lower.contains(&name_lower)
-            })
-            .collect()
+    fn rename_shortcut(&self, config: &mut AppConfig, old: &str, new: &str) -> Result<()> {
+        let old_key = config
+            .find_shortcut_key(old)
+            .cloned()
+            .ok_or_else(|| NavrError::ShortcutNotFound(old.to_string()))?;
+
+        // Changing only the case of a name does not replace anything
+        if config.find_shortcut_key(new).is_some_and(|key| *key != old_key)
+            && !confirm_overwrite(config, new)?
+        {
+            return Ok(());
+        }
+
+        config.rename_shortcut(&old_key, new)?;
+        println!(
+            "{} Renamed shortcut: {} → {}",
+            "✓".if_supports_color(Stdout, |t| t.green()),
+            old_key,
+            new.if_supports_color(Stdout, |t| t.cyan())
+        );
+        Ok(())
+    }
+
+    /// Emit a jump destination, remembering the directory being left
+    fn jump_output(&self, config: &AppConfig, path: &Path) {
+        if let Ok(current) = env::current_dir()
+            && path.canonicalize().map_or(true, |p| p != current)
+            && !config.skip_for_dry_run(|| format!("push {} onto the back stack", current.display()))
+        {
+            if let Err(e) = history::push_stack(&current, config.shell.max_stack) {
+                tracing::debug!("Failed to record {} on the back stack: {}", current.display(), e);
+            }
+            if let Err(e) = history::set_last_dir(&current) {
+                tracing::debug!("Failed to remember {} for 'jump -': {}", current.display(), e);
+            }
+        }
+        if self.track
+            && let Err(e) = record_visit(config, path)
+        {
+            tracing::debug!("Failed to record {} in the history: {}", path.display(), e);
+        }
+
+        self.output_path(config, path);
     }
 
-    fn output_path(&self, path: &PathBuf) {
+    fn output_path(&self, config: &AppConfig, path: &Path) {
         // Output the path for shell integration to capture
         // The shell wrapper will use this to actually change directory
-        // Use a special marker to indicate this is a jump request
-        
+        let path_str = self.path_string(config, path);
+
+        // Nothing else may reach stdout: the wrappers cd to whatever it holds
+        if self.verbose {
+            eprintln!(
+                "{} Jumping to {}",
+                "→".if_supports_color(Stderr, |t| t.blue()),
+                path_str.if_supports_color(Stderr, |t| t.cyan())
+            );
+        }
+        println!("{}", path_str);
+    }
+
+    /// `path` as handed to the shell: absolute, in the platform's format
+    fn path_string(&self, config: &AppConfig, path: &Path) -> String {
         // On Windows, handle path canonicalization and formatting
-        let path_str = if cfg!(windows) {
+        if cfg!(windows) {
             // For Windows, use absolute path without canonicalize to avoid \\?\ prefix
             let absolute_path = if path.is_absolute() {
-                path.clone()
+                path.to_path_buf()
             } else {
                 // Convert relative path to absolute
                 std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")).join(path)
@@ -246,10 +1648,478 @@ impl JumpCommand {
             // Convert to string and normalize separators
             absolute_path.to_string_lossy().replace('/', "\\")
         } else {
-            // For Unix-like systems, use canonicalize
-            path.canonicalize().unwrap_or(path.clone()).to_string_lossy().to_string()
+            // For Unix-like systems, resolve symlinks only if configured to
+            platform::normalize_path(path, config.behavior.follow_symlinks)
+                .to_string_lossy()
+                .to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_filter_matches_name() {
+        let options = ListOptions {
+            filter: Some("PROJ".to_string()),
+            ..Default::default()
         };
-        
-        println!("{}", path_str);
+        let shortcut = Shortcut::new("/home/user/code");
+
+        assert!(options.matches("myproj", &shortcut, false));
+        assert!(!options.matches("myproj", &shortcut, true));
+        assert!(!options.matches("docs", &shortcut, false));
+    }
+
+    fn style_fixture(style: &str) -> AppConfig {
+        let mut config = AppConfig::default();
+        config.shell.completion_style = style.to_string();
+        for name in ["pictures", "projects", "proj", "docs"] {
+            config.shortcuts.insert(name.to_string(), Shortcut::new(format!("/home/user/{}", name)));
+        }
+        config
+    }
+
+    fn matched_names<'a>(config: &'a AppConfig, query: &str) -> Vec<&'a str> {
+        match_shortcuts(config, query)
+            .into_iter()
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_closest_match_clear_winner() {
+        let mut config = style_fixture("fuzzy");
+        config.shortcuts.insert("navr".to_string(), Shortcut::new("/home/user/navr"));
+
+        // Only one shortcut matches at all
+        assert!(matches!(closest_shortcuts(&config, "dcs"), Closest::One(name) if name == "docs"));
+        // "projects" matches every letter, well ahead of "proj" and "pictures"
+        assert!(matches!(closest_shortcuts(&config, "prjects"), Closest::One(name) if name == "projects"));
+
+        let cmd = JumpCommand::new(None, false, None, None);
+        let path = cmd.resolve_target(&config, "nvr", false).unwrap();
+        assert_eq!(path, PathBuf::from("/home/user/navr"));
+    }
+
+    #[test]
+    fn test_closest_match_ambiguous() {
+        let config = style_fixture("fuzzy");
+        let Closest::Several(candidates) = closest_shortcuts(&config, "pj") else {
+            panic!("expected several close matches");
+        };
+        let names: Vec<&str> = candidates.iter().map(|(name, _)| name.as_str()).collect();
+        assert!(names.contains(&"proj") && names.contains(&"projects"));
+        assert!(!names.contains(&"docs"));
+
+        let cmd = JumpCommand::new(None, false, None, None);
+        let err = cmd.resolve_target(&config, "pj", false).unwrap_err();
+        assert!(matches!(err.downcast_ref(), Some(NavrError::PathNotFound(_))));
+
+        // Non-fuzzy styles treat every extra match as ambiguous
+        let config = style_fixture("prefix");
+        assert!(matches!(closest_shortcuts(&config, "pro"), Closest::Several(c) if c.len() == 2));
+        assert!(matches!(closest_shortcuts(&config, "pi"), Closest::One(name) if name == "pictures"));
+    }
+
+    #[test]
+    fn test_target_source_follows_case_sensitivity() {
+        let mut config = AppConfig::default();
+        config.shortcuts.insert("proj".to_string(), Shortcut::new("/home/user/proj"));
+        config.shortcuts.insert("p".to_string(), Shortcut::alias_of("proj"));
+        config.set_session_shortcuts(std::collections::HashMap::from([(
+            "Proj".to_string(),
+            "/tmp/scratch".to_string(),
+        )]));
+
+        assert_eq!(
+            TargetSource::of_shortcut(&config, "proj"),
+            TargetSource::SessionShortcut("Proj".to_string())
+        );
+        assert_eq!(
+            TargetSource::of_shortcut(&config, "P"),
+            TargetSource::Alias { name: "p".to_string(), of: "proj".to_string() }
+        );
+
+        config.behavior.case_sensitive = true;
+        assert_eq!(TargetSource::of_shortcut(&config, "proj"), TargetSource::Shortcut("proj".to_string()));
+        assert_eq!(config.get_shortcut("proj").unwrap().map(String::as_str), Some("/home/user/proj"));
+    }
+
+    #[test]
+    fn test_max_suggestions_caps_did_you_mean() {
+        let mut config = style_fixture("prefix");
+        config.shortcuts.insert("pics".to_string(), Shortcut::new("/home/user/pics"));
+        let Closest::Several(candidates) = closest_shortcuts(&config, "p") else {
+            panic!("expected several close matches");
+        };
+        assert_eq!(suggestions(&config, &candidates).len(), 4);
+
+        let mut capped = config.clone();
+        capped.set_dry_run(true);
+        capped.set_value("behavior.max_suggestions", "2").unwrap();
+        assert_eq!(suggestions(&capped, &candidates).len(), 2);
+        assert!(capped.set_value("behavior.max_suggestions", "-1").is_err());
+    }
+
+    #[test]
+    fn test_fuzzy_style_ranks_best_first() {
+        let config = style_fixture("fuzzy");
+        let matches = matched_names(&config, "prj");
+
+        assert_eq!(matches.first(), Some(&"proj"));
+        assert!(matches.contains(&"projects"));
+        let pictures = matches.iter().position(|n| *n == "pictures");
+        assert!(pictures.is_none_or(|i| i > 1));
+        assert!(!matches.contains(&"docs"));
+    }
+
+    #[test]
+    fn test_prefix_style_matches_leading_text() {
+        let config = style_fixture("prefix");
+
+        assert_eq!(matched_names(&config, "pro"), vec!["proj", "projects"]);
+        assert_eq!(matched_names(&config, "PIC"), vec!["pictures"]);
+        assert!(matched_names(&config, "prj").is_empty());
+    }
+
+    #[test]
+    fn test_exact_style_disables_suggestions() {
+        let config = style_fixture("exact");
+
+        assert_eq!(matched_names(&config, "proj"), vec!["proj"]);
+        assert!(matched_names(&config, "pro").is_empty());
+        assert!(matched_names(&config, "prj").is_empty());
+    }
+
+    #[test]
+    fn test_flat_list_ignores_groups() {
+        let toml_str = r#"
+            [shortcuts]
+            zed = "/opt/zed"
+            home = { path = "/home/user", group = "System" }
+            api = { path = "/srv/api", group = "Development" }
+        "#;
+        let config: AppConfig = toml::from_str(toml_str).unwrap();
+
+        let grouped = JumpCommand::new(None, true, None, None);
+        assert_eq!(grouped.list_sections(grouped.listed_shortcuts(&config)).len(), 3);
+
+        let cmd = JumpCommand::new(None, true, None, None).with_list_options(ListOptions {
+            flat: true,
+            ..Default::default()
+        });
+        let sections = cmd.list_sections(cmd.listed_shortcuts(&config));
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].0, None);
+        let names: Vec<&str> = sections[0].1.iter().map(|(n, _)| *n).collect();
+        assert_eq!(names, ["api", "home", "zed"]);
+    }
+
+    #[test]
+    fn test_list_groups_by_assigned_group() {
+        let toml_str = r#"
+            [shortcuts]
+            scratch = "/tmp/scratch"
+            home = { path = "/home/user", group = "System" }
+            docs-archive = { path = "/srv/archive", group = "Archive" }
+            old-docs = { path = "/srv/old", group = "archive" }
+        "#;
+        let config: AppConfig = toml::from_str(toml_str).unwrap();
+
+        let cmd = JumpCommand::new(None, true, None, None);
+        let groups: Vec<(&str, Vec<&str>)> = group_entries(cmd.listed_shortcuts(&config))
+            .into_iter()
+            .map(|(group, entries)| (group, entries.into_iter().map(|(n, _)| n).collect()))
+            .collect();
+        assert_eq!(
+            groups,
+            [
+                ("System", vec!["home"]),
+                ("Archive", vec!["docs-archive", "old-docs"]),
+                ("Custom", vec!["scratch"]),
+            ]
+        );
+
+        let cmd = cmd.with_list_options(ListOptions {
+            group: Some("ARCHIVE".to_string()),
+            ..Default::default()
+        });
+        let names: Vec<&str> = cmd.listed_shortcuts(&config).into_iter().map(|(n, _)| n).collect();
+        assert_eq!(names, ["docs-archive", "old-docs"]);
+    }
+
+    #[test]
+    fn test_list_filter_matches_path() {
+        let options = ListOptions {
+            filter: Some("code".to_string()),
+            ..Default::default()
+        };
+
+        assert!(options.matches("work", &Shortcut::new("/home/user/code/work"), true));
+        assert!(!options.matches("work", &Shortcut::new("/home/user/work"), true));
+    }
+
+    fn relative_jump_fixture(name: &str) -> (PathBuf, AppConfig) {
+        let root = std::env::temp_dir().join(format!("navr-rel-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(root.join("navr").join("src")).unwrap();
+
+        let mut config = AppConfig::default();
+        config.shortcuts.insert("proj".to_string(), Shortcut::new(root.to_string_lossy()));
+        (root, config)
+    }
+
+    #[test]
+    fn test_copy_resolves_like_jump() {
+        let (root, config) = relative_jump_fixture("copy");
+        let cmd = JumpCommand::new(Some("proj/navr".to_string()), false, None, None).with_copy(true);
+
+        let text = cmd.copy_text(&config, "proj/navr").unwrap();
+        let expected = platform::normalize_path(&root.join("navr"), config.behavior.follow_symlinks);
+        assert_eq!(Path::new(&text), expected);
+        assert!(cmd.copy_text(&config, "proj/nope").is_err());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_relative_jump_two_levels() {
+        let (root, config) = relative_jump_fixture("two");
+        let cmd = JumpCommand::new(None, false, None, None);
+
+        let path = cmd.resolve_target(&config, "proj/navr", false).unwrap();
+        assert_eq!(path, root.join("navr"));
+        assert!(cmd.resolve_target(&config, "proj/missing", false).is_err());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_errors_have_kinds() {
+        let (root, config) = relative_jump_fixture("kinds");
+        std::fs::write(root.join("notes.txt"), "").unwrap();
+        let cmd = JumpCommand::new(None, false, None, None);
+
+        let err = cmd.resolve_target(&config, "proj/missing", false).unwrap_err();
+        assert!(matches!(err.downcast_ref(), Some(NavrError::PathNotFound(_))));
+        let file = root.join("notes.txt").to_string_lossy().to_string();
+        let err = cmd.resolve_target(&config, &file, false).unwrap_err();
+        assert!(matches!(err.downcast_ref(), Some(NavrError::NotADirectory(_))));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_remote_shortcut_is_not_jumped_to() {
+        let mut config = AppConfig::default();
+        config.shortcuts.insert("box".to_string(), Shortcut::new("ssh://build-box/srv/app"));
+        let cmd = JumpCommand::new(None, false, None, None);
+
+        let err = cmd.resolve_target(&config, "box", false).unwrap_err();
+        assert!(err.to_string().contains("navr open box"));
+        assert!(cmd.resolve_target(&config, "box/logs", false).is_err());
+        assert_eq!(broken_reason(&config, "box"), None);
+    }
+
+    #[test]
+    fn test_relative_jump_three_levels() {
+        let (root, config) = relative_jump_fixture("three");
+        let cmd = JumpCommand::new(None, false, None, None);
+
+        let path = cmd.resolve_target(&config, "proj/navr/src", false).unwrap();
+        assert_eq!(path, root.join("navr").join("src"));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_jump_updates_last_used() {
+        let (root, mut config) = relative_jump_fixture("last-used");
+        config.set_file_path(root.join("config.toml"));
+        config.save().unwrap();
+        let cmd = JumpCommand::new(None, false, None, None);
+        assert!(config.shortcuts["proj"].last_used.is_none());
+
+        cmd.resolve_jump(&mut config, "proj/navr").unwrap();
+        let used = config.shortcuts["proj"].last_used.expect("last_used set by jump");
+
+        let loaded = AppConfig::load_from_path(root.join("config.toml")).unwrap();
+        assert_eq!(loaded.shortcuts["proj"].last_used, Some(used));
+
+        // The use is recorded on the shortcut the target resolved through
+        config.shortcuts.get_mut("proj").unwrap().last_used = None;
+        cmd.resolve_jump(&mut config, "pro").unwrap();
+        assert!(config.shortcuts["proj"].last_used.is_some());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_sort_recent_puts_unused_last() {
+        let now = Utc::now();
+        let mut old = Shortcut::new("/a");
+        old.last_used = Some(now - chrono::Duration::days(3));
+        let mut new = Shortcut::new("/b");
+        new.last_used = Some(now);
+        let never = Shortcut::new("/c");
+
+        let mut entries = vec![("never", &never), ("old", &old), ("new", &new)];
+        ListSort::Recent.sort(&mut entries);
+        let names: Vec<_> = entries.iter().map(|(n, _)| *n).collect();
+        assert_eq!(names, ["new", "old", "never"]);
+    }
+
+    #[test]
+    fn test_dry_run_does_not_create_missing_directory() {
+        let (root, mut config) = relative_jump_fixture("dry-run");
+        config.behavior.create_missing = true;
+        config.set_dry_run(true);
+        let cmd = JumpCommand::new(None, false, None, None);
+
+        let path = cmd.resolve_target(&config, "proj/new/dir", false).unwrap();
+        assert_eq!(path, root.join("new").join("dir"));
+        assert!(!path.exists());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_add_path_bookmarks_other_directory() {
+        let (root, mut config) = relative_jump_fixture("add-path");
+        config.set_file_path(root.join("config.toml"));
+        config.behavior.confirm_overwrite = false;
+
+        let target = root.join("navr").join("src");
+        let cmd = JumpCommand::new(None, false, Some("src".to_string()), None)
+            .with_add_path(Some(target.to_string_lossy().to_string()));
+        cmd.execute(&mut config).unwrap();
+        let expected = std::fs::canonicalize(&target).unwrap();
+        assert_eq!(config.shortcuts["src"].path, expected.to_string_lossy());
+
+        let missing = JumpCommand::new(None, false, Some("gone".to_string()), None)
+            .with_add_path(Some(root.join("missing").to_string_lossy().to_string()));
+        assert!(missing.execute(&mut config).is_err());
+        assert!(!config.shortcuts.contains_key("gone"));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_add_without_name_uses_directory_name() {
+        let (root, mut config) = relative_jump_fixture("auto-name");
+        config.set_file_path(root.join("config.toml"));
+        let first = root.join("navr").join("Web App");
+        let second = root.join("other").join("web-app");
+        std::fs::create_dir_all(&first).unwrap();
+        std::fs::create_dir_all(&second).unwrap();
+
+        let add = |config: &mut AppConfig, dir: &Path| {
+            JumpCommand::new(None, false, None, None)
+                .with_auto_add(true)
+                .with_add_path(Some(dir.to_string_lossy().to_string()))
+                .execute(config)
+                .unwrap();
+        };
+
+        add(&mut config, &first);
+        let first = std::fs::canonicalize(&first).unwrap();
+        assert_eq!(config.shortcuts["web-app"].path, first.to_string_lossy());
+
+        // Adding the same directory again keeps a single shortcut
+        add(&mut config, &first);
+        assert!(!config.shortcuts.contains_key("web-app-2"));
+
+        // Another directory with the same name gets a numbered variant
+        add(&mut config, &second);
+        let second = std::fs::canonicalize(&second).unwrap();
+        assert_eq!(config.shortcuts["web-app-2"].path, second.to_string_lossy());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_check_marks_each_target() {
+        let (root, mut config) = relative_jump_fixture("check");
+        let file = root.join("notes.txt");
+        std::fs::write(&file, "").unwrap();
+        for (name, path) in [
+            ("gone", root.join("missing")),
+            ("notes", file),
+            ("box", "ssh://host/srv".into()),
+        ] {
+            config.shortcuts.insert(name.to_string(), Shortcut::new(path.to_string_lossy()));
+        }
+        #[cfg(unix)]
+        {
+            let link = root.join("proj-link");
+            std::os::unix::fs::symlink(root.join("navr"), &link).unwrap();
+            config.shortcuts.insert("link".to_string(), Shortcut::new(link.to_string_lossy()));
+            assert_eq!(TargetStatus::of(&config, "link"), TargetStatus::Symlink);
+            assert_eq!(TargetStatus::Symlink.glyph(), "↪");
+        }
+
+        let glyphs: Vec<&str> = ["proj", "gone", "notes", "box"]
+            .iter()
+            .map(|name| TargetStatus::of(&config, name).glyph())
+            .collect();
+        assert_eq!(glyphs, ["✓", "✗", "▪", "?"]);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_add_from_list_skips_invalid_lines() {
+        let (root, mut config) = relative_jump_fixture("add-from");
+        let content = format!(
+            "# new machine\nsrc = {}\n\nold\t{}\nPROJ = {}\n",
+            root.join("navr").join("src").display(),
+            root.join("missing").display(),
+            root.join("navr").display()
+        );
+
+        let list = add_shortcut_list(&content, &mut config).unwrap();
+        let added: Vec<&str> = list.added.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(added, ["src"]);
+        assert!(config.shortcuts.contains_key("src"));
+        let skipped: Vec<usize> = list.skipped.iter().map(|(number, _)| *number).collect();
+        assert_eq!(skipped, [4, 5]);
+        assert!(list.skipped[0].1.contains("does not exist"));
+        // An existing shortcut is refused while confirm_overwrite is on
+        assert!(list.skipped[1].1.contains("already exists"));
+        assert_eq!(config.shortcuts["proj"].path, root.to_string_lossy());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_list_broken_shows_only_dangling() {
+        let (root, mut config) = relative_jump_fixture("broken");
+        config
+            .shortcuts
+            .insert("gone".to_string(), Shortcut::new(root.join("missing").to_string_lossy()));
+        let cmd = JumpCommand::new(None, true, None, None).with_list_options(ListOptions {
+            broken: true,
+            ..Default::default()
+        });
+
+        let names: Vec<&str> = cmd.listed_shortcuts(&config).iter().map(|(n, _)| *n).collect();
+        assert_eq!(names, ["gone"]);
+        assert_eq!(broken_reason(&config, "gone"), Some("missing"));
+        assert_eq!(broken_reason(&config, "proj"), None);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_home_falls_back_to_env() {
+        let env = |key: &str| (key == "USERPROFILE").then(|| "C:\\Users\\me".into());
+
+        let detected = Some(PathBuf::from("/home/me"));
+        assert_eq!(home_dir_or_env(detected.clone(), env), detected);
+        assert_eq!(home_dir_or_env(None, env), Some(PathBuf::from("C:\\Users\\me")));
+        assert_eq!(home_dir_or_env(None, |_| Some("".into())), None);
+        assert_eq!(home_dir_or_env(None, |_| None), None);
     }
 }
@@ -1,7 +1,23 @@
 //! Command implementations for QuickNav
 
+#[cfg(feature = "interactive")]
+pub mod browse;
+pub mod complete;
 pub mod config;
+pub mod doctor;
 pub mod export;
+pub mod history;
 pub mod import;
 pub mod jump;
 pub mod open;
+pub mod profile;
+pub mod prune;
+pub mod report;
+pub mod scan;
+pub mod selftest;
+pub mod stats;
+
+/// `"s"` unless `count` is one, as in "Added 1 shortcut" but "Added 2 shortcuts"
+pub fn plural(count: usize) -> &'static str {
+    if count == 1 { "" } else { "s" }
+}
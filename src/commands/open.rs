@@ -1,81 +1,506 @@
 //! Open command - Open directories in file manager
 
 use anyhow::{Context, Result};
-use owo_colors::OwoColorize;
-use std::path::PathBuf;
+use inquire::Select;
+use owo_colors::{OwoColorize, Stream::Stdout};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 // use std::process::Command;
 
-use crate::config::AppConfig;
-use crate::platform::file_manager::FileManager;
+use crate::archive::{self, ArchiveKind};
+use crate::commands::history::record_visit;
+use crate::commands::plural;
+use crate::config::{is_remote_uri, AppConfig, ResolutionOrder};
+use crate::error::NavrError;
+use crate::history::{self, HistoryEntry};
+use crate::platform::file_manager::{self, FileManager};
+use crate::platform::{self, resolve_symlinked_file, symlink_file_target, wsl};
+
+/// The `n`th most recent distinct directory in history
+///
+/// The current directory is skipped when it is the latest entry, so
+/// `--recent` means the previous place.
+pub fn recent_target(config: &AppConfig, n: usize) -> Result<String> {
+    if !config.shell.track_history {
+        anyhow::bail!("History tracking is disabled; set shell.track_history = true");
+    }
+
+    let entries = history::read_history()?;
+    let current = std::env::current_dir().ok();
+    let current = current.map(|dir| std::fs::canonicalize(&dir).unwrap_or(dir));
+    pick_recent(&entries, current.as_deref(), n)
+}
+
+fn pick_recent(entries: &[HistoryEntry], current: Option<&Path>, n: usize) -> Result<String> {
+    let recent = history::recent_entries(entries, true, None);
+    if recent.is_empty() {
+        anyhow::bail!("No history yet; directories are recorded by the shell integration");
+    }
+
+    let skip = usize::from(recent.first().is_some_and(|p| Some(Path::new(p)) == current));
+    let available = recent.len() - skip;
+    n.checked_sub(1)
+        .and_then(|i| recent.get(skip + i))
+        .map(|path| path.to_string())
+        .with_context(|| format!("--recent must be between 1 and {}", available))
+}
+
+/// The URI to open if `target` is, or names a shortcut to, a remote location
+fn remote_target(target: &str, config: &AppConfig) -> Result<Option<String>> {
+    if is_remote_uri(target) {
+        return Ok(Some(target.to_string()));
+    }
+    let target = config.strip_shortcut_sigil(target).unwrap_or(target);
+    Ok(config.get_shortcut(target)?.filter(|path| is_remote_uri(path)).cloned())
+}
 
 pub struct OpenCommand {
-    target: String,
+    targets: Vec<String>,
     file_manager: Option<String>,
+    reveal: bool,
+    terminal: bool,
+    extra_args: Vec<String>,
+    wait: bool,
+    print_cmd: bool,
+    reuse_window: Option<bool>,
+    track: bool,
+    app: Option<String>,
+    detached: bool,
 }
 
 impl OpenCommand {
-    pub fn new(target: String) -> Self {
+    pub fn new(targets: Vec<String>) -> Self {
         Self {
-            target,
+            targets,
             file_manager: None,
+            reveal: false,
+            terminal: false,
+            extra_args: Vec::new(),
+            wait: false,
+            print_cmd: false,
+            reuse_window: None,
+            track: true,
+            app: None,
+            detached: false,
         }
     }
 
-    pub fn with_manager(target: String, file_manager: Option<String>) -> Self {
+    pub fn with_manager(targets: Vec<String>, file_manager: Option<String>) -> Self {
         Self {
-            target,
+            targets,
             file_manager,
+            reveal: false,
+            terminal: false,
+            extra_args: Vec::new(),
+            wait: false,
+            print_cmd: false,
+            reuse_window: None,
+            track: true,
+            app: None,
+            detached: false,
         }
     }
 
+    /// Open a terminal in the target instead of a file manager
+    pub fn with_terminal(mut self, terminal: bool) -> Self {
+        self.terminal = terminal;
+        self
+    }
+
+    /// Pass extra arguments to the file manager, before the target path
+    pub fn with_extra_args(mut self, extra_args: Vec<String>) -> Self {
+        self.extra_args = extra_args;
+        self
+    }
+
+    /// Wait for terminal file managers to exit before returning
+    pub fn with_wait(mut self, wait: bool) -> Self {
+        self.wait = wait;
+        self
+    }
+
+    /// Reuse (`Some(true)`) or avoid (`Some(false)`) an open file manager
+    /// window, overriding `behavior.reuse_window`
+    pub fn with_reuse_window(mut self, reuse_window: Option<bool>) -> Self {
+        self.reuse_window = reuse_window;
+        self
+    }
+
+    /// Record opened directories in the history when `shell.track_history` is on
+    pub fn with_track(mut self, track: bool) -> Self {
+        self.track = track;
+        self
+    }
+
+    /// Print the command that would open each target instead of running it
+    pub fn with_print_cmd(mut self, print_cmd: bool) -> Self {
+        self.print_cmd = print_cmd;
+        self
+    }
+
+    /// Open the target in this application instead of a file manager
+    pub fn with_app(mut self, app: Option<String>) -> Self {
+        self.app = app;
+        self
+    }
+
+    /// Detach the opened program from navr and the terminal, overriding
+    /// `behavior.detach_children` when set
+    pub fn with_detached(mut self, detached: bool) -> Self {
+        self.detached = detached;
+        self
+    }
+
+    /// Select the target inside its folder instead of opening it
+    pub fn with_reveal(mut self, reveal: bool) -> Self {
+        self.reveal = reveal;
+        self
+    }
+
+    /// Open every target, reporting failures only after trying them all
     pub fn execute(&self, config: &AppConfig) -> Result<()> {
+        if let [target] = self.targets.as_slice() {
+            return self.open_target(target, config);
+        }
+
+        let mut failures = Vec::new();
+        for target in &self.targets {
+            if let Err(e) = self.open_target(target, config) {
+                failures.push(format!("{} — {}", target, e));
+            }
+        }
+
+        let opened = self.targets.len() - failures.len();
+        if !failures.is_empty() {
+            anyhow::bail!(
+                "Opened {} of {} ({} failed: {})",
+                opened,
+                self.targets.len(),
+                failures.len(),
+                failures.join("; ")
+            );
+        }
+
+        if !self.print_cmd {
+            println!(
+                "{} Opened {} of {}",
+                "✓".if_supports_color(Stdout, |t| t.green()),
+                opened,
+                self.targets.len()
+            );
+        }
+        Ok(())
+    }
+
+    fn open_target(&self, target: &str, config: &AppConfig) -> Result<()> {
+        if self.print_cmd {
+            println!("{}", self.command_line(target, config)?);
+            return Ok(());
+        }
+
+        if let Some(uri) = remote_target(target, config)? {
+            return self.open_remote(&uri, config);
+        }
+
         // Resolve target path
-        let path = self.resolve_path(config)?;
+        let path = self.resolve_path(target, config)?;
+        if self.track
+            && let Err(e) = record_visit(config, &path)
+        {
+            tracing::debug!("Failed to record {} in the history: {}", path.display(), e);
+        }
+
+        if self.terminal {
+            return self.open_terminal(target, &path, config);
+        }
+        if let Some(app) = &self.app {
+            return self.open_app(&path, app, config);
+        }
+
+        let (path, reveal) = match self.archive_kind(&path, config) {
+            Some(kind) => self.archive_action(&path, kind, config)?,
+            None => (path, self.reveal),
+        };
+
+        if self.file_manager.is_none() && wsl::use_explorer(config) {
+            println!(
+                "{} {} {} in Windows Explorer...",
+                "→".if_supports_color(Stdout, |t| t.blue()),
+                if reveal { "Revealing" } else { "Opening" },
+                path.display().to_string().if_supports_color(Stdout, |t| t.cyan())
+            );
+            return wsl::open_in_explorer(&path, reveal, config);
+        }
 
         // Determine file manager to use
         let fm = self
-            .file_manager
-            .clone()
+            .requested_manager(config)
             .unwrap_or_else(|| config.get_file_manager());
 
         // Open the directory
-        self.open_directory(&path, &fm, config)?;
+        self.open_directory(&path, &fm, reveal, config)?;
 
         Ok(())
     }
 
-    fn resolve_path(&self, config: &AppConfig) -> Result<PathBuf> {
+    /// The command `open_target` would run for `target`, quoted for a shell
+    ///
+    /// Shows the requested file manager only; fallbacks are not tried.
+    fn command_line(&self, target: &str, config: &AppConfig) -> Result<String> {
+        let cmd = if let Some(uri) = remote_target(target, config)? {
+            file_manager::build_remote_command(&uri, config)?
+        } else {
+            let path = self.resolve_path(target, config)?;
+            if self.terminal {
+                file_manager::build_terminal_command(&path, config)?
+            } else if let Some(app) = &self.app {
+                file_manager::build_app_command(&path, &config.file_manager_command(app), &self.extra_args)?
+            } else if self.file_manager.is_none() && wsl::use_explorer(config) {
+                wsl::explorer_command(&path, self.reveal)
+            } else {
+                let fm = self
+                    .requested_manager(config)
+                    .unwrap_or_else(|| config.get_file_manager());
+                let file_manager = FileManager::new(&fm)
+                    .with_args(self.extra_args.clone())
+                    .with_reuse_window(self.reuse_window(config));
+                if self.reveal {
+                    file_manager.build_reveal_command(&path, config)?
+                } else {
+                    file_manager.build_command(&path, config)?
+                }
+            }
+        };
+        Ok(file_manager::format_command(&cmd))
+    }
+
+    fn resolve_path(&self, target: &str, config: &AppConfig) -> Result<PathBuf> {
+        // A sigil forces shortcut lookup; otherwise an existing path may win
+        let (target, forced) = match config.strip_shortcut_sigil(target) {
+            Some(name) => (name, true),
+            None => (target, false),
+        };
+        if !forced && config.behavior.resolution_order() == ResolutionOrder::PathFirst {
+            let path = PathBuf::from(shellexpand::full(target)?.to_string());
+            if path.exists() {
+                return self.finish_path(path, config);
+            }
+        }
+
         // Try to resolve as shortcut first
-        if let Some(shortcut_path) = config.get_shortcut(&self.target) {
-            return Ok(PathBuf::from(shortcut_path));
+        if let Some(shortcut_path) = config.get_shortcut(target)? {
+            return self.finish_path(PathBuf::from(shortcut_path), config);
+        }
+        if forced {
+            return Err(NavrError::ShortcutNotFound(target.to_string()).into());
         }
 
         // Expand and resolve as direct path
-        let expanded = shellexpand::full(&self.target)?.to_string();
+        let expanded = shellexpand::full(target)?.to_string();
         let path = PathBuf::from(&expanded);
 
         if path.exists() {
-            Ok(path)
+            self.finish_path(path, config)
         } else if config.behavior.create_missing {
-            std::fs::create_dir_all(&path)
-                .with_context(|| format!("Failed to create directory: {}", self.target))?;
-            println!("{} Created directory: {}", "✓".green(), path.display());
+            if config.skip_for_dry_run(|| format!("create directory {}", path.display())) {
+                return Ok(path);
+            }
+            platform::create_dir_all(&path, &config.behavior)
+                .with_context(|| format!("Failed to create directory: {}", target))?;
+            println!(
+                "{} Created directory: {}",
+                "✓".if_supports_color(Stdout, |t| t.green()),
+                path.display()
+            );
             Ok(path)
         } else {
-            anyhow::bail!("Path not found: {}", self.target)
+            Err(NavrError::PathNotFound(target.to_string()).into())
+        }
+    }
+
+    /// Apply symlink-to-file handling to a resolved path
+    fn finish_path(&self, path: PathBuf, config: &AppConfig) -> Result<PathBuf> {
+        if self.reveal {
+            // Reveal the file a symlink points at rather than its folder
+            if config.behavior.follow_symlinks
+                && let Some(file) = symlink_file_target(&path)
+            {
+                return Ok(file);
+            }
+            return Ok(path);
+        }
+
+        let path = resolve_symlinked_file(&path, &config.behavior)?.unwrap_or(path);
+        if self.archive_kind(&path, config).is_some() {
+            return Ok(path);
+        }
+
+        // File managers expect a directory, so open the one holding the file
+        if path.is_file() {
+            let file = std::fs::canonicalize(&path).unwrap_or(path);
+            let parent = file
+                .parent()
+                .map(Path::to_path_buf)
+                .with_context(|| format!("'{}' has no parent directory", file.display()))?;
+            // Keep stdout to the bare command for --print-cmd
+            let notice = format!(
+                "{} {} is a file; opening its folder {}",
+                "ℹ".if_supports_color(Stdout, |t| t.blue()),
+                file.display().to_string().if_supports_color(Stdout, |t| t.dimmed()),
+                parent.display().to_string().if_supports_color(Stdout, |t| t.cyan())
+            );
+            if self.print_cmd {
+                eprintln!("{}", notice);
+            } else {
+                println!("{}", notice);
+            }
+            return Ok(parent);
         }
+
+        Ok(path)
+    }
+
+    /// The `--with` file manager, with `file_managers` aliases expanded
+    fn requested_manager(&self, config: &AppConfig) -> Option<String> {
+        self.file_manager.as_deref().map(|name| config.file_manager_command(name))
     }
 
-    fn open_directory(&self, path: &PathBuf, fm: &str, config: &AppConfig) -> Result<()> {
+    fn detached(&self, config: &AppConfig) -> bool {
+        self.detached || config.behavior.detach_children
+    }
+
+    fn reuse_window(&self, config: &AppConfig) -> bool {
+        self.reuse_window.unwrap_or(config.behavior.reuse_window)
+    }
+
+    /// The format of `path` when it is an archive `open` should offer to extract
+    ///
+    /// Only with `behavior.open_archives` on and someone at the terminal to ask.
+    fn archive_kind(&self, path: &Path, config: &AppConfig) -> Option<ArchiveKind> {
+        if !config.behavior.open_archives
+            || self.reveal
+            || self.terminal
+            || self.app.is_some()
+            || self.print_cmd
+        {
+            return None;
+        }
+        ArchiveKind::detect(path).filter(|_| path.is_file() && std::io::stdin().is_terminal())
+    }
+
+    /// Ask whether to extract an archive and open the copy, or reveal it
+    ///
+    /// Returns the path to open and whether to reveal it.
+    fn archive_action(
+        &self,
+        path: &Path,
+        kind: ArchiveKind,
+        config: &AppConfig,
+    ) -> Result<(PathBuf, bool)> {
+        const EXTRACT: &str = "Extract to a temporary folder and open it";
+        const REVEAL: &str = "Reveal the archive in its folder";
+
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let choice = Select::new(&format!("{} is an archive:", name), vec![EXTRACT, REVEAL])
+            .prompt()?;
+        if choice == REVEAL {
+            return Ok((path.to_path_buf(), true));
+        }
+
+        let dest = archive::temp_destination(&ArchiveKind::stem(path));
+        if config.skip_for_dry_run(|| format!("extract {} into {}", path.display(), dest.display())) {
+            return Ok((dest, false));
+        }
+        let files = archive::extract(path, kind, &dest)
+            .with_context(|| format!("Failed to extract {}", path.display()))?;
+        println!(
+            "{} Extracted {} file{} to {}",
+            "✓".if_supports_color(Stdout, |t| t.green()),
+            files,
+            plural(files),
+            dest.display().to_string().if_supports_color(Stdout, |t| t.cyan())
+        );
+        Ok((dest, false))
+    }
+
+    fn open_terminal(&self, target: &str, path: &Path, config: &AppConfig) -> Result<()> {
+        if !path.is_dir() {
+            return Err(NavrError::NotADirectory(target.to_string()).into());
+        }
+
+        println!(
+            "{} Opening terminal in {}...",
+            "→".if_supports_color(Stdout, |t| t.blue()),
+            path.display().to_string().if_supports_color(Stdout, |t| t.cyan())
+        );
+
+        file_manager::open_terminal(path, self.detached(config), config)
+    }
+
+    fn open_app(&self, path: &Path, app: &str, config: &AppConfig) -> Result<()> {
+        let command = config.file_manager_command(app);
+        println!(
+            "{} Opening {} in {}...",
+            "→".if_supports_color(Stdout, |t| t.blue()),
+            path.display().to_string().if_supports_color(Stdout, |t| t.cyan()),
+            command.if_supports_color(Stdout, |t| t.yellow())
+        );
+
+        file_manager::open_app(path, &command, &self.extra_args, self.detached(config), config)
+    }
+
+    fn open_remote(&self, uri: &str, config: &AppConfig) -> Result<()> {
+        if let Some(app) = &self.app {
+            anyhow::bail!("Cannot open remote location {} in {}", uri, app);
+        }
+        if self.terminal || self.reveal {
+            anyhow::bail!(
+                "Cannot {} remote location {}",
+                if self.terminal { "open a terminal in" } else { "reveal" },
+                uri
+            );
+        }
+
+        println!(
+            "{} Opening remote {}...",
+            "→".if_supports_color(Stdout, |t| t.blue()),
+            uri.if_supports_color(Stdout, |t| t.cyan())
+        );
+        file_manager::open_remote(uri, self.detached(config), config)
+    }
+
+    fn open_directory(&self, path: &Path, fm: &str, reveal: bool, config: &AppConfig) -> Result<()> {
         println!(
-            "{} Opening {} with {}...",
-            "→".blue(),
-            path.display().to_string().cyan(),
-            fm.yellow()
+            "{} {} {} with {}...",
+            "→".if_supports_color(Stdout, |t| t.blue()),
+            if reveal { "Revealing" } else { "Opening" },
+            path.display().to_string().if_supports_color(Stdout, |t| t.cyan()),
+            fm.if_supports_color(Stdout, |t| t.yellow())
         );
 
-        let file_manager = FileManager::new(fm);
-        file_manager.open(path, config)?;
+        let used = file_manager::open_with_fallback(fm, |candidate| {
+            // Extra arguments were written for the requested file manager only
+            let args = if candidate == fm { self.extra_args.clone() } else { Vec::new() };
+            let file_manager = FileManager::new(candidate)
+                .with_args(args)
+                .with_wait(self.wait)
+                .with_reuse_window(self.reuse_window(config))
+                .with_detached(self.detached(config));
+            if reveal {
+                file_manager.reveal(path, config)
+            } else {
+                file_manager.open(path, config)
+            }
+        })?;
+
+        if used != fm {
+            println!(
+                "{} '{}' failed; opened with {} instead",
+                "ℹ".if_supports_color(Stdout, |t| t.blue()),
+                fm,
+                used.if_supports_color(Stdout, |t| t.yellow())
+            );
+        }
 
         Ok(())
     }
@@ -183,3 +608,128 @@ pub fn list_file_managers() -> Vec<(String, bool)> {
 
     managers
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_target_opens_containing_folder() {
+        let dir = std::env::temp_dir().join(format!("navr-open-file-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("todo.md");
+        std::fs::write(&file, "- [ ] write tests\n").unwrap();
+        let config = AppConfig::default();
+
+        let file_target = file.to_string_lossy().to_string();
+        let cmd = OpenCommand::new(vec![file_target.clone()]);
+        let resolved = cmd.resolve_path(&file_target, &config).unwrap();
+        assert_eq!(resolved, std::fs::canonicalize(&dir).unwrap());
+
+        // Directories are opened as before
+        assert_eq!(cmd.resolve_path(&dir.to_string_lossy(), &config).unwrap(), dir);
+
+        // Revealing keeps the file itself
+        let cmd = cmd.with_reveal(true);
+        assert_eq!(cmd.resolve_path(&file_target, &config).unwrap(), file);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_print_cmd_shows_file_manager_args() {
+        let config = AppConfig::default();
+        let name = format!("navr-print-cmd-{}", std::process::id());
+        let dir = std::env::temp_dir().join(&name);
+        std::fs::create_dir_all(&dir).unwrap();
+        let target = dir.to_string_lossy().to_string();
+
+        let cmd = OpenCommand::with_manager(vec![target.clone()], Some("nautilus".to_string()))
+            .with_print_cmd(true);
+        let line = cmd.command_line(&target, &config).unwrap();
+        assert!(line.starts_with("nautilus --new-window "), "{}", line);
+        assert!(line.ends_with(&name), "{}", line);
+
+        // Printing never spawns, so an uninstalled file manager is fine
+        assert!(cmd.execute(&config).is_ok());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_print_cmd_expands_aliases() {
+        let mut config = AppConfig::default();
+        config.file_managers.insert("ide".to_string(), "code --new-window".to_string());
+        let name = format!("navr-print-app-{}", std::process::id());
+        let dir = std::env::temp_dir().join(&name);
+        std::fs::create_dir_all(&dir).unwrap();
+        let target = dir.to_string_lossy().to_string();
+
+        // --app runs the command with the directory as its last argument
+        let cmd = OpenCommand::new(vec![target.clone()])
+            .with_app(Some("ide".to_string()))
+            .with_extra_args(vec!["--reuse".to_string()]);
+        let line = cmd.command_line(&target, &config).unwrap();
+        assert!(line.starts_with("code --new-window --reuse "), "{}", line);
+        assert!(line.ends_with(&name), "{}", line);
+
+        // --with looks up the same aliases; other names are used as given
+        let with = |name: &str| OpenCommand::with_manager(vec![target.clone()], Some(name.to_string()));
+        assert!(with("ide").command_line(&target, &config).unwrap().starts_with("code --new-window "));
+        assert!(with("zed").command_line(&target, &config).unwrap().starts_with("zed "));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_archive_target_unchanged_when_disabled() {
+        let dir = std::env::temp_dir().join(format!("navr-open-archive-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let archive = dir.join("backup.zip");
+        std::fs::write(&archive, b"PK\x05\x06").unwrap();
+        let config = AppConfig::default();
+        assert!(!config.behavior.open_archives);
+
+        let target = archive.to_string_lossy().to_string();
+        let cmd = OpenCommand::new(vec![target.clone()]);
+        assert_eq!(cmd.archive_kind(&archive, &config), None);
+        assert_eq!(cmd.resolve_path(&target, &config).unwrap(), std::fs::canonicalize(&dir).unwrap());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_multiple_targets_report_failures() {
+        let mut config = AppConfig::default();
+        config.set_dry_run(true);
+        let targets = vec![
+            std::env::temp_dir().to_string_lossy().to_string(),
+            "/nonexistent/navr/open".to_string(),
+        ];
+
+        let cmd = OpenCommand::with_manager(targets, Some("xdg-open".to_string()));
+        let err = cmd.execute(&config).unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "Opened 1 of 2 (1 failed: /nonexistent/navr/open — Path not found: /nonexistent/navr/open)"
+        );
+    }
+
+    #[test]
+    fn test_recent_skips_current_directory() {
+        let entries: Vec<HistoryEntry> = ["/a", "/b", "/a", "/c"]
+            .iter()
+            .map(|path| HistoryEntry {
+                path: path.to_string(),
+                visited_at: None,
+            })
+            .collect();
+
+        assert_eq!(pick_recent(&entries, Some(Path::new("/c")), 1).unwrap(), "/a");
+        assert_eq!(pick_recent(&entries, Some(Path::new("/c")), 2).unwrap(), "/b");
+        assert_eq!(pick_recent(&entries, Some(Path::new("/elsewhere")), 1).unwrap(), "/c");
+        assert!(pick_recent(&entries, Some(Path::new("/c")), 3).is_err());
+        assert!(pick_recent(&entries, None, 0).is_err());
+        assert!(pick_recent(&[], None, 1).is_err());
+    }
+}
@@ -0,0 +1,225 @@
+//! Profile command - Keep separate configs, e.g. for work and personal use
+
+use anyhow::Result;
+use clap::Subcommand;
+use inquire::Confirm;
+use owo_colors::{OwoColorize, Stream::Stdout, Style};
+use std::path::{Path, PathBuf};
+
+use crate::config::backup_path;
+use crate::config::defaults::create_default_config;
+use crate::config::paths::{self, DEFAULT_PROFILE, PROFILE_ENV};
+use crate::lock::lock_path;
+
+#[derive(Debug, Subcommand)]
+pub enum ProfileSubCommand {
+    /// List profiles, marking the active one
+    List,
+    /// Create a profile with the default settings
+    Create {
+        /// Profile name
+        name: String,
+    },
+    /// Delete a profile with its history and caches
+    Delete {
+        /// Profile name
+        name: String,
+
+        /// Do not ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Use a profile whenever no --profile or NAVR_PROFILE is given
+    Switch {
+        /// Profile name, or 'default' for config.toml
+        name: String,
+    },
+}
+
+/// Run a profile subcommand
+///
+/// Does not need the active config, so it also works when that profile is missing.
+pub fn execute(action: &ProfileSubCommand, dry_run: bool) -> Result<()> {
+    let config_dir = paths::config_dir()?;
+    // Settings for new profiles; also decides whether changes are only described
+    let mut template = create_default_config();
+    template.set_dry_run(dry_run);
+
+    match action {
+        ProfileSubCommand::List => {
+            let active = paths::active_profile()?;
+            let active = active.as_deref().unwrap_or(DEFAULT_PROFILE);
+            for name in list_profiles(&config_dir)? {
+                if name == active {
+                    println!(
+                        "{} {}",
+                        "*".if_supports_color(Stdout, |t| t.green()),
+                        name.if_supports_color(Stdout, |t| t.style(Style::new().cyan().bold()))
+                    );
+                } else {
+                    println!("  {}", name);
+                }
+            }
+        }
+        ProfileSubCommand::Create { name } => {
+            let file = new_profile_file(&config_dir, name)?;
+            template.set_file_path(&file);
+            template.save()?;
+            println!(
+                "{} Created profile '{}' at {}",
+                "✓".if_supports_color(Stdout, |t| t.green()),
+                name.if_supports_color(Stdout, |t| t.cyan()),
+                file.display()
+            );
+            println!("  Use 'navr config profile switch {}' to make it the default", name);
+        }
+        ProfileSubCommand::Delete { name, yes } => {
+            let file = existing_profile_file(&config_dir, name)?;
+            if name == DEFAULT_PROFILE {
+                anyhow::bail!("The default profile cannot be deleted");
+            }
+            let confirmed = *yes
+                || Confirm::new(&format!("Delete profile '{}' with its history?", name))
+                    .with_default(false)
+                    .prompt()?;
+            if !confirmed {
+                println!("{} Cancelled", "✗".if_supports_color(Stdout, |t| t.red()));
+                return Ok(());
+            }
+            if !template.skip_for_dry_run(|| format!("delete profile {}", name)) {
+                delete_profile(&config_dir, &file, &paths::data_dir_of(Some(name))?, name)?;
+            }
+            println!(
+                "{} Deleted profile '{}'",
+                "✓".if_supports_color(Stdout, |t| t.green()),
+                name.if_supports_color(Stdout, |t| t.cyan())
+            );
+        }
+        ProfileSubCommand::Switch { name } => {
+            existing_profile_file(&config_dir, name)?;
+            if !template.skip_for_dry_run(|| format!("switch to profile {}", name)) {
+                switch_profile(&config_dir, name)?;
+            }
+            println!(
+                "{} Switched to profile '{}'",
+                "✓".if_supports_color(Stdout, |t| t.green()),
+                name.if_supports_color(Stdout, |t| t.cyan())
+            );
+            if let Ok(env) = std::env::var(PROFILE_ENV)
+                && !env.is_empty()
+                && env != *name
+            {
+                println!(
+                    "{} {}={} still selects '{}' in this shell",
+                    "!".if_supports_color(Stdout, |t| t.yellow()),
+                    PROFILE_ENV,
+                    env,
+                    env
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `default` followed by the named profiles in `config_dir`, sorted
+fn list_profiles(config_dir: &Path) -> Result<Vec<String>> {
+    let mut names = Vec::new();
+    if let Ok(entries) = std::fs::read_dir(config_dir.join("profiles")) {
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "toml")
+                && let Some(stem) = path.file_stem().and_then(|s| s.to_str())
+                && paths::validate_profile_name(stem).is_ok()
+            {
+                names.push(stem.to_string());
+            }
+        }
+    }
+    names.sort();
+    names.insert(0, DEFAULT_PROFILE.to_string());
+    Ok(names)
+}
+
+/// Config file for a new profile `name`, which must not exist yet
+fn new_profile_file(config_dir: &Path, name: &str) -> Result<PathBuf> {
+    paths::validate_profile_name(name)?;
+    if name == DEFAULT_PROFILE {
+        anyhow::bail!("'{}' is the name of the built-in profile", DEFAULT_PROFILE);
+    }
+    let file = paths::profile_config_file(config_dir, Some(name));
+    if file.exists() {
+        anyhow::bail!("Profile '{}' already exists", name);
+    }
+    Ok(file)
+}
+
+/// Config file of the existing profile `name`
+fn existing_profile_file(config_dir: &Path, name: &str) -> Result<PathBuf> {
+    paths::validate_profile_name(name)?;
+    let profile = (name != DEFAULT_PROFILE).then_some(name);
+    let file = paths::profile_config_file(config_dir, profile);
+    if profile.is_some() && !file.exists() {
+        anyhow::bail!("Profile '{}' does not exist", name);
+    }
+    Ok(file)
+}
+
+fn switch_profile(config_dir: &Path, name: &str) -> Result<()> {
+    let profile = (name != DEFAULT_PROFILE).then_some(name);
+    paths::set_current_profile(config_dir, profile)
+}
+
+/// Remove the config, its backup and the data of profile `name`, going back
+/// to the default profile if it was the current one
+fn delete_profile(config_dir: &Path, file: &Path, data_dir: &Path, name: &str) -> Result<()> {
+    std::fs::remove_file(file)?;
+    for leftover in [backup_path(file), lock_path(file)?] {
+        if leftover.exists() {
+            std::fs::remove_file(leftover)?;
+        }
+    }
+    if data_dir.exists() {
+        std::fs::remove_dir_all(data_dir)?;
+    }
+    if paths::current_profile(config_dir)?.as_deref() == Some(name) {
+        paths::set_current_profile(config_dir, None)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_switch_and_delete_profiles() {
+        let dir = std::env::temp_dir().join(format!("navr-profiles-{}", std::process::id()));
+        let file = new_profile_file(&dir, "work").unwrap();
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(&file, "").unwrap();
+        std::fs::write(backup_path(&file), "").unwrap();
+
+        assert!(new_profile_file(&dir, "work").is_err());
+        assert!(new_profile_file(&dir, DEFAULT_PROFILE).is_err());
+        assert!(existing_profile_file(&dir, "home").is_err());
+        assert_eq!(list_profiles(&dir).unwrap(), [DEFAULT_PROFILE, "work"]);
+
+        // Switching records the profile; `default` clears it again
+        assert_eq!(paths::current_profile(&dir).unwrap(), None);
+        switch_profile(&dir, "work").unwrap();
+        assert_eq!(paths::current_profile(&dir).unwrap().as_deref(), Some("work"));
+        switch_profile(&dir, DEFAULT_PROFILE).unwrap();
+        assert_eq!(paths::current_profile(&dir).unwrap(), None);
+
+        // Deleting the current profile falls back to the default one
+        switch_profile(&dir, "work").unwrap();
+        let data_dir = dir.join("data-work");
+        std::fs::create_dir_all(&data_dir).unwrap();
+        delete_profile(&dir, &file, &data_dir, "work").unwrap();
+        assert!(!file.exists() && !backup_path(&file).exists() && !data_dir.exists());
+        assert_eq!(paths::current_profile(&dir).unwrap(), None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,101 @@
+//! Prune command - Remove shortcuts pointing at missing directories
+
+use anyhow::Result;
+use inquire::Confirm;
+use owo_colors::{OwoColorize, Stream::Stdout};
+use std::path::Path;
+
+use crate::config::{is_remote_uri, AppConfig};
+use crate::platform::path_exists;
+
+pub fn execute(config: &mut AppConfig) -> Result<()> {
+    let broken = find_broken(config);
+
+    if broken.is_empty() {
+        println!(
+            "{} All {} shortcuts point at existing paths",
+            "✓".if_supports_color(Stdout, |t| t.green()),
+            config.shortcuts.len()
+        );
+        return Ok(());
+    }
+
+    println!("{}", "Broken Shortcuts:".if_supports_color(Stdout, |t| t.bold()));
+    for (name, path) in &broken {
+        println!(
+            "  {} → {}",
+            name.if_supports_color(Stdout, |t| t.cyan()),
+            path.if_supports_color(Stdout, |t| t.red())
+        );
+    }
+    println!();
+
+    if config.is_dry_run() {
+        println!(
+            "{} {} broken shortcuts would be removed (dry run)",
+            "ℹ".if_supports_color(Stdout, |t| t.blue()),
+            broken.len().to_string().if_supports_color(Stdout, |t| t.yellow())
+        );
+        return Ok(());
+    }
+
+    let confirm = Confirm::new(&format!("Remove {} broken shortcuts?", broken.len()))
+        .with_default(false)
+        .prompt()?;
+
+    if !confirm {
+        println!("{} Cancelled", "✗".if_supports_color(Stdout, |t| t.red()));
+        return Ok(());
+    }
+
+    for (name, _) in &broken {
+        config.shortcuts.remove(name);
+    }
+    config.save()?;
+
+    println!(
+        "{} Removed {} broken shortcuts",
+        "✓".if_supports_color(Stdout, |t| t.green()),
+        broken.len().to_string().if_supports_color(Stdout, |t| t.cyan())
+    );
+
+    Ok(())
+}
+
+/// Collect shortcuts whose target no longer exists, sorted by name
+///
+/// Aliases are broken when they no longer resolve to an existing path.
+fn find_broken(config: &AppConfig) -> Vec<(String, String)> {
+    let mut broken: Vec<_> = config
+        .shortcuts
+        .iter()
+        .filter(|(name, _)| match config.get_shortcut(name) {
+            // Remote locations cannot be checked from here
+            Ok(Some(path)) => {
+                !is_remote_uri(path) && !path_exists(Path::new(path), config.behavior.follow_symlinks)
+            }
+            _ => true,
+        })
+        .map(|(name, s)| (name.clone(), s.current_path().clone()))
+        .collect();
+
+    broken.sort();
+    broken
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Shortcut;
+
+    #[test]
+    fn test_find_broken() {
+        let mut config = AppConfig::default();
+        let existing = std::env::temp_dir();
+        config.shortcuts.insert("tmp".to_string(), Shortcut::new(existing.to_string_lossy()));
+        config.shortcuts.insert("gone".to_string(), Shortcut::new("/nonexistent/navr/prune"));
+
+        let broken = find_broken(&config);
+        assert_eq!(broken, vec![("gone".to_string(), "/nonexistent/navr/prune".to_string())]);
+    }
+}
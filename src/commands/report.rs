@@ -0,0 +1,36 @@
+//! Pass/warn/fail reporting shared by diagnostic commands
+
+use owo_colors::{OwoColorize, Stream::Stdout};
+
+/// Tallies and prints individual check results
+#[derive(Default)]
+pub struct Report {
+    pub warned: usize,
+    pub failed: usize,
+}
+
+impl Report {
+    pub fn pass(&mut self, name: &str, detail: &str) {
+        println!(
+            "  {} {}: {}",
+            "✓".if_supports_color(Stdout, |t| t.green()),
+            name,
+            detail.if_supports_color(Stdout, |t| t.dimmed())
+        );
+    }
+
+    pub fn warn(&mut self, name: &str, detail: &str) {
+        println!("  {} {}: {}", "!".if_supports_color(Stdout, |t| t.yellow()), name, detail);
+        self.warned += 1;
+    }
+
+    pub fn fail(&mut self, name: &str, detail: &str) {
+        println!(
+            "  {} {}: {}",
+            "✗".if_supports_color(Stdout, |t| t.red()),
+            name,
+            detail.if_supports_color(Stdout, |t| t.red())
+        );
+        self.failed += 1;
+    }
+}
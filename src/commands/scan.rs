@@ -0,0 +1,189 @@
+//! Scan command - Suggest shortcuts for project directories under a root
+//!
+//! A project is a directory containing `.git`. Projects are not searched
+//! for nested projects.
+
+use anyhow::{Context, Result};
+use inquire::MultiSelect;
+use owo_colors::{OwoColorize, Stream::Stdout};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::commands::plural;
+use crate::config::{numbered_name, shortcut_name_for, AppConfig, Shortcut};
+
+/// How many levels below the root are searched by default
+pub const DEFAULT_SCAN_DEPTH: usize = 2;
+
+/// Options controlling which directories are visited
+#[derive(Debug, Clone, Copy)]
+struct Walk {
+    max_depth: usize,
+    follow_symlinks: bool,
+    hidden: bool,
+}
+
+/// A project offered as a new shortcut
+struct Suggestion {
+    name: String,
+    path: PathBuf,
+}
+
+impl fmt::Display for Suggestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}  → {}", self.name, self.path.display())
+    }
+}
+
+pub fn execute(config: &mut AppConfig, root: &str, max_depth: usize, hidden: bool) -> Result<()> {
+    let expanded = shellexpand::full(root)?.to_string();
+    let root = std::fs::canonicalize(&expanded)
+        .with_context(|| format!("Scan root not found: {}", root))?;
+
+    println!(
+        "{} Scanning {} (depth {})...",
+        "→".if_supports_color(Stdout, |t| t.blue()),
+        root.display().to_string().if_supports_color(Stdout, |t| t.cyan()),
+        max_depth
+    );
+
+    let walk = Walk {
+        max_depth,
+        follow_symlinks: config.behavior.follow_symlinks,
+        hidden,
+    };
+    let suggestions = suggest(config, find_projects(&root, walk));
+    if suggestions.is_empty() {
+        println!(
+            "{} No new project directories found",
+            "ℹ".if_supports_color(Stdout, |t| t.blue())
+        );
+        return Ok(());
+    }
+
+    let selected = MultiSelect::new("Create shortcuts for:", suggestions)
+        .with_all_selected_by_default()
+        .prompt()?;
+    if selected.is_empty() {
+        println!("{} Cancelled", "✗".if_supports_color(Stdout, |t| t.red()));
+        return Ok(());
+    }
+
+    for suggestion in &selected {
+        config.shortcuts.insert(
+            suggestion.name.clone(),
+            Shortcut::created_now(suggestion.path.to_string_lossy()),
+        );
+    }
+    config.save()?;
+
+    println!(
+        "{} Added {} shortcut{}:",
+        "✓".if_supports_color(Stdout, |t| t.green()),
+        selected.len().to_string().if_supports_color(Stdout, |t| t.cyan()),
+        plural(selected.len())
+    );
+    for suggestion in &selected {
+        println!(
+            "  {} → {}",
+            suggestion.name.if_supports_color(Stdout, |t| t.cyan()),
+            suggestion.path.display().to_string().if_supports_color(Stdout, |t| t.dimmed())
+        );
+    }
+
+    Ok(())
+}
+
+/// Name each project not yet bookmarked, adding a numeric suffix on clashes
+fn suggest(config: &AppConfig, projects: Vec<PathBuf>) -> Vec<Suggestion> {
+    let mut suggestions: Vec<Suggestion> = Vec::new();
+    for path in projects {
+        let path_str = path.to_string_lossy();
+        if config.shortcuts.values().any(|s| s.path == path_str) {
+            continue;
+        }
+        let Some(base) = shortcut_name_for(&path) else {
+            continue;
+        };
+
+        let name = numbered_name(&base, |name| {
+            config.find_shortcut_key(name).is_some() || suggestions.iter().any(|s| s.name == name)
+        });
+        suggestions.push(Suggestion { name, path });
+    }
+    suggestions
+}
+
+/// Find project directories at most `walk.max_depth` levels below `root`, sorted
+fn find_projects(root: &Path, walk: Walk) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    visit(root, 0, walk, &mut found);
+    found.sort();
+    found
+}
+
+fn visit(dir: &Path, depth: usize, walk: Walk, found: &mut Vec<PathBuf>) {
+    if dir.join(".git").exists() {
+        found.push(dir.to_path_buf());
+        return;
+    }
+    if depth >= walk.max_depth {
+        return;
+    }
+
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        tracing::debug!("Skipping unreadable directory {}", dir.display());
+        return;
+    };
+    for entry in entries.flatten() {
+        if !walk.hidden && entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let path = entry.path();
+        let is_dir = if file_type.is_symlink() {
+            walk.follow_symlinks && path.is_dir()
+        } else {
+            file_type.is_dir()
+        };
+        if is_dir {
+            visit(&path, depth + 1, walk, found);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_projects() {
+        let root = std::env::temp_dir().join(format!("navr-scan-{}", std::process::id()));
+        for repo in ["api", "group/web", ".config/dots", "deep/er/still"] {
+            std::fs::create_dir_all(root.join(repo).join(".git")).unwrap();
+        }
+        std::fs::create_dir_all(root.join("notes")).unwrap();
+
+        let walk = Walk {
+            max_depth: DEFAULT_SCAN_DEPTH,
+            follow_symlinks: true,
+            hidden: false,
+        };
+        assert_eq!(find_projects(&root, walk), [root.join("api"), root.join("group/web")]);
+
+        let hidden = Walk { hidden: true, ..walk };
+        assert!(find_projects(&root, hidden).contains(&root.join(".config/dots")));
+
+        let mut config = AppConfig::default();
+        config.shortcuts.insert("api".to_string(), Shortcut::new("/elsewhere/api"));
+        let names: Vec<String> = suggest(&config, find_projects(&root, walk))
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(names, ["api-2", "web"]);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
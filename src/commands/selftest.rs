@@ -0,0 +1,112 @@
+//! Self-test command - Exercise the config and open pipeline in a sandbox
+//!
+//! Everything happens inside a temporary directory: the real configuration
+//! and data files are never read or written, and no process is spawned.
+
+use anyhow::{Context, Result};
+use owo_colors::{OwoColorize, Stream::Stdout, Style};
+use std::path::{Path, PathBuf};
+
+use crate::config::AppConfig;
+use crate::platform::file_manager::FileManager;
+
+const SHORTCUT_NAME: &str = "selftest";
+
+pub fn execute() -> Result<()> {
+    let sandbox = std::env::temp_dir().join(format!("navr-selftest-{}", std::process::id()));
+
+    println!(
+        "{}",
+        "Navr Self-Test".if_supports_color(Stdout, |t| t.style(Style::new().bold().underline()))
+    );
+    println!(
+        "  Sandbox: {}",
+        sandbox.display().to_string().if_supports_color(Stdout, |t| t.dimmed())
+    );
+    println!();
+
+    let result = run_steps(&sandbox);
+    let _ = std::fs::remove_dir_all(&sandbox);
+
+    println!();
+    match result {
+        Ok(count) => {
+            println!("{} All {} steps passed", "✓".if_supports_color(Stdout, |t| t.green()), count);
+            Ok(())
+        }
+        Err(_) => anyhow::bail!("Self-test failed"),
+    }
+}
+
+/// Run each step in order, stopping at the first failure
+fn run_steps(sandbox: &Path) -> Result<usize> {
+    let mut steps = Steps::default();
+
+    let target = steps.run("Create sandbox directory", || {
+        let target = sandbox.join("project");
+        std::fs::create_dir_all(&target)
+            .with_context(|| format!("Failed to create {:?}", target))?;
+        Ok(std::fs::canonicalize(&target)?)
+    })?;
+
+    let config_file = sandbox.join("config.toml");
+    steps.run("Save sandbox config with a shortcut", || {
+        let mut config = AppConfig::default();
+        config.set_file_path(&config_file);
+        config.set_shortcut(SHORTCUT_NAME, &target.to_string_lossy(), false)?;
+        Ok(())
+    })?;
+
+    let config = steps.run("Reload sandbox config", || {
+        let config = AppConfig::load_from_path(&config_file)?;
+        if !config.shortcuts.contains_key(SHORTCUT_NAME) {
+            anyhow::bail!("shortcut '{}' missing after reload", SHORTCUT_NAME);
+        }
+        Ok(config)
+    })?;
+
+    let resolved = steps.run("Resolve shortcut", || {
+        let path = config
+            .get_shortcut(SHORTCUT_NAME)?
+            .map(PathBuf::from)
+            .context("shortcut did not resolve")?;
+        if path != target {
+            anyhow::bail!("resolved to {}, expected {}", path.display(), target.display());
+        }
+        Ok(path)
+    })?;
+
+    let fm = config.get_file_manager();
+    steps.run(&format!("Build '{}' command", fm), || {
+        let cmd = FileManager::new(&fm).build_command(&resolved, &config)?;
+        let path = resolved.to_string_lossy();
+        if !cmd.get_args().any(|a| a.to_string_lossy().contains(path.as_ref())) {
+            anyhow::bail!("target path missing from command arguments");
+        }
+        Ok(())
+    })?;
+
+    Ok(steps.passed)
+}
+
+/// Tracks and reports individual self-test steps
+#[derive(Default)]
+struct Steps {
+    passed: usize,
+}
+
+impl Steps {
+    fn run<T>(&mut self, name: &str, f: impl FnOnce() -> Result<T>) -> Result<T> {
+        match f() {
+            Ok(value) => {
+                println!("  {} {}", "✓".if_supports_color(Stdout, |t| t.green()), name);
+                self.passed += 1;
+                Ok(value)
+            }
+            Err(e) => {
+                println!("  {} {}: {}", "✗".if_supports_color(Stdout, |t| t.red()), name, e);
+                Err(e)
+            }
+        }
+    }
+}
@@ -0,0 +1,201 @@
+//! Stats command - Summarize navigation history
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use owo_colors::{OwoColorize, Stream::Stdout, Style};
+use std::collections::HashMap;
+
+use crate::history::{self, HistoryEntry};
+
+/// Number of most-visited directories to show
+const TOP_LIMIT: usize = 10;
+
+/// Number of days shown in the activity chart, ending today
+const CHART_DAYS: u64 = 7;
+
+/// Width of the longest bar in the activity chart
+const CHART_WIDTH: usize = 30;
+
+/// Usage figures computed from history entries
+#[derive(Debug)]
+struct Stats<'a> {
+    total: usize,
+    unique: usize,
+    /// Most visited directories with their visit counts
+    top: Vec<(&'a str, usize)>,
+    /// Visits per day, oldest day first
+    per_day: Vec<(NaiveDate, usize)>,
+}
+
+pub fn execute(since: Option<&str>) -> Result<()> {
+    let since = since.map(parse_since).transpose()?;
+    let entries = history::read_history()?;
+    let entries: Vec<&HistoryEntry> = entries
+        .iter()
+        .filter(|e| since.is_none_or(|since| e.visited_at.is_some_and(|t| t >= since)))
+        .collect();
+
+    if entries.is_empty() {
+        match since {
+            Some(_) => println!(
+                "{} No history in the selected period",
+                "ℹ".if_supports_color(Stdout, |t| t.blue())
+            ),
+            None => println!("{} No history yet", "ℹ".if_supports_color(Stdout, |t| t.blue())),
+        }
+        return Ok(());
+    }
+
+    let stats = summarize(&entries, Local::now().date_naive());
+
+    println!(
+        "{}",
+        "Navigation Stats".if_supports_color(Stdout, |t| t.style(Style::new().bold().underline()))
+    );
+    println!();
+    println!(
+        "  {}: {}",
+        "Total jumps".if_supports_color(Stdout, |t| t.cyan()),
+        stats.total.to_string().if_supports_color(Stdout, |t| t.yellow())
+    );
+    println!(
+        "  {}: {}",
+        "Unique directories".if_supports_color(Stdout, |t| t.cyan()),
+        stats.unique.to_string().if_supports_color(Stdout, |t| t.yellow())
+    );
+    println!();
+
+    println!("{}", "Most Visited:".if_supports_color(Stdout, |t| t.bold()));
+    let width = stats.top.iter().map(|(_, count)| count.to_string().len()).max().unwrap_or(0);
+    for (path, count) in &stats.top {
+        println!(
+            "  {:>width$}  {}",
+            count.to_string().if_supports_color(Stdout, |t| t.yellow()),
+            path,
+            width = width
+        );
+    }
+    println!();
+
+    println!("{}", "Last 7 Days:".if_supports_color(Stdout, |t| t.bold()));
+    let max = stats.per_day.iter().map(|(_, count)| *count).max().unwrap_or(0);
+    for (day, count) in &stats.per_day {
+        println!(
+            "  {}  {:<bar_width$}  {}",
+            day.format("%a %m-%d").to_string().if_supports_color(Stdout, |t| t.dimmed()),
+            bar(*count, max).if_supports_color(Stdout, |t| t.green()),
+            count,
+            bar_width = CHART_WIDTH
+        );
+    }
+
+    Ok(())
+}
+
+/// Parse a `--since` value: a date (local midnight) or an RFC 3339 timestamp
+fn parse_since(value: &str) -> Result<DateTime<Utc>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").with_context(|| {
+        format!("Invalid date '{}': expected YYYY-MM-DD or an RFC 3339 timestamp", value)
+    })?;
+    date.and_hms_opt(0, 0, 0)
+        .and_then(|midnight| midnight.and_local_timezone(Local).earliest())
+        .map(|time| time.with_timezone(&Utc))
+        .with_context(|| format!("Invalid date '{}'", value))
+}
+
+fn summarize<'a>(entries: &[&'a HistoryEntry], today: NaiveDate) -> Stats<'a> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for entry in entries {
+        *counts.entry(entry.path.as_str()).or_default() += 1;
+    }
+
+    let unique = counts.len();
+    let mut top: Vec<_> = counts.into_iter().collect();
+    top.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    top.truncate(TOP_LIMIT);
+
+    let per_day = (0..CHART_DAYS)
+        .rev()
+        .filter_map(|ago| today.checked_sub_days(chrono::Days::new(ago)))
+        .map(|day| {
+            let count = entries
+                .iter()
+                .filter(|e| e.visited_at.is_some_and(|t| t.with_timezone(&Local).date_naive() == day))
+                .count();
+            (day, count)
+        })
+        .collect();
+
+    Stats {
+        total: entries.len(),
+        unique,
+        top,
+        per_day,
+    }
+}
+
+/// Bar scaled so that `max` fills the chart width; any visit shows at least one block
+fn bar(count: usize, max: usize) -> String {
+    if count == 0 || max == 0 {
+        return String::new();
+    }
+    "█".repeat((count * CHART_WIDTH / max).max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn visit(path: &str, day: NaiveDate) -> HistoryEntry {
+        let noon = day.and_hms_opt(12, 0, 0).unwrap().and_local_timezone(Local).unwrap();
+        HistoryEntry {
+            path: path.to_string(),
+            visited_at: Some(noon.with_timezone(&Utc)),
+        }
+    }
+
+    #[test]
+    fn test_summarize() {
+        let today = NaiveDate::from_ymd_opt(2024, 5, 10).unwrap();
+        let yesterday = NaiveDate::from_ymd_opt(2024, 5, 9).unwrap();
+        let history = [
+            HistoryEntry {
+                path: "/old".to_string(),
+                visited_at: None,
+            },
+            visit("/a", yesterday),
+            visit("/b", today),
+            visit("/a", today),
+        ];
+        let entries: Vec<&HistoryEntry> = history.iter().collect();
+
+        let stats = summarize(&entries, today);
+        assert_eq!(stats.total, 4);
+        assert_eq!(stats.unique, 3);
+        assert_eq!(stats.top, vec![("/a", 2), ("/b", 1), ("/old", 1)]);
+        assert_eq!(stats.per_day.len(), 7);
+        assert_eq!(stats.per_day[5], (yesterday, 1));
+        assert_eq!(stats.per_day[6], (today, 2));
+    }
+
+    #[test]
+    fn test_parse_since() {
+        assert!(parse_since("2024-05-01").is_ok());
+        assert_eq!(
+            parse_since("2024-05-01T08:00:00Z").unwrap().timestamp(),
+            1_714_550_400
+        );
+        assert!(parse_since("last week").is_err());
+    }
+
+    #[test]
+    fn test_bar_scales_to_width() {
+        assert_eq!(bar(0, 10), "");
+        assert_eq!(bar(10, 10).chars().count(), CHART_WIDTH);
+        assert_eq!(bar(1, 1000).chars().count(), 1);
+    }
+}
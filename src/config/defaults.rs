@@ -2,79 +2,89 @@
 
 use super::*;
 
+/// Group of the default shortcuts for standard user directories
+pub const SYSTEM_GROUP: &str = "System";
+
+/// Group of the default shortcuts for development directories
+pub const DEVELOPMENT_GROUP: &str = "Development";
+
 /// Get platform-specific default shortcuts
-pub fn default_shortcuts() -> HashMap<String, String> {
+pub fn default_shortcuts() -> HashMap<String, Shortcut> {
     let mut shortcuts = HashMap::new();
+    let mut add = |name: &str, mut shortcut: Shortcut, group: &str| {
+        shortcut.group = Some(group.to_string());
+        shortcuts.insert(name.to_string(), shortcut);
+    };
 
     // Common shortcuts
     if let Some(home) = dirs::home_dir() {
-        shortcuts.insert("home".to_string(), home.to_string_lossy().to_string());
-        shortcuts.insert("~".to_string(), home.to_string_lossy().to_string());
-        shortcuts.insert("h".to_string(), home.to_string_lossy().to_string());
+        add("home", Shortcut::new(home.to_string_lossy()), SYSTEM_GROUP);
+        add("~", Shortcut::alias_of("home"), SYSTEM_GROUP);
+        add("h", Shortcut::alias_of("home"), SYSTEM_GROUP);
     }
 
     if let Some(desktop) = dirs::desktop_dir() {
-        shortcuts.insert("desktop".to_string(), desktop.to_string_lossy().to_string());
-        shortcuts.insert("desk".to_string(), desktop.to_string_lossy().to_string());
+        add("desktop", Shortcut::new(desktop.to_string_lossy()), SYSTEM_GROUP);
+        add("desk", Shortcut::alias_of("desktop"), SYSTEM_GROUP);
     }
 
     if let Some(documents) = dirs::document_dir() {
-        shortcuts.insert("docs".to_string(), documents.to_string_lossy().to_string());
-        shortcuts.insert("documents".to_string(), documents.to_string_lossy().to_string());
+        add("docs", Shortcut::new(documents.to_string_lossy()), SYSTEM_GROUP);
+        add("documents", Shortcut::alias_of("docs"), SYSTEM_GROUP);
     }
 
     if let Some(downloads) = dirs::download_dir() {
-        shortcuts.insert("downloads".to_string(), downloads.to_string_lossy().to_string());
-        shortcuts.insert("dl".to_string(), downloads.to_string_lossy().to_string());
+        add("downloads", Shortcut::new(downloads.to_string_lossy()), SYSTEM_GROUP);
+        add("dl", Shortcut::alias_of("downloads"), SYSTEM_GROUP);
     }
 
     if let Some(pictures) = dirs::picture_dir() {
-        shortcuts.insert("pictures".to_string(), pictures.to_string_lossy().to_string());
-        shortcuts.insert("pics".to_string(), pictures.to_string_lossy().to_string());
+        add("pictures", Shortcut::new(pictures.to_string_lossy()), SYSTEM_GROUP);
+        add("pics", Shortcut::alias_of("pictures"), SYSTEM_GROUP);
     }
 
     if let Some(music) = dirs::audio_dir() {
-        shortcuts.insert("music".to_string(), music.to_string_lossy().to_string());
+        add("music", Shortcut::new(music.to_string_lossy()), SYSTEM_GROUP);
     }
 
     if let Some(videos) = dirs::video_dir() {
-        shortcuts.insert("videos".to_string(), videos.to_string_lossy().to_string());
+        add("videos", Shortcut::new(videos.to_string_lossy()), SYSTEM_GROUP);
     }
 
     if let Some(config) = dirs::config_dir() {
-        shortcuts.insert("config".to_string(), config.to_string_lossy().to_string());
-        shortcuts.insert("cfg".to_string(), config.to_string_lossy().to_string());
+        add("config", Shortcut::new(config.to_string_lossy()), SYSTEM_GROUP);
+        add("cfg", Shortcut::alias_of("config"), SYSTEM_GROUP);
     }
 
     // Development shortcuts
     if let Some(home) = dirs::home_dir() {
         let dev = home.join("dev");
         if dev.exists() {
-            shortcuts.insert("dev".to_string(), dev.to_string_lossy().to_string());
+            add("dev", Shortcut::new(dev.to_string_lossy()), DEVELOPMENT_GROUP);
         }
 
         let projects = home.join("projects");
         if projects.exists() {
-            shortcuts.insert("projects".to_string(), projects.to_string_lossy().to_string());
-            shortcuts.insert("proj".to_string(), projects.to_string_lossy().to_string());
+            add("projects", Shortcut::new(projects.to_string_lossy()), DEVELOPMENT_GROUP);
+            add("proj", Shortcut::alias_of("projects"), DEVELOPMENT_GROUP);
         }
 
         let workspace = home.join("workspace");
         if workspace.exists() {
-            shortcuts.insert("workspace".to_string(), workspace.to_string_lossy().to_string());
-            shortcuts.insert("ws".to_string(), workspace.to_string_lossy().to_string());
+            add("workspace", Shortcut::new(workspace.to_string_lossy()), DEVELOPMENT_GROUP);
+            add("ws", Shortcut::alias_of("workspace"), DEVELOPMENT_GROUP);
         }
 
         // Git repositories
         let repos = home.join("repos");
         if repos.exists() {
-            shortcuts.insert("repos".to_string(), repos.to_string_lossy().to_string());
+            add("repos", Shortcut::new(repos.to_string_lossy()), DEVELOPMENT_GROUP);
         }
 
         let github = home.join("github");
         if github.exists() {
-            shortcuts.insert("github".to_string(), github.to_string_lossy().to_string());
-            shortcuts.insert("gh".to_string(), github.to_string_lossy().to_string());
+            add("github", Shortcut::new(github.to_string_lossy()), DEVELOPMENT_GROUP);
+            add("gh", Shortcut::alias_of("github"), DEVELOPMENT_GROUP);
         }
     }
 
@@ -104,51 +114,38 @@ pub fn default_shortcuts() -> HashMap<String, String> {
 //     }
 // }
 
-// /// Get list of common file managers for the platform
-// pub fn common_file_managers() -> Vec<&'static str> {
-//     #[cfg(target_os = "windows")]
-//     {
-//         vec![
-//             "explorer",
-//             "totalcmd",
-//             "doublecmd",
-//             "files",  // Files (Windows File Manager alternative)
-//             "onecommander",
-//         ]
-//     }
-
-//     #[cfg(target_os = "macos")]
-//     {
-//         vec![
-//             "open",           // Default Finder
-//             "finder",
-//             "pathfinder",
-//             "forklift",
-//             "commanderone",
-//         ]
-//     }
-
-//     #[cfg(target_os = "linux")]
-//     {
-//         vec![
-//             "xdg-open",       // Default
-//             "nautilus",       // GNOME Files
-//             "dolphin",        // KDE
-//             "thunar",         // XFCE
-//             "pcmanfm",        // LXDE/LXQt
-//             "nemo",           // Cinnamon
-//             "caja",           // MATE
-//             "ranger",         // Terminal-based
-//             "vifm",           // Terminal-based
-//             "mc",             // Midnight Commander
-//         ]
-//     }
-
-//     #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
-//     {
-//         vec!["xdg-open"]
-//     }
-// }
+/// Common file managers for `os` (one of `PLATFORMS`)
+pub fn common_file_managers(os: &str) -> &'static [&'static str] {
+    match os {
+        "windows" => &[
+            "explorer",
+            "totalcmd",
+            "doublecmd",
+            "files",  // Files (Windows File Manager alternative)
+            "onecommander",
+        ],
+        "macos" => &[
+            "open",           // Default Finder
+            "finder",
+            "pathfinder",
+            "forklift",
+            "commanderone",
+        ],
+        "linux" => &[
+            "xdg-open",       // Default
+            "nautilus",       // GNOME Files
+            "dolphin",        // KDE
+            "thunar",         // XFCE
+            "pcmanfm",        // LXDE/LXQt
+            "nemo",           // Cinnamon
+            "caja",           // MATE
+            "ranger",         // Terminal-based
+            "vifm",           // Terminal-based
+            "mc",             // Midnight Commander
+        ],
+        _ => &[],
+    }
+}
 
 /// Detect the current desktop environment on Linux
 pub fn detect_desktop_environment() -> Option<String> {
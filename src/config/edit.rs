@@ -0,0 +1,115 @@
+//! Format-preserving updates of an existing config file
+//!
+//! Saving rewrites only the entries whose values changed, so comments,
+//! key order and formatting added by hand survive.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use toml_edit::{DocumentMut, Item, Table, TableLike, Value};
+
+use super::shortcut::Shortcut;
+
+/// Render `config` as TOML, editing `existing` in place when it parses
+pub fn update<T: Serialize>(existing: &str, config: &T) -> Result<String> {
+    let Ok(mut document) = existing.parse::<DocumentMut>() else {
+        return Ok(toml::to_string_pretty(config)?);
+    };
+
+    let updated = toml_edit::ser::to_document(config)?;
+    merge_table(document.as_table_mut(), updated.as_table(), true);
+    Ok(document.to_string())
+}
+
+/// Write `shortcut` as shortcut `name` of `existing`, leaving every other
+/// entry as it is; `None` when the file has no such shortcut
+pub fn update_shortcut(existing: &str, name: &str, shortcut: &Shortcut) -> Result<Option<String>> {
+    let mut document = existing.parse::<DocumentMut>()?;
+    let Some(current) = document
+        .get_mut("shortcuts")
+        .and_then(Item::as_table_like_mut)
+        .and_then(|shortcuts| shortcuts.get_mut(name))
+    else {
+        return Ok(None);
+    };
+
+    let updated = toml_edit::ser::to_document(&HashMap::from([(name, shortcut)]))?;
+    merge_item(current, &updated[name]);
+    Ok(Some(document.to_string()))
+}
+
+/// Lay out new sections as `[table]` headers, the way a fresh file is written
+fn expand_inline(item: Item) -> Item {
+    match item {
+        Item::Value(Value::InlineTable(inline)) => {
+            let mut table = Table::new();
+            for (key, value) in inline {
+                table.insert(&key, expand_inline(Item::Value(value)));
+            }
+            // A table holding only subtables needs no header of its own
+            table.set_implicit(table.iter().all(|(_, item)| item.is_table()) && !table.is_empty());
+            Item::Table(table)
+        }
+        item => item,
+    }
+}
+
+/// Make `existing` hold the entries of `updated`, touching only what differs
+///
+/// `standard` is set when `existing` is a `[table]` rather than an inline table.
+fn merge_table(existing: &mut dyn TableLike, updated: &dyn TableLike, standard: bool) {
+    let removed: Vec<String> = existing
+        .iter()
+        .map(|(key, _)| key.to_string())
+        .filter(|key| !updated.contains_key(key))
+        .collect();
+    for key in removed {
+        existing.remove(&key);
+    }
+
+    for (key, item) in updated.iter() {
+        match existing.get_mut(key) {
+            Some(current) => merge_item(current, item),
+            None => {
+                let item = if standard {
+                    expand_inline(item.clone())
+                } else {
+                    item.clone()
+                };
+                existing.insert(key, item);
+            }
+        }
+    }
+}
+
+fn merge_item(existing: &mut Item, updated: &Item) {
+    let standard = existing.is_table();
+    if let (Some(current), Some(new)) = (existing.as_table_like_mut(), updated.as_table_like()) {
+        merge_table(current, new, standard);
+        return;
+    }
+
+    if let (Some(current), Some(new)) = (existing.as_value_mut(), updated.as_value()) {
+        if !same_value(current, new) {
+            // Keep the surrounding whitespace and any trailing comment
+            let decor = current.decor().clone();
+            *current = new.clone();
+            *current.decor_mut() = decor;
+        }
+        return;
+    }
+
+    *existing = updated.clone();
+}
+
+/// Compare values by content, ignoring how they are written
+fn same_value(a: &Value, b: &Value) -> bool {
+    let parse = |value: &Value| {
+        value
+            .to_string()
+            .parse::<toml_edit::de::ValueDeserializer>()
+            .ok()
+            .and_then(|de| toml::Value::deserialize(de).ok())
+    };
+    matches!((parse(a), parse(b)), (Some(a), Some(b)) if a == b)
+}
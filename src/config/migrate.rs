@@ -0,0 +1,93 @@
+//! Upgrades of configs written by older versions
+//!
+//! Each step is tagged with the version that introduced the format it
+//! produces, and runs only for configs saved before that version.
+
+use super::AppConfig;
+use super::defaults::default_shortcuts;
+use super::shortcut::LEGACY_ALIAS_PREFIX;
+
+/// A migration and the version it upgrades to
+type Step = (&'static str, fn(&mut AppConfig) -> bool);
+
+/// Migration steps, oldest first
+const STEPS: &[Step] = &[
+    ("0.1.14", split_legacy_aliases),
+    ("0.1.14", group_default_shortcuts),
+];
+
+/// Bring `config` up to the running version
+///
+/// Returns whether any step changed a setting. The new `version` is
+/// written with the next save; configs from a newer navr are left alone.
+pub fn migrate(config: &mut AppConfig) -> bool {
+    let current = env!("CARGO_PKG_VERSION");
+    // An unreadable version is treated as older than every step
+    let stored = parse_version(&config.version).unwrap_or_default();
+    if parse_version(current).is_some_and(|current| stored >= current) {
+        return false;
+    }
+
+    let mut changed = false;
+    for (since, step) in STEPS {
+        if parse_version(since).is_some_and(|since| stored < since) {
+            tracing::debug!("Migrating config from {} to {}", config.version, since);
+            changed |= step(config);
+        }
+    }
+    config.version = current.to_string();
+    changed
+}
+
+/// `major.minor.patch`, ignoring any pre-release suffix
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let version = version.trim().split(['-', '+']).next()?;
+    let mut parts = version.split('.');
+    // Missing parts count as zero, so "0.1" is 0.1.0
+    let mut next = || parts.next().map_or(Some(0), |part| part.parse().ok());
+    Some((next()?, next()?, next()?))
+}
+
+/// Turn `@name` paths into `alias_of` entries
+///
+/// Older versions stored an alias as its target's name behind an `@`, which
+/// a directory named like that could not be told apart from. Only paths
+/// naming an existing shortcut are converted.
+fn split_legacy_aliases(config: &mut AppConfig) -> bool {
+    let targets: Vec<(String, String)> = config
+        .shortcuts
+        .iter()
+        .filter(|(_, shortcut)| shortcut.alias_of.is_none())
+        .filter_map(|(name, shortcut)| {
+            let target = shortcut.path.strip_prefix(LEGACY_ALIAS_PREFIX)?;
+            config.find_shortcut_key(target).map(|_| (name.clone(), target.to_string()))
+        })
+        .collect();
+
+    for (name, target) in &targets {
+        if let Some(shortcut) = config.shortcuts.get_mut(name) {
+            shortcut.path.clear();
+            shortcut.alias_of = Some(target.clone());
+        }
+    }
+    !targets.is_empty()
+}
+
+/// Put untouched built-in shortcuts into the groups they are created with now
+///
+/// Older versions created them without a group, so `jump --list` showed
+/// them all under the default heading.
+fn group_default_shortcuts(config: &mut AppConfig) -> bool {
+    let mut changed = false;
+    for (name, default) in default_shortcuts() {
+        if let Some(shortcut) = config.shortcuts.get_mut(&name)
+            && shortcut.group.is_none()
+            && shortcut.path == default.path
+            && shortcut.alias_of == default.alias_of
+        {
+            shortcut.group = default.group;
+            changed = true;
+        }
+    }
+    changed
+}
@@ -3,26 +3,51 @@
 //! Handles loading, saving, and modifying application configuration
 
 use anyhow::{Context, Result};
+use globset::{Glob, GlobBuilder, GlobSetBuilder};
+use owo_colors::{OwoColorize, Stream::Stderr};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::ops::RangeInclusive;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::error::NavrError;
+use crate::lock::with_lock;
 
 pub mod defaults;
+mod edit;
+mod migrate;
+pub mod paths;
+pub mod schema;
+pub mod shortcut;
+
+pub use shortcut::{
+    is_remote_uri, numbered_name, shortcut_name_for, validate_shortcut_name, Shortcut,
+};
 
 /// Application configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AppConfig {
     /// Version of the configuration format
-    #[serde(default = "default_version")]
+    ///
+    /// Files without one predate versioning and get every migration.
+    #[serde(default = "unversioned")]
+    #[schemars(default = "default_version")]
     pub version: String,
 
     /// Default file manager to use
     #[serde(default)]
     pub default_file_manager: Option<String>,
 
-    /// Directory shortcuts (alias -> path)
+    /// Command that opens remote shortcuts such as `ssh://host/path`
+    #[serde(default)]
+    pub remote_opener: Option<String>,
+
+    /// Directory shortcuts (alias -> shortcut)
     #[serde(default)]
-    pub shortcuts: HashMap<String, String>,
+    pub shortcuts: HashMap<String, Shortcut>,
 
     /// Shell integration settings
     #[serde(default)]
@@ -39,9 +64,21 @@ pub struct AppConfig {
     /// Custom file managers per platform
     #[serde(default)]
     pub file_managers: HashMap<String, String>,
+
+    /// File this configuration is saved to (defaults to `config_path()`)
+    #[serde(skip)]
+    config_file: Option<PathBuf>,
+
+    /// Report writes and spawned programs instead of performing them
+    #[serde(skip)]
+    dry_run: bool,
+
+    /// Shortcuts added with `jump --add --temp` for this shell session only
+    #[serde(skip)]
+    session_shortcuts: HashMap<String, String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ShellConfig {
     /// Enable shell integration
     #[serde(default = "default_true")]
@@ -62,9 +99,159 @@ pub struct ShellConfig {
     /// Maximum history entries
     #[serde(default = "default_max_history")]
     pub max_history: usize,
+
+    /// Maximum entries kept on the `jump --back` stack
+    #[serde(default = "default_max_stack")]
+    pub max_stack: usize,
+
+    /// Glob patterns for directories never recorded in history
+    ///
+    /// Patterns use glob syntax (`*`, `**`, `?`, `[a-z]`, `{a,b}`), where
+    /// `*` stays within one path component. Absolute and `~`-relative
+    /// patterns match from the root; other patterns match at any depth. A
+    /// matching directory also excludes everything beneath it.
+    #[serde(default)]
+    pub ignore_patterns: Vec<String>,
+}
+
+impl ShellConfig {
+    /// Check whether visits to `path` should be left out of the history
+    ///
+    /// Patterns that do not compile are skipped.
+    pub fn ignores(&self, path: &Path) -> bool {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &self.ignore_patterns {
+            if pattern.trim().is_empty() {
+                continue;
+            }
+            match Self::ignore_glob(pattern) {
+                Ok(glob) => {
+                    builder.add(glob);
+                }
+                Err(e) => tracing::debug!("Skipping ignore pattern: {}", e),
+            }
+        }
+        let Ok(set) = builder.build() else {
+            return false;
+        };
+        path.ancestors().any(|dir| set.is_match(dir))
+    }
+
+    /// Compile an entry of `ignore_patterns` as `ignores` matches it
+    pub fn ignore_glob(pattern: &str) -> Result<Glob, globset::Error> {
+        let pattern = shellexpand::tilde(pattern.trim());
+        let pattern = if Path::new(pattern.as_ref()).has_root() {
+            pattern.to_string()
+        } else {
+            format!("**/{}", pattern)
+        };
+        GlobBuilder::new(&pattern).literal_separator(true).build()
+    }
+}
+
+/// How shortcut names are matched against a partial query
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionStyle {
+    /// Names starting with the query
+    Prefix,
+    /// Names containing the query as a subsequence, best match first
+    Fuzzy,
+    /// Only the name equal to the query; no suggestions
+    Exact,
+}
+
+impl CompletionStyle {
+    pub const NAMES: &'static [&'static str] = &["prefix", "fuzzy", "exact"];
+}
+
+impl std::str::FromStr for CompletionStyle {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "prefix" => Ok(Self::Prefix),
+            "fuzzy" => Ok(Self::Fuzzy),
+            "exact" => Ok(Self::Exact),
+            _ => anyhow::bail!(
+                "Invalid completion style '{}': expected one of {}",
+                s,
+                Self::NAMES.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for CompletionStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Prefix => "prefix",
+            Self::Fuzzy => "fuzzy",
+            Self::Exact => "exact",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Whether a bare jump/open target is looked up as a shortcut or a path first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolutionOrder {
+    /// Shortcuts shadow directories of the same name
+    ShortcutFirst,
+    /// Existing paths shadow shortcuts of the same name
+    PathFirst,
+}
+
+impl ResolutionOrder {
+    pub const NAMES: &'static [&'static str] = &["shortcut_first", "path_first"];
+}
+
+impl std::str::FromStr for ResolutionOrder {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "shortcut_first" => Ok(Self::ShortcutFirst),
+            "path_first" => Ok(Self::PathFirst),
+            _ => anyhow::bail!(
+                "Invalid resolution order '{}': expected one of {}",
+                s,
+                Self::NAMES.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for ResolutionOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::ShortcutFirst => "shortcut_first",
+            Self::PathFirst => "path_first",
+        };
+        f.write_str(name)
+    }
+}
+
+impl ShellConfig {
+    /// Parsed completion style, falling back to fuzzy for unknown values
+    pub fn completion_style(&self) -> CompletionStyle {
+        self.completion_style.parse().unwrap_or_else(|e| {
+            tracing::warn!("{}; using fuzzy", e);
+            CompletionStyle::Fuzzy
+        })
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+impl BehaviorConfig {
+    /// Parsed resolution order, falling back to shortcut_first for unknown values
+    pub fn resolution_order(&self) -> ResolutionOrder {
+        self.resolution_order.parse().unwrap_or_else(|e| {
+            tracing::warn!("{}; using shortcut_first", e);
+            ResolutionOrder::ShortcutFirst
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BehaviorConfig {
     /// Confirm before overwriting shortcuts
     #[serde(default = "default_true")]
@@ -85,9 +272,54 @@ pub struct BehaviorConfig {
     /// Default to home directory if no target specified
     #[serde(default = "default_true")]
     pub default_to_home: bool,
+
+    /// Allow shortcuts that resolve to files (uses the containing directory)
+    #[serde(default = "default_false")]
+    pub allow_file_shortcuts: bool,
+
+    /// Permission mode for directories created by `create_missing`, such as
+    /// `"0700"`; Unix only, and still subject to the umask
+    #[serde(default, with = "octal_mode", skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<String>")]
+    pub create_missing_mode: Option<u32>,
+
+    /// Offer to retry with sudo/doas when creating a directory is denied
+    #[serde(default = "default_false")]
+    pub allow_privileged_mkdir: bool,
+
+    /// Whether shortcuts or paths win when a target could be either
+    #[serde(default = "default_resolution_order")]
+    pub resolution_order: String,
+
+    /// Prefix such as `"@"` that makes a target always name a shortcut
+    #[serde(default)]
+    pub shortcut_sigil: Option<String>,
+
+    /// Let `open` reuse an existing file manager window instead of starting a new one
+    #[serde(default = "default_false")]
+    pub reuse_window: bool,
+
+    /// Offer to extract zip and tar archives when `open` is pointed at one
+    #[serde(default = "default_false")]
+    pub open_archives: bool,
+
+    /// Seconds `open --wait` blocks before reporting the file manager as
+    /// possibly stuck; 0 waits indefinitely
+    #[serde(default)]
+    pub spawn_timeout_secs: u64,
+
+    /// Most shortcuts suggested after a failed jump and previewed by
+    /// `config show`; 0 turns suggestions off
+    #[serde(default = "default_max_suggestions")]
+    pub max_suggestions: usize,
+
+    /// Start programs launched by `open` in a session of their own, so
+    /// they survive the terminal closing
+    #[serde(default = "default_false")]
+    pub detach_children: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 pub struct PlatformConfig {
     /// Windows-specific settings
     #[serde(default)]
@@ -102,7 +334,7 @@ pub struct PlatformConfig {
     pub linux: LinuxConfig,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 pub struct WindowsConfig {
     /// Use Windows Terminal
     #[serde(default = "default_true")]
@@ -115,9 +347,14 @@ pub struct WindowsConfig {
     /// Preferred file manager
     #[serde(default)]
     pub file_manager: Option<String>,
+
+    /// Open folders through ShellExecuteW instead of spawning explorer.exe,
+    /// for shells that replace Explorer
+    #[serde(default)]
+    pub use_shell_execute: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 pub struct MacOSConfig {
     /// Use Finder integration
     #[serde(default = "default_true")]
@@ -132,7 +369,7 @@ pub struct MacOSConfig {
     pub file_manager: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct LinuxConfig {
     /// Preferred terminal
     #[serde(default)]
@@ -145,6 +382,165 @@ pub struct LinuxConfig {
     /// Preferred file manager
     #[serde(default)]
     pub file_manager: Option<String>,
+
+    /// Under WSL, open paths in Windows Explorer unless a file manager is configured
+    #[serde(default = "default_true")]
+    pub wsl_interop: bool,
+}
+
+/// Where the file manager returned by `resolve_file_manager` came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileManagerSource {
+    /// Set in the configuration and found on this system
+    Configured(String),
+    /// Nothing configured; detected for the current environment
+    Detected(String),
+    /// The configured one is missing; a common one for this platform is used
+    Fallback { missing: String, name: String },
+}
+
+impl FileManagerSource {
+    pub fn into_name(self) -> String {
+        match self {
+            FileManagerSource::Configured(name)
+            | FileManagerSource::Detected(name)
+            | FileManagerSource::Fallback { name, .. } => name,
+        }
+    }
+}
+
+/// What `AppConfig::set_shortcut` did
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetOutcome {
+    /// The name was new
+    Inserted,
+    /// An existing shortcut of that name was replaced
+    Overwritten,
+    /// The name exists and `behavior.confirm_overwrite` asks for a
+    /// confirmation first; nothing was changed
+    Refused,
+}
+
+/// How `AppConfig::merge` resolves entries defined on both sides
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeStrategy {
+    /// Keep the existing entry (default)
+    #[default]
+    KeepExisting,
+    /// Replace the existing entry with the incoming one
+    PreferIncoming,
+}
+
+/// Kind of value a configuration key accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigValueKind {
+    Bool,
+    Integer,
+    String,
+    /// One of a fixed set of values
+    Choice(&'static [&'static str]),
+}
+
+/// A configuration key addressable via `config set`/`config get`
+#[derive(Debug, Clone, Copy)]
+pub struct ConfigKey {
+    pub name: &'static str,
+    pub kind: ConfigValueKind,
+    /// Deprecated keys are still accepted but no longer suggested
+    pub deprecated: bool,
+}
+
+impl ConfigKey {
+    const fn new(name: &'static str, kind: ConfigValueKind) -> Self {
+        Self {
+            name,
+            kind,
+            deprecated: false,
+        }
+    }
+}
+
+/// Canonical list of keys understood by `set_value`/`get_value`
+pub const CONFIG_KEYS: &[ConfigKey] = &[
+    ConfigKey::new("default_file_manager", ConfigValueKind::String),
+    ConfigKey::new("remote_opener", ConfigValueKind::String),
+    ConfigKey::new("shell.enabled", ConfigValueKind::Bool),
+    ConfigKey::new("shell.completion_style", ConfigValueKind::Choice(CompletionStyle::NAMES)),
+    ConfigKey::new("shell.hook_cd", ConfigValueKind::Bool),
+    ConfigKey::new("shell.track_history", ConfigValueKind::Bool),
+    ConfigKey::new("shell.max_history", ConfigValueKind::Integer),
+    ConfigKey::new("shell.max_stack", ConfigValueKind::Integer),
+    ConfigKey::new("shell.ignore_patterns", ConfigValueKind::String),
+    ConfigKey::new("behavior.confirm_overwrite", ConfigValueKind::Bool),
+    ConfigKey::new("behavior.create_missing", ConfigValueKind::Bool),
+    ConfigKey::new("behavior.follow_symlinks", ConfigValueKind::Bool),
+    ConfigKey::new("behavior.case_sensitive", ConfigValueKind::Bool),
+    ConfigKey::new("behavior.default_to_home", ConfigValueKind::Bool),
+    ConfigKey::new("behavior.allow_file_shortcuts", ConfigValueKind::Bool),
+    ConfigKey::new("behavior.create_missing_mode", ConfigValueKind::String),
+    ConfigKey::new("behavior.allow_privileged_mkdir", ConfigValueKind::Bool),
+    ConfigKey::new("behavior.resolution_order", ConfigValueKind::Choice(ResolutionOrder::NAMES)),
+    ConfigKey::new("behavior.shortcut_sigil", ConfigValueKind::String),
+    ConfigKey::new("behavior.reuse_window", ConfigValueKind::Bool),
+    ConfigKey::new("behavior.open_archives", ConfigValueKind::Bool),
+    ConfigKey::new("behavior.spawn_timeout_secs", ConfigValueKind::Integer),
+    ConfigKey::new("behavior.max_suggestions", ConfigValueKind::Integer),
+    ConfigKey::new("behavior.detach_children", ConfigValueKind::Bool),
+    ConfigKey::new("platform.windows.use_windows_terminal", ConfigValueKind::Bool),
+    ConfigKey::new("platform.windows.use_powershell_aliases", ConfigValueKind::Bool),
+    ConfigKey::new("platform.windows.file_manager", ConfigValueKind::String),
+    ConfigKey::new("platform.windows.use_shell_execute", ConfigValueKind::Bool),
+    ConfigKey::new("platform.macos.use_finder", ConfigValueKind::Bool),
+    ConfigKey::new("platform.macos.prefer_iterm2", ConfigValueKind::Bool),
+    ConfigKey::new("platform.macos.file_manager", ConfigValueKind::String),
+    ConfigKey::new("platform.linux.terminal", ConfigValueKind::String),
+    ConfigKey::new("platform.linux.desktop_env", ConfigValueKind::String),
+    ConfigKey::new("platform.linux.file_manager", ConfigValueKind::String),
+    ConfigKey::new("platform.linux.wsl_interop", ConfigValueKind::Bool),
+];
+
+/// Accepted values of the numeric settings, checked by `set_value` and on load
+const NUMERIC_RANGES: &[(&str, RangeInclusive<u64>)] = &[
+    ("shell.max_history", 1..=1_000_000),
+    ("shell.max_stack", 1..=10_000),
+    // A day is already far longer than any file manager takes to start
+    ("behavior.spawn_timeout_secs", 0..=86_400),
+    ("behavior.max_suggestions", 0..=100),
+];
+
+/// The accepted values of numeric setting `key`
+pub fn numeric_range(key: &str) -> Option<&'static RangeInclusive<u64>> {
+    NUMERIC_RANGES.iter().find(|(name, _)| *name == key).map(|(_, range)| range)
+}
+
+/// Parse `value` for numeric setting `key`, rejecting it outside `NUMERIC_RANGES`
+fn parse_numeric(key: &str, value: &str) -> Result<u64> {
+    let range = numeric_range(key).with_context(|| format!("{} is not a numeric setting", key))?;
+    value
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .filter(|n| range.contains(n))
+        .with_context(|| {
+            format!(
+                "{} must be a number from {} to {}, not '{}'",
+                key,
+                range.start(),
+                range.end(),
+                value
+            )
+        })
+}
+
+/// Maximum number of aliases followed when resolving a shortcut
+const MAX_ALIAS_DEPTH: usize = 16;
+
+/// Prefix for keys addressing entries of the `file_managers` map
+const FILE_MANAGERS_PREFIX: &str = "file_managers.";
+
+/// Look up a configuration key by name
+pub fn find_config_key(name: &str) -> Option<&'static ConfigKey> {
+    CONFIG_KEYS.iter().find(|k| k.name == name)
 }
 
 impl Default for AppConfig {
@@ -152,11 +548,64 @@ impl Default for AppConfig {
         Self {
             version: default_version(),
             default_file_manager: None,
+            remote_opener: None,
             shortcuts: HashMap::new(),
             shell: ShellConfig::default(),
             behavior: BehaviorConfig::default(),
             platform: PlatformConfig::default(),
             file_managers: HashMap::new(),
+            config_file: None,
+            dry_run: false,
+            session_shortcuts: HashMap::new(),
+        }
+    }
+}
+
+// Keep in sync with the serde field defaults so a fresh config matches a
+// config file with the section omitted
+impl Default for ShellConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_true(),
+            completion_style: default_completion_style(),
+            hook_cd: default_true(),
+            track_history: default_true(),
+            max_history: default_max_history(),
+            max_stack: default_max_stack(),
+            ignore_patterns: Vec::new(),
+        }
+    }
+}
+
+impl Default for LinuxConfig {
+    fn default() -> Self {
+        Self {
+            terminal: None,
+            desktop_env: None,
+            file_manager: None,
+            wsl_interop: default_true(),
+        }
+    }
+}
+
+impl Default for BehaviorConfig {
+    fn default() -> Self {
+        Self {
+            confirm_overwrite: default_true(),
+            create_missing: default_false(),
+            follow_symlinks: default_true(),
+            case_sensitive: default_false(),
+            default_to_home: default_true(),
+            allow_file_shortcuts: default_false(),
+            create_missing_mode: None,
+            allow_privileged_mkdir: default_false(),
+            resolution_order: default_resolution_order(),
+            shortcut_sigil: None,
+            reuse_window: default_false(),
+            open_archives: default_false(),
+            spawn_timeout_secs: 0,
+            max_suggestions: default_max_suggestions(),
+            detach_children: default_false(),
         }
     }
 }
@@ -168,6 +617,12 @@ impl AppConfig {
         
         if config_path.exists() {
             Self::load_from_path(&config_path)
+        } else if let Some(profile) = paths::active_profile()? {
+            anyhow::bail!(
+                "Profile '{}' does not exist; create it with 'navr config profile create {}'",
+                profile,
+                profile
+            )
         } else {
             let config = Self::default();
             config.save()?;
@@ -180,120 +635,504 @@ impl AppConfig {
         let content = std::fs::read_to_string(&path)
             .with_context(|| format!("Failed to read config from {:?}", path.as_ref()))?;
         
-        let config: AppConfig = toml::from_str(&content)
-            .with_context(|| format!("Failed to parse config from {:?}", path.as_ref()))?;
-        
+        let mut config: AppConfig = toml::from_str(&content).map_err(|source| {
+            let backup = backup_path(path.as_ref());
+            NavrError::ConfigParse {
+                path: path.as_ref().to_path_buf(),
+                backup: backup.exists().then_some(backup),
+                source,
+            }
+        })?;
+        config.config_file = Some(path.as_ref().to_path_buf());
+        config.upgrade();
+
         Ok(config)
     }
 
-    /// Save configuration to default location
+    /// Migrate settings written by an older navr and clamp out-of-range values
+    pub fn upgrade(&mut self) {
+        let written_by = self.version.clone();
+        if migrate::migrate(self) {
+            tracing::info!("Upgraded config written by navr {}", written_by);
+        }
+        self.sanitize();
+    }
+
+    /// Bring numeric settings edited by hand back into `NUMERIC_RANGES`,
+    /// logging each value that had to be changed
+    fn sanitize(&mut self) {
+        let clamp = |key: &str, value: u64| {
+            let Some(range) = numeric_range(key) else {
+                return value;
+            };
+            let clamped = value.clamp(*range.start(), *range.end());
+            if clamped != value {
+                tracing::warn!("{} = {} is out of range; using {}", key, value, clamped);
+            }
+            clamped
+        };
+
+        self.shell.max_history = clamp("shell.max_history", self.shell.max_history as u64) as usize;
+        self.shell.max_stack = clamp("shell.max_stack", self.shell.max_stack as u64) as usize;
+        self.behavior.spawn_timeout_secs =
+            clamp("behavior.spawn_timeout_secs", self.behavior.spawn_timeout_secs);
+        self.behavior.max_suggestions =
+            clamp("behavior.max_suggestions", self.behavior.max_suggestions as u64) as usize;
+    }
+
+    /// Save configuration to the file it was loaded from
     pub fn save(&self) -> Result<()> {
-        let config_path = Self::config_path()?;
+        let config_path = self.file_path()?;
+        if self.skip_for_dry_run(|| format!("save config to {}", config_path.display())) {
+            return Ok(());
+        }
         
         // Ensure config directory exists
         if let Some(parent) = config_path.parent() {
             std::fs::create_dir_all(parent)?;
         }
         
-        let content = toml::to_string_pretty(self)?;
-        std::fs::write(&config_path, content)
-            .with_context(|| format!("Failed to write config to {:?}", config_path))?;
-        
-        Ok(())
+        with_lock(&config_path, || {
+            // Edit an existing file in place so hand-written comments survive
+            let content = match std::fs::read_to_string(&config_path) {
+                Ok(existing) => edit::update(&existing, self)?,
+                Err(_) => toml::to_string_pretty(self)?,
+            };
+            write_atomic(&config_path, &content, true)
+                .with_context(|| format!("Failed to write config to {:?}", config_path))
+        })
     }
 
-    /// Get default configuration path
+    /// Enable or disable dry-run mode for this configuration
+    pub fn set_dry_run(&mut self, dry_run: bool) {
+        self.dry_run = dry_run;
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Use these session-only shortcuts, which win over persistent ones
+    pub fn set_session_shortcuts(&mut self, shortcuts: HashMap<String, String>) {
+        self.session_shortcuts = shortcuts;
+    }
+
+    /// Session-only shortcuts, keyed by name
+    pub fn session_shortcuts(&self) -> &HashMap<String, String> {
+        &self.session_shortcuts
+    }
+
+    /// In dry-run mode, describe the action on stderr and return `true` so
+    /// the caller skips it
+    pub fn skip_for_dry_run(&self, action: impl FnOnce() -> String) -> bool {
+        if self.dry_run {
+            eprintln!(
+                "{} Would {}",
+                "[dry-run]".if_supports_color(Stderr, |t| t.yellow()),
+                action()
+            );
+        }
+        self.dry_run
+    }
+
+    /// Replace all settings with `other`, keeping the file and dry-run mode
+    pub fn replace_settings(&mut self, other: AppConfig) {
+        let config_file = self.config_file.take();
+        let dry_run = self.dry_run;
+        *self = other;
+        self.config_file = config_file;
+        self.dry_run = dry_run;
+    }
+
+    /// Get the configuration path of the active profile
     pub fn config_path() -> Result<PathBuf> {
-        let config_dir = dirs::config_dir()
-            .context("Could not determine config directory")?;
-        Ok(config_dir.join("navr").join("config.toml"))
+        paths::config_file()
+    }
+
+    /// Get the file this configuration is saved to
+    pub fn file_path(&self) -> Result<PathBuf> {
+        match &self.config_file {
+            Some(path) => Ok(path.clone()),
+            None => Self::config_path(),
+        }
+    }
+
+    /// Save this configuration to `path` from now on
+    pub fn set_file_path(&mut self, path: impl Into<PathBuf>) {
+        self.config_file = Some(path.into());
     }
 
     /// Add or update a shortcut
-    pub fn set_shortcut(&mut self, name: &str, path: &str) -> Result<()> {
+    ///
+    /// An existing shortcut is only replaced with `force` or with
+    /// `behavior.confirm_overwrite` off; otherwise the caller gets
+    /// `SetOutcome::Refused` and decides whether to ask and retry.
+    pub fn set_shortcut(&mut self, name: &str, path: &str, force: bool) -> Result<SetOutcome> {
+        let stored = self.stored_path(path)?;
+        let outcome = self.insert_shortcut(name, Shortcut::created_now(stored), force)?;
+        if outcome != SetOutcome::Refused {
+            self.save()?;
+        }
+        Ok(outcome)
+    }
+
+    /// `set_shortcut` for a prepared shortcut, without saving
+    ///
+    /// For callers that add several shortcuts and save once at the end.
+    pub fn insert_shortcut(
+        &mut self,
+        name: &str,
+        shortcut: Shortcut,
+        force: bool,
+    ) -> Result<SetOutcome> {
+        validate_shortcut_name(name)?;
+
+        let existing = self.find_shortcut_key(name).cloned();
+        if existing.is_some() && !force && self.behavior.confirm_overwrite {
+            return Ok(SetOutcome::Refused);
+        }
+
+        // A case variant replaces the old key instead of sitting beside it
+        if let Some(key) = &existing {
+            self.shortcuts.remove(key);
+        }
+        self.shortcuts.insert(name.to_string(), shortcut);
+        Ok(if existing.is_some() { SetOutcome::Overwritten } else { SetOutcome::Inserted })
+    }
+
+    /// `path` as a shortcut stores it: expanded and normalized, unless remote
+    pub fn stored_path(&self, path: &str) -> Result<String> {
         let expanded = shellexpand::full(path)?.to_string();
-        let canonical = std::fs::canonicalize(&expanded)
-            .unwrap_or_else(|_| PathBuf::from(&expanded));
-        
-        self.shortcuts.insert(name.to_string(), canonical.to_string_lossy().to_string());
-        self.save()?;
-        Ok(())
+        if is_remote_uri(&expanded) {
+            return Ok(expanded);
+        }
+        Ok(crate::platform::normalize_path(Path::new(&expanded), self.behavior.follow_symlinks)
+            .to_string_lossy()
+            .to_string())
     }
 
     /// Remove a shortcut
     pub fn remove_shortcut(&mut self, name: &str) -> Result<bool> {
-        let removed = self.shortcuts.remove(name).is_some();
-        if removed {
-            self.save()?;
+        let Some(key) = self.find_shortcut_key(name).cloned() else {
+            return Ok(false);
+        };
+
+        self.shortcuts.remove(&key);
+        self.save()?;
+        Ok(true)
+    }
+
+    /// Rename a shortcut, keeping its metadata and retargeting aliases of it
+    ///
+    /// An existing shortcut named `new` is replaced.
+    pub fn rename_shortcut(&mut self, old: &str, new: &str) -> Result<()> {
+        validate_shortcut_name(new)?;
+
+        let old_key = self
+            .find_shortcut_key(old)
+            .cloned()
+            .ok_or_else(|| NavrError::ShortcutNotFound(old.to_string()))?;
+        if let Some(existing) = self.find_shortcut_key(new).cloned()
+            && existing != old_key
+        {
+            self.shortcuts.remove(&existing);
+        }
+
+        let shortcut = self.shortcuts.remove(&old_key).expect("key was just found");
+        self.shortcuts.insert(new.to_string(), shortcut);
+
+        let case_sensitive = self.behavior.case_sensitive;
+        for shortcut in self.shortcuts.values_mut() {
+            let refers_to_old = shortcut.alias_target().is_some_and(|target| {
+                if case_sensitive {
+                    target == old_key
+                } else {
+                    target.eq_ignore_ascii_case(&old_key)
+                }
+            });
+            if refers_to_old {
+                shortcut.alias_of = Some(new.to_string());
+            }
+        }
+
+        self.save()
+    }
+
+    /// The shortcut name in `target` when it starts with `behavior.shortcut_sigil`
+    pub fn strip_shortcut_sigil<'a>(&self, target: &'a str) -> Option<&'a str> {
+        let sigil = self.behavior.shortcut_sigil.as_deref().filter(|s| !s.is_empty())?;
+        target.strip_prefix(sigil)
+    }
+
+    /// Find the stored key for a shortcut name, honoring case sensitivity
+    pub fn find_shortcut_key(&self, name: &str) -> Option<&String> {
+        if self.behavior.case_sensitive {
+            self.shortcuts.get_key_value(name).map(|(k, _)| k)
+        } else {
+            self.shortcuts.keys().find(|k| k.eq_ignore_ascii_case(name))
         }
-        Ok(removed)
     }
 
-    /// Get shortcut path
-    pub fn get_shortcut(&self, name: &str) -> Option<&String> {
+    /// Find the key of a session shortcut name, honoring case sensitivity
+    pub fn find_session_key(&self, name: &str) -> Option<&String> {
         if self.behavior.case_sensitive {
-            self.shortcuts.get(name)
+            self.session_shortcuts.get_key_value(name).map(|(k, _)| k)
         } else {
-            self.shortcuts.iter()
-                .find(|(k, _)| k.eq_ignore_ascii_case(name))
-                .map(|(_, v)| v)
+            self.session_shortcuts.keys().find(|k| k.eq_ignore_ascii_case(name))
+        }
+    }
+
+    /// Add or update a shortcut that is an alias of another shortcut
+    pub fn set_alias(&mut self, name: &str, target: &str) -> Result<()> {
+        validate_shortcut_name(name)?;
+
+        let target = self
+            .find_shortcut_key(target)
+            .cloned()
+            .ok_or_else(|| NavrError::ShortcutNotFound(target.to_string()))?;
+
+        let alias = Shortcut {
+            created_at: Some(chrono::Utc::now()),
+            ..Shortcut::alias_of(&target)
+        };
+        let previous = self.shortcuts.insert(name.to_string(), alias);
+        if let Err(e) = self.get_shortcut(name) {
+            match previous {
+                Some(previous) => self.shortcuts.insert(name.to_string(), previous),
+                None => self.shortcuts.remove(name),
+            };
+            return Err(e);
         }
+
+        self.save()?;
+        Ok(())
     }
 
-    /// Get default file manager for current platform
+    /// Get shortcut path, following aliases to the shortcut they refer to
+    ///
+    /// Session shortcuts are checked before persistent ones.
+    pub fn get_shortcut(&self, name: &str) -> Result<Option<&String>> {
+        if let Some(key) = self.find_session_key(name) {
+            return Ok(self.session_shortcuts.get(key));
+        }
+
+        let Some(mut key) = self.find_shortcut_key(name) else {
+            return Ok(None);
+        };
+
+        let mut visited = HashSet::new();
+        loop {
+            let shortcut = &self.shortcuts[key];
+            let Some(target) = shortcut.alias_target() else {
+                return Ok(Some(shortcut.current_path()));
+            };
+
+            if !visited.insert(key) {
+                anyhow::bail!("Shortcut '{}' is part of an alias cycle through '{}'", name, key);
+            }
+            if visited.len() > MAX_ALIAS_DEPTH {
+                anyhow::bail!("Shortcut '{}' exceeds the maximum alias depth of {}", name, MAX_ALIAS_DEPTH);
+            }
+
+            key = self.find_shortcut_key(target).with_context(|| {
+                format!("Shortcut '{}' is an alias of missing shortcut '{}'", key, target)
+            })?;
+        }
+    }
+
+    /// Stamp a shortcut's `last_used` time, if it exists and no session
+    /// shortcut shadows it
+    ///
+    /// Only that shortcut is rewritten, as it is in the file now, so a jump
+    /// neither replaces the backup nor drops changes made by other shells.
+    pub fn mark_shortcut_used(&mut self, name: &str) -> Result<()> {
+        if self.find_session_key(name).is_some() {
+            return Ok(());
+        }
+        let Some(key) = self.find_shortcut_key(name).cloned() else {
+            return Ok(());
+        };
+
+        let now = chrono::Utc::now();
+        if let Some(shortcut) = self.shortcuts.get_mut(&key) {
+            shortcut.last_used = Some(now);
+        }
+
+        let config_path = self.file_path()?;
+        if self.skip_for_dry_run(|| format!("record use of '{}' in {}", key, config_path.display())) {
+            return Ok(());
+        }
+
+        with_lock(&config_path, || {
+            let Ok(existing) = std::fs::read_to_string(&config_path) else {
+                return Ok(());
+            };
+            let on_disk: AppConfig = toml::from_str(&existing)?;
+            let Some(mut shortcut) = on_disk.shortcuts.get(&key).cloned() else {
+                return Ok(());
+            };
+            shortcut.last_used = Some(now);
+
+            match edit::update_shortcut(&existing, &key, &shortcut)? {
+                Some(content) => write_atomic(&config_path, &content, false),
+                None => Ok(()),
+            }
+        })
+    }
+
+    /// The command registered as the `file_managers` alias `name`, or `name`
+    /// itself when it is not an alias
+    pub fn file_manager_command(&self, name: &str) -> String {
+        self.file_managers.get(name).cloned().unwrap_or_else(|| name.to_string())
+    }
+
+    /// Get the file manager to use, falling back to auto-detection when
+    /// neither the configured one nor a common one is installed
     pub fn get_file_manager(&self) -> String {
-        // Check explicit setting first
-        if let Some(fm) = &self.default_file_manager {
-            return fm.clone();
+        match self.resolve_file_manager() {
+            Ok(FileManagerSource::Fallback { missing, name }) => {
+                tracing::warn!("File manager '{}' is not installed; using '{}'", missing, name);
+                name
+            }
+            Ok(source) => source.into_name(),
+            Err(e) => {
+                tracing::warn!("{}; falling back to auto-detection", e);
+                defaults::detect_best_file_manager()
+            }
+        }
+    }
+
+    /// Resolve the file manager
+    ///
+    /// A configured one that is missing is replaced by the first installed
+    /// common file manager for this platform; it is an error only when none
+    /// of those is installed either.
+    pub fn resolve_file_manager(&self) -> Result<FileManagerSource> {
+        // Check explicit setting first, then the platform-specific one
+        let configured = self.default_file_manager.as_ref().or(self.platform_file_manager());
+
+        match configured {
+            Some(fm) if is_command_available(fm) => Ok(FileManagerSource::Configured(fm.clone())),
+            Some(fm) => defaults::common_file_managers(std::env::consts::OS)
+                .iter()
+                .find(|name| is_command_available(name))
+                .map(|name| FileManagerSource::Fallback {
+                    missing: fm.clone(),
+                    name: name.to_string(),
+                })
+                .ok_or_else(|| NavrError::FileManagerNotInstalled(fm.clone()).into()),
+            None => Ok(FileManagerSource::Detected(defaults::detect_best_file_manager())),
         }
+    }
 
-        // Check platform-specific setting
+    /// Get the file manager configured for the current platform
+    fn platform_file_manager(&self) -> Option<&String> {
         #[cfg(target_os = "windows")]
         {
-            self.platform.windows.file_manager.clone()
-                .unwrap_or_else(|| "explorer".to_string())
+            self.platform.windows.file_manager.as_ref()
         }
 
         #[cfg(target_os = "macos")]
         {
-            self.platform.macos.file_manager.clone()
-                .unwrap_or_else(|| "open".to_string())
+            self.platform.macos.file_manager.as_ref()
         }
 
         #[cfg(target_os = "linux")]
         {
-            self.platform.linux.file_manager.clone()
-                .unwrap_or_else(|| {
-                    // Try to detect common file managers
-                    for fm in &["xdg-open", "nautilus", "dolphin", "thunar", "pcmanfm"] {
-                        if which::which(fm).is_ok() {
-                            return fm.to_string();
-                        }
-                    }
-                    "xdg-open".to_string()
-                })
+            self.platform.linux.file_manager.as_ref()
         }
 
         #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
         {
-            "xdg-open".to_string()
+            None
         }
     }
 
     /// Set configuration value by key
+    ///
+    /// Optional string keys are cleared by setting them to an empty value.
     pub fn set_value(&mut self, key: &str, value: &str) -> Result<()> {
         match key {
-            "default_file_manager" => self.default_file_manager = Some(value.to_string()),
+            "default_file_manager" => self.default_file_manager = optional(value),
+            "remote_opener" => self.remote_opener = optional(value),
             "shell.enabled" => self.shell.enabled = value.parse()?,
+            "shell.completion_style" => {
+                self.shell.completion_style = value.parse::<CompletionStyle>()?.to_string()
+            }
             "shell.hook_cd" => self.shell.hook_cd = value.parse()?,
             "shell.track_history" => self.shell.track_history = value.parse()?,
-            "shell.max_history" => self.shell.max_history = value.parse()?,
+            "shell.max_history" => self.shell.max_history = parse_numeric(key, value)? as usize,
+            "shell.max_stack" => self.shell.max_stack = parse_numeric(key, value)? as usize,
+            "shell.ignore_patterns" => {
+                let patterns: Vec<String> = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|p| !p.is_empty())
+                    .map(String::from)
+                    .collect();
+                for pattern in &patterns {
+                    ShellConfig::ignore_glob(pattern)?;
+                }
+                self.shell.ignore_patterns = patterns
+            }
             "behavior.confirm_overwrite" => self.behavior.confirm_overwrite = value.parse()?,
             "behavior.create_missing" => self.behavior.create_missing = value.parse()?,
             "behavior.follow_symlinks" => self.behavior.follow_symlinks = value.parse()?,
             "behavior.case_sensitive" => self.behavior.case_sensitive = value.parse()?,
             "behavior.default_to_home" => self.behavior.default_to_home = value.parse()?,
-            _ => anyhow::bail!("Unknown configuration key: {}", key),
+            "behavior.allow_file_shortcuts" => self.behavior.allow_file_shortcuts = value.parse()?,
+            "behavior.allow_privileged_mkdir" => {
+                self.behavior.allow_privileged_mkdir = value.parse()?
+            }
+            "behavior.resolution_order" => {
+                self.behavior.resolution_order = value.parse::<ResolutionOrder>()?.to_string()
+            }
+            "behavior.shortcut_sigil" => {
+                let sigil = optional(value);
+                if let Some(sigil) = &sigil
+                    && sigil.contains(|c: char| c.is_whitespace() || std::path::is_separator(c))
+                {
+                    anyhow::bail!("Invalid shortcut sigil '{}': use a symbol such as '@'", sigil);
+                }
+                self.behavior.shortcut_sigil = sigil
+            }
+            "behavior.reuse_window" => self.behavior.reuse_window = value.parse()?,
+            "behavior.detach_children" => self.behavior.detach_children = value.parse()?,
+            "behavior.open_archives" => self.behavior.open_archives = value.parse()?,
+            "behavior.spawn_timeout_secs" => {
+                self.behavior.spawn_timeout_secs = parse_numeric(key, value)?
+            }
+            "behavior.max_suggestions" => {
+                self.behavior.max_suggestions = parse_numeric(key, value)? as usize
+            }
+            "behavior.create_missing_mode" => {
+                self.behavior.create_missing_mode =
+                    optional(value).map(|mode| octal_mode::parse(&mode)).transpose()?
+            }
+            "platform.windows.use_windows_terminal" => {
+                self.platform.windows.use_windows_terminal = value.parse()?
+            }
+            "platform.windows.use_powershell_aliases" => {
+                self.platform.windows.use_powershell_aliases = value.parse()?
+            }
+            "platform.windows.file_manager" => self.platform.windows.file_manager = optional(value),
+            "platform.windows.use_shell_execute" => {
+                self.platform.windows.use_shell_execute = value.parse()?
+            }
+            "platform.macos.use_finder" => self.platform.macos.use_finder = value.parse()?,
+            "platform.macos.prefer_iterm2" => self.platform.macos.prefer_iterm2 = value.parse()?,
+            "platform.macos.file_manager" => self.platform.macos.file_manager = optional(value),
+            "platform.linux.terminal" => self.platform.linux.terminal = optional(value),
+            "platform.linux.desktop_env" => self.platform.linux.desktop_env = optional(value),
+            "platform.linux.file_manager" => self.platform.linux.file_manager = optional(value),
+            "platform.linux.wsl_interop" => self.platform.linux.wsl_interop = value.parse()?,
+            _ => {
+                let alias = file_manager_alias(key)?;
+                match optional(value) {
+                    Some(command) => self.file_managers.insert(alias.to_string(), command),
+                    None => self.file_managers.remove(alias),
+                };
+            }
         }
         self.save()?;
         Ok(())
@@ -303,35 +1142,133 @@ impl AppConfig {
     pub fn get_value(&self, key: &str) -> Result<String> {
         match key {
             "default_file_manager" => Ok(self.default_file_manager.clone().unwrap_or_default()),
+            "remote_opener" => Ok(self.remote_opener.clone().unwrap_or_default()),
             "shell.enabled" => Ok(self.shell.enabled.to_string()),
+            "shell.completion_style" => Ok(self.shell.completion_style.clone()),
             "shell.hook_cd" => Ok(self.shell.hook_cd.to_string()),
             "shell.track_history" => Ok(self.shell.track_history.to_string()),
             "shell.max_history" => Ok(self.shell.max_history.to_string()),
+            "shell.max_stack" => Ok(self.shell.max_stack.to_string()),
+            "shell.ignore_patterns" => Ok(self.shell.ignore_patterns.join(",")),
             "behavior.confirm_overwrite" => Ok(self.behavior.confirm_overwrite.to_string()),
             "behavior.create_missing" => Ok(self.behavior.create_missing.to_string()),
             "behavior.follow_symlinks" => Ok(self.behavior.follow_symlinks.to_string()),
             "behavior.case_sensitive" => Ok(self.behavior.case_sensitive.to_string()),
             "behavior.default_to_home" => Ok(self.behavior.default_to_home.to_string()),
-            _ => anyhow::bail!("Unknown configuration key: {}", key),
+            "behavior.allow_file_shortcuts" => Ok(self.behavior.allow_file_shortcuts.to_string()),
+            "behavior.allow_privileged_mkdir" => {
+                Ok(self.behavior.allow_privileged_mkdir.to_string())
+            }
+            "behavior.resolution_order" => Ok(self.behavior.resolution_order.clone()),
+            "behavior.shortcut_sigil" => Ok(self.behavior.shortcut_sigil.clone().unwrap_or_default()),
+            "behavior.reuse_window" => Ok(self.behavior.reuse_window.to_string()),
+            "behavior.detach_children" => Ok(self.behavior.detach_children.to_string()),
+            "behavior.open_archives" => Ok(self.behavior.open_archives.to_string()),
+            "behavior.spawn_timeout_secs" => Ok(self.behavior.spawn_timeout_secs.to_string()),
+            "behavior.max_suggestions" => Ok(self.behavior.max_suggestions.to_string()),
+            "behavior.create_missing_mode" => {
+                Ok(self.behavior.create_missing_mode.map(octal_mode::format).unwrap_or_default())
+            }
+            "platform.windows.use_windows_terminal" => {
+                Ok(self.platform.windows.use_windows_terminal.to_string())
+            }
+            "platform.windows.use_powershell_aliases" => {
+                Ok(self.platform.windows.use_powershell_aliases.to_string())
+            }
+            "platform.windows.file_manager" => {
+                Ok(self.platform.windows.file_manager.clone().unwrap_or_default())
+            }
+            "platform.windows.use_shell_execute" => {
+                Ok(self.platform.windows.use_shell_execute.to_string())
+            }
+            "platform.macos.use_finder" => Ok(self.platform.macos.use_finder.to_string()),
+            "platform.macos.prefer_iterm2" => Ok(self.platform.macos.prefer_iterm2.to_string()),
+            "platform.macos.file_manager" => {
+                Ok(self.platform.macos.file_manager.clone().unwrap_or_default())
+            }
+            "platform.linux.terminal" => Ok(self.platform.linux.terminal.clone().unwrap_or_default()),
+            "platform.linux.desktop_env" => {
+                Ok(self.platform.linux.desktop_env.clone().unwrap_or_default())
+            }
+            "platform.linux.file_manager" => {
+                Ok(self.platform.linux.file_manager.clone().unwrap_or_default())
+            }
+            "platform.linux.wsl_interop" => Ok(self.platform.linux.wsl_interop.to_string()),
+            _ => {
+                let alias = file_manager_alias(key)?;
+                self.file_managers
+                    .get(alias)
+                    .cloned()
+                    .with_context(|| format!("No file manager alias '{}' configured", alias))
+            }
+        }
+    }
+
+    /// Every gettable key with its value: `CONFIG_KEYS` in order, then the
+    /// `file_managers` aliases sorted by name
+    pub fn all_values(&self) -> Result<Vec<(String, String)>> {
+        let mut values = Vec::new();
+        for key in CONFIG_KEYS.iter().filter(|k| !k.deprecated) {
+            values.push((key.name.to_string(), self.get_value(key.name)?));
+        }
+
+        let mut aliases: Vec<&String> = self.file_managers.keys().collect();
+        aliases.sort();
+        for alias in aliases {
+            let key = format!("{}{}", FILE_MANAGERS_PREFIX, alias);
+            let value = self.get_value(&key)?;
+            values.push((key, value));
         }
+        Ok(values)
     }
 
     /// Merge with another configuration
-    pub fn merge(&mut self, other: AppConfig) {
-        // Merge shortcuts
-        for (k, v) in other.shortcuts {
-            self.shortcuts.entry(k).or_insert(v);
+    ///
+    /// `strategy` decides which side wins when both define the same
+    /// shortcut or file manager.
+    ///
+    /// Incoming shortcuts replace existing ones only under `PreferIncoming`,
+    /// and then only as `insert_shortcut` allows: without `force`, names it
+    /// refuses keep their current shortcut and are returned.
+    pub fn merge(
+        &mut self,
+        other: AppConfig,
+        strategy: MergeStrategy,
+        force: bool,
+    ) -> Result<Vec<String>> {
+        let mut refused = Vec::new();
+        let mut incoming: Vec<(String, Shortcut)> = other.shortcuts.into_iter().collect();
+        incoming.sort_by(|(a, _), (b, _)| a.cmp(b));
+        for (name, shortcut) in incoming {
+            let exists = self.find_shortcut_key(&name).is_some();
+            if exists && strategy == MergeStrategy::KeepExisting {
+                continue;
+            }
+            if self.insert_shortcut(&name, shortcut, force)? == SetOutcome::Refused {
+                refused.push(name);
+            }
         }
 
         // Merge file managers
         for (k, v) in other.file_managers {
-            self.file_managers.entry(k).or_insert(v);
+            match strategy {
+                MergeStrategy::KeepExisting => {
+                    self.file_managers.entry(k).or_insert(v);
+                }
+                MergeStrategy::PreferIncoming => {
+                    self.file_managers.insert(k, v);
+                }
+            }
         }
 
         // Override other settings if they're not default
         if other.default_file_manager.is_some() {
             self.default_file_manager = other.default_file_manager;
         }
+        if other.remote_opener.is_some() {
+            self.remote_opener = other.remote_opener;
+        }
+        Ok(refused)
     }
 
     /// Export to JSON format
@@ -345,11 +1282,112 @@ impl AppConfig {
     }
 }
 
+/// Path of the backup kept next to a config file
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".bak");
+    PathBuf::from(name)
+}
+
+/// Replace `path` with `content` without ever leaving it half-written
+///
+/// The content goes to a temporary file in the same directory which is
+/// then renamed over `path`; with `backup`, the previous file is copied to
+/// its backup path first.
+fn write_atomic(path: &Path, content: &str, backup: bool) -> Result<()> {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let file_name = path.file_name().context("Config path has no file name")?;
+    let mut tmp_name = std::ffi::OsString::from(".");
+    tmp_name.push(file_name);
+    tmp_name.push(format!(
+        ".{}.{}.tmp",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let tmp = path.with_file_name(tmp_name);
+
+    let result = (|| {
+        let mut file = std::fs::File::create(&tmp)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+
+        if backup && path.exists() {
+            std::fs::copy(path, backup_path(path))?;
+        }
+        std::fs::rename(&tmp, path)
+    })();
+
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    Ok(result?)
+}
+
+/// Check whether the program of a (possibly multi-word) command is installed
+fn is_command_available(command: &str) -> bool {
+    command
+        .split_whitespace()
+        .next()
+        .is_some_and(|program| which::which(program).is_ok())
+}
+
+/// Extract the alias from a `file_managers.<alias>` key
+fn file_manager_alias(key: &str) -> Result<&str> {
+    match key.strip_prefix(FILE_MANAGERS_PREFIX) {
+        Some(alias) if !alias.is_empty() && !alias.contains('.') => Ok(alias),
+        _ => anyhow::bail!("Unknown configuration key: {}", key),
+    }
+}
+
+/// Treat an empty value as unsetting an optional key
+fn optional(value: &str) -> Option<String> {
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
+/// Permission modes stored as octal strings like `"0755"`
+mod octal_mode {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn parse(value: &str) -> anyhow::Result<u32> {
+        let digits = value.trim().trim_start_matches("0o");
+        match u32::from_str_radix(digits, 8) {
+            Ok(mode) if mode <= 0o7777 => Ok(mode),
+            _ => anyhow::bail!("Invalid permission mode '{}': expected octal like 0755", value),
+        }
+    }
+
+    pub fn format(mode: u32) -> String {
+        format!("{:04o}", mode)
+    }
+
+    pub fn serialize<S: Serializer>(mode: &Option<u32>, serializer: S) -> Result<S::Ok, S::Error> {
+        match mode {
+            Some(mode) => serializer.serialize_str(&format(*mode)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u32>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|value| parse(&value).map_err(serde::de::Error::custom))
+            .transpose()
+    }
+}
+
 // Helper functions for serde defaults
 fn default_version() -> String {
     env!("CARGO_PKG_VERSION").to_string()
 }
 
+fn unversioned() -> String {
+    "0.0.0".to_string()
+}
+
 fn default_true() -> bool {
     true
 }
@@ -362,9 +1400,21 @@ fn default_completion_style() -> String {
     "fuzzy".to_string()
 }
 
+fn default_resolution_order() -> String {
+    "shortcut_first".to_string()
+}
+
 fn default_max_history() -> usize {
     1000
 }
 
+fn default_max_suggestions() -> usize {
+    5
+}
+
+fn default_max_stack() -> usize {
+    50
+}
+
 #[cfg(test)]
 mod tests;
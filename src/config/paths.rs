@@ -0,0 +1,198 @@
+//! Locations of the config and data directories
+//!
+//! By default these are the platform config and data directories. Setting
+//! `NAVR_CONFIG_DIR`, or passing `--portable`, puts everything in a single
+//! directory instead.
+//!
+//! A named profile keeps its config in `profiles/<name>.toml` under the
+//! config directory and its data in `profiles/<name>` under the data
+//! directory. Without one, `config.toml` and the data directory itself are
+//! used.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Environment variable that overrides both directories
+pub const CONFIG_DIR_ENV: &str = "NAVR_CONFIG_DIR";
+
+/// Environment variable selecting a profile, as exported by the shell integration
+pub const PROFILE_ENV: &str = "NAVR_PROFILE";
+
+/// Name standing for the plain `config.toml`
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// File in the config directory naming the profile used when none is given
+const CURRENT_PROFILE_FILE: &str = "current_profile";
+
+/// Directory chosen by `--portable`, taking precedence over the environment
+static PORTABLE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Profile chosen by `--profile`, taking precedence over the environment
+static PROFILE_FLAG: OnceLock<String> = OnceLock::new();
+
+/// Keep config and data next to the navr executable for this process
+pub fn use_portable_dir() -> Result<()> {
+    let exe = std::env::current_exe().context("Could not determine the navr executable path")?;
+    let dir = exe
+        .parent()
+        .context("The navr executable has no parent directory")?
+        .to_path_buf();
+    tracing::debug!("Portable mode: using {}", dir.display());
+    let _ = PORTABLE_DIR.set(dir);
+    Ok(())
+}
+
+/// Use profile `name` for this process
+pub fn use_profile(name: &str) -> Result<()> {
+    validate_profile_name(name)?;
+    let _ = PROFILE_FLAG.set(name.to_string());
+    Ok(())
+}
+
+/// The profile given with `--profile`, if any
+pub fn profile_flag() -> Option<&'static str> {
+    PROFILE_FLAG.get().map(String::as_str)
+}
+
+/// Get the directory holding `config.toml` and the profiles
+pub fn config_dir() -> Result<PathBuf> {
+    resolve(override_dir(), dirs::config_dir()).context("Could not determine config directory")
+}
+
+/// Get the config file of the active profile
+pub fn config_file() -> Result<PathBuf> {
+    Ok(profile_config_file(&config_dir()?, active_profile()?.as_deref()))
+}
+
+/// Get the directory holding history, the back stack and caches of the active profile
+pub fn data_dir() -> Result<PathBuf> {
+    data_dir_of(active_profile()?.as_deref())
+}
+
+/// Get the data directory of `profile`; `None` for the default profile
+pub fn data_dir_of(profile: Option<&str>) -> Result<PathBuf> {
+    let dir = resolve(override_dir(), dirs::data_dir()).context("Could not determine data directory")?;
+    Ok(profile_data_dir(dir, profile))
+}
+
+/// The active profile: `--profile`, then `NAVR_PROFILE`, then the one last
+/// switched to; `None` for the default profile
+pub fn active_profile() -> Result<Option<String>> {
+    let name = match profile_flag() {
+        Some(name) => Some(name.to_string()),
+        None => match std::env::var(PROFILE_ENV).ok().filter(|name| !name.is_empty()) {
+            Some(name) => {
+                validate_profile_name(&name)
+                    .with_context(|| format!("Invalid {}", PROFILE_ENV))?;
+                Some(name)
+            }
+            None => current_profile(&config_dir()?)?,
+        },
+    };
+    Ok(name.filter(|name| name != DEFAULT_PROFILE))
+}
+
+/// The profile recorded by `config profile switch` in `config_dir`
+pub fn current_profile(config_dir: &Path) -> Result<Option<String>> {
+    let file = config_dir.join(CURRENT_PROFILE_FILE);
+    match std::fs::read_to_string(&file) {
+        Ok(content) => {
+            let name = content.trim();
+            Ok((!name.is_empty() && validate_profile_name(name).is_ok()).then(|| name.to_string()))
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {:?}", file)),
+    }
+}
+
+/// Record `name` in `config_dir` as the profile to use when none is given;
+/// `None` goes back to the default profile
+pub fn set_current_profile(config_dir: &Path, name: Option<&str>) -> Result<()> {
+    let file = config_dir.join(CURRENT_PROFILE_FILE);
+    match name {
+        Some(name) => std::fs::create_dir_all(config_dir)
+            .and_then(|_| std::fs::write(&file, format!("{}\n", name))),
+        None if file.exists() => std::fs::remove_file(&file),
+        None => Ok(()),
+    }
+    .with_context(|| format!("Failed to update {:?}", file))
+}
+
+/// Config file of `profile` under `config_dir`
+pub fn profile_config_file(config_dir: &Path, profile: Option<&str>) -> PathBuf {
+    match profile {
+        Some(name) => config_dir.join("profiles").join(format!("{}.toml", name)),
+        None => config_dir.join("config.toml"),
+    }
+}
+
+/// Data directory of `profile`, given the base data directory
+pub fn profile_data_dir(data_dir: PathBuf, profile: Option<&str>) -> PathBuf {
+    match profile {
+        Some(name) => data_dir.join("profiles").join(name),
+        None => data_dir,
+    }
+}
+
+/// Profile names become file names, so they are kept to letters, digits, `-` and `_`
+pub fn validate_profile_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        anyhow::bail!(
+            "Invalid profile name '{}': use letters, digits, '-' and '_'",
+            name
+        );
+    }
+    Ok(())
+}
+
+fn override_dir() -> Option<PathBuf> {
+    PORTABLE_DIR.get().cloned().or_else(|| {
+        std::env::var_os(CONFIG_DIR_ENV)
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+    })
+}
+
+/// An override is used as-is; platform directories get a `navr` subdirectory
+fn resolve(override_dir: Option<PathBuf>, platform_dir: Option<PathBuf>) -> Option<PathBuf> {
+    override_dir.or_else(|| platform_dir.map(|dir| dir.join("navr")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_override_replaces_platform_dir() {
+        let platform = Some(PathBuf::from("/home/user/.config"));
+        let portable = Some(PathBuf::from("/media/usb/navr-bin"));
+
+        assert_eq!(resolve(None, platform.clone()), Some(PathBuf::from("/home/user/.config/navr")));
+        assert_eq!(resolve(portable.clone(), platform), portable);
+        assert_eq!(resolve(None, None), None);
+    }
+
+    #[test]
+    fn test_profile_paths() {
+        let config_dir = Path::new("/home/user/.config/navr");
+        let data_dir = PathBuf::from("/home/user/.local/share/navr");
+
+        assert_eq!(profile_config_file(config_dir, None), config_dir.join("config.toml"));
+        assert_eq!(
+            profile_config_file(config_dir, Some("work")),
+            config_dir.join("profiles").join("work.toml")
+        );
+        assert_eq!(profile_data_dir(data_dir.clone(), None), data_dir);
+        assert_eq!(
+            profile_data_dir(data_dir.clone(), Some("work")),
+            data_dir.join("profiles").join("work")
+        );
+
+        assert!(validate_profile_name("work_2024").is_ok());
+        assert!(validate_profile_name("../work").is_err());
+        assert!(validate_profile_name("").is_err());
+    }
+}
@@ -0,0 +1,60 @@
+//! JSON Schema for `config.toml`, printed by `navr config schema`
+//!
+//! The structure, descriptions and defaults are derived from the config
+//! structs; the accepted values of choice and numeric keys are filled in
+//! from `CONFIG_KEYS` and the ranges `config set` enforces.
+
+use schemars::generate::SchemaSettings;
+use serde_json::{json, Value};
+
+use super::{numeric_range, AppConfig, ConfigValueKind, CONFIG_KEYS};
+
+/// The schema of the whole config file
+pub fn schema() -> Value {
+    let generator = SchemaSettings::draft2020_12()
+        .with(|settings| settings.inline_subschemas = true)
+        .into_generator();
+    let mut schema = generator.into_root_schema_for::<AppConfig>().to_value();
+    schema["title"] = json!("navr configuration");
+
+    for key in CONFIG_KEYS {
+        let pointer = format!("/properties/{}", key.name.replace('.', "/properties/"));
+        let Some(property) = schema.pointer_mut(&pointer) else {
+            continue;
+        };
+        if let ConfigValueKind::Choice(names) = key.kind {
+            property["enum"] = json!(names);
+        }
+        if let Some(range) = numeric_range(key.name) {
+            property["minimum"] = json!(range.start());
+            property["maximum"] = json!(range.end());
+        }
+        if key.deprecated {
+            property["deprecated"] = Value::Bool(true);
+        }
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_describes_keys_and_defaults() {
+        let schema = schema();
+        let properties = &schema["properties"];
+
+        assert_eq!(properties["shortcuts"]["type"], "object");
+        assert_eq!(properties["version"]["default"], AppConfig::default().version);
+        assert_eq!(properties["behavior"]["properties"]["case_sensitive"]["type"], "boolean");
+        assert_eq!(properties["shell"]["properties"]["max_history"]["default"], 1000);
+        assert_eq!(properties["shell"]["properties"]["max_history"]["minimum"], 1);
+        assert_eq!(properties["shell"]["properties"]["completion_style"]["default"], "fuzzy");
+        assert_eq!(properties["shell"]["properties"]["ignore_patterns"]["type"], "array");
+        assert_eq!(
+            properties["platform"]["properties"]["linux"]["properties"]["wsl_interop"]["type"],
+            "boolean"
+        );
+    }
+}
@@ -0,0 +1,248 @@
+//! Shortcut entries and their optional metadata
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Names that would be ambiguous with real paths
+const RESERVED_NAMES: &[&str] = &[".", ".."];
+
+/// Platform names accepted as `platform_overrides` keys
+pub const PLATFORMS: &[&str] = &["windows", "macos", "linux"];
+
+/// Group listing shortcuts that have no `group` set
+pub const DEFAULT_GROUP: &str = "Custom";
+
+/// Prefix that marked a path as an alias before `alias_of` existed
+pub const LEGACY_ALIAS_PREFIX: char = '@';
+
+/// Check that a shortcut name is usable on the command line
+pub fn validate_shortcut_name(name: &str) -> Result<()> {
+    if name.is_empty() {
+        anyhow::bail!("Shortcut name cannot be empty");
+    }
+    if name.starts_with('-') {
+        anyhow::bail!("Invalid shortcut name '{}': names cannot start with '-'", name);
+    }
+    if name.chars().any(char::is_whitespace) {
+        anyhow::bail!("Invalid shortcut name '{}': names cannot contain whitespace", name);
+    }
+    if name.contains(['/', '\\']) {
+        anyhow::bail!("Invalid shortcut name '{}': names cannot contain path separators", name);
+    }
+    if RESERVED_NAMES.contains(&name) {
+        anyhow::bail!("Invalid shortcut name '{}': this name is reserved", name);
+    }
+    Ok(())
+}
+
+/// Check whether a shortcut path is a URI like `ssh://host/path` rather than a local path
+///
+/// Schemes need at least two characters so Windows drive letters never match.
+pub fn is_remote_uri(path: &str) -> bool {
+    let Some((scheme, _)) = path.split_once("://") else {
+        return false;
+    };
+    scheme.len() > 1
+        && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
+
+/// Derive a shortcut name from the last component of a directory path
+pub fn shortcut_name_for(path: &Path) -> Option<String> {
+    let leaf = path.file_name()?.to_string_lossy().to_lowercase();
+    let name: String = leaf
+        .trim_start_matches('-')
+        .chars()
+        .map(|c| if c.is_whitespace() { '-' } else { c })
+        .collect();
+    validate_shortcut_name(&name).ok()?;
+    Some(name)
+}
+
+/// `base`, or the first of `base-2`, `base-3`, ... that is not taken
+pub fn numbered_name(base: &str, taken: impl Fn(&str) -> bool) -> String {
+    (1..)
+        .map(|n| if n == 1 { base.to_string() } else { format!("{}-{}", base, n) })
+        .find(|name| !taken(name))
+        .expect("some numbered name is free")
+}
+
+/// A directory shortcut
+///
+/// Serialized as a bare path string when no metadata is set, so configs
+/// written by older versions (`name = "/path"`) keep loading unchanged.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(from = "ShortcutRepr", into = "ShortcutRepr")]
+pub struct Shortcut {
+    /// Target directory; empty for aliases
+    pub path: String,
+
+    /// Name of the shortcut this one is an alias of
+    pub alias_of: Option<String>,
+
+    /// Short human-readable description
+    pub description: Option<String>,
+
+    /// Free-form tags used for filtering
+    pub tags: Vec<String>,
+
+    /// Heading the shortcut is listed under; `DEFAULT_GROUP` when unset
+    pub group: Option<String>,
+
+    /// When the shortcut was added
+    pub created_at: Option<DateTime<Utc>>,
+
+    /// When the shortcut was last jumped to
+    pub last_used: Option<DateTime<Utc>>,
+
+    /// Paths used instead of `path` on specific platforms, keyed by OS name
+    pub platform_overrides: HashMap<String, String>,
+}
+
+impl Shortcut {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            alias_of: None,
+            description: None,
+            tags: Vec::new(),
+            group: None,
+            created_at: None,
+            last_used: None,
+            platform_overrides: HashMap::new(),
+        }
+    }
+
+    /// Create a shortcut stamped with the current time
+    pub fn created_now(path: impl Into<String>) -> Self {
+        Self {
+            created_at: Some(Utc::now()),
+            ..Self::new(path)
+        }
+    }
+
+    /// Create a shortcut that refers to another shortcut by name
+    pub fn alias_of(target: &str) -> Self {
+        Self {
+            alias_of: Some(target.to_string()),
+            ..Self::new(String::new())
+        }
+    }
+
+    /// Name of the shortcut this one is an alias of, if any
+    pub fn alias_target(&self) -> Option<&str> {
+        self.alias_of.as_deref()
+    }
+
+    /// Path to use on the given platform (`std::env::consts::OS` naming)
+    pub fn path_for(&self, os: &str) -> &String {
+        self.platform_overrides.get(os).unwrap_or(&self.path)
+    }
+
+    /// Path to use on the platform navr was built for
+    pub fn current_path(&self) -> &String {
+        self.path_for(std::env::consts::OS)
+    }
+
+    /// Heading the shortcut is listed under
+    pub fn group(&self) -> &str {
+        self.group.as_deref().unwrap_or(DEFAULT_GROUP)
+    }
+
+    /// Check whether the shortcut carries the given tag (case-insensitive)
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    fn has_metadata(&self) -> bool {
+        self.alias_of.is_some()
+            || self.description.is_some()
+            || !self.tags.is_empty()
+            || self.group.is_some()
+            || self.created_at.is_some()
+            || self.last_used.is_some()
+            || !self.platform_overrides.is_empty()
+    }
+}
+
+impl From<String> for Shortcut {
+    fn from(path: String) -> Self {
+        Self::new(path)
+    }
+}
+
+/// On-disk representation: either a bare path or a full table
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+enum ShortcutRepr {
+    Path(String),
+    Full {
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        path: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        alias_of: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        tags: Vec<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        group: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        created_at: Option<DateTime<Utc>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        last_used: Option<DateTime<Utc>>,
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        #[schemars(extend("propertyNames" = { "enum": PLATFORMS }))]
+        platform_overrides: HashMap<String, String>,
+    },
+}
+
+impl From<ShortcutRepr> for Shortcut {
+    fn from(repr: ShortcutRepr) -> Self {
+        match repr {
+            ShortcutRepr::Path(path) => Self::new(path),
+            ShortcutRepr::Full {
+                path,
+                alias_of,
+                description,
+                tags,
+                group,
+                created_at,
+                last_used,
+                platform_overrides,
+            } => Self {
+                path,
+                alias_of,
+                description,
+                tags,
+                group,
+                created_at,
+                last_used,
+                platform_overrides,
+            },
+        }
+    }
+}
+
+impl From<Shortcut> for ShortcutRepr {
+    fn from(shortcut: Shortcut) -> Self {
+        if shortcut.has_metadata() {
+            ShortcutRepr::Full {
+                path: shortcut.path,
+                alias_of: shortcut.alias_of,
+                description: shortcut.description,
+                tags: shortcut.tags,
+                group: shortcut.group,
+                created_at: shortcut.created_at,
+                last_used: shortcut.last_used,
+                platform_overrides: shortcut.platform_overrides,
+            }
+        } else {
+            ShortcutRepr::Path(shortcut.path)
+        }
+    }
+}
@@ -12,16 +12,28 @@ mod tests {
         assert!(config.default_file_manager.is_none());
     }
 
+    #[test]
+    fn test_default_matches_missing_sections() {
+        let parsed: AppConfig = toml::from_str("").unwrap();
+        let config = AppConfig::default();
+
+        assert_eq!(config.shell.max_history, parsed.shell.max_history);
+        assert_eq!(config.shell.max_stack, parsed.shell.max_stack);
+        assert_eq!(config.shell.completion_style, parsed.shell.completion_style);
+        assert_eq!(config.behavior.confirm_overwrite, parsed.behavior.confirm_overwrite);
+        assert_eq!(config.behavior.default_to_home, parsed.behavior.default_to_home);
+    }
+
     #[test]
     fn test_shortcut_operations() {
         let mut config = AppConfig::default();
         
         // Add shortcut
-        config.set_shortcut("test", "/tmp/test").unwrap();
+        config.set_shortcut("test", "/tmp/test", false).unwrap();
         assert!(config.shortcuts.contains_key("test"));
         
         // Get shortcut
-        let path = config.get_shortcut("test");
+        let path = config.get_shortcut("test").unwrap();
         assert!(path.is_some());
         assert!(path.unwrap().contains("test"));
         
@@ -33,31 +45,101 @@ mod tests {
         assert!(!config.remove_shortcut("nonexistent").unwrap());
     }
 
+    #[test]
+    fn test_set_shortcut_refuses_overwrite_without_force() {
+        let dir = std::env::temp_dir().join(format!("navr-overwrite-{}", std::process::id()));
+        let mut config = AppConfig::default();
+        config.set_file_path(dir.join("config.toml"));
+        assert!(config.behavior.confirm_overwrite);
+
+        assert_eq!(config.set_shortcut("work", "/srv/work", false).unwrap(), SetOutcome::Inserted);
+        assert_eq!(config.set_shortcut("work", "/srv/other", false).unwrap(), SetOutcome::Refused);
+        assert_eq!(config.shortcuts["work"].path, "/srv/work");
+
+        assert_eq!(config.set_shortcut("work", "/srv/other", true).unwrap(), SetOutcome::Overwritten);
+        assert_eq!(config.shortcuts["work"].path, "/srv/other");
+
+        // Without confirm_overwrite, replacing needs no force
+        config.behavior.confirm_overwrite = false;
+        assert_eq!(config.set_shortcut("work", "/srv/work", false).unwrap(), SetOutcome::Overwritten);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_set_shortcut_replaces_case_variant() {
+        let dir = std::env::temp_dir().join(format!("navr-overwrite-case-{}", std::process::id()));
+        let mut config = AppConfig::default();
+        config.set_file_path(dir.join("config.toml"));
+        assert!(!config.behavior.case_sensitive);
+
+        config.set_shortcut("proj", "/srv/proj", false).unwrap();
+        assert_eq!(config.set_shortcut("PROJ", "/srv/other", false).unwrap(), SetOutcome::Refused);
+        assert_eq!(config.set_shortcut("PROJ", "/srv/other", true).unwrap(), SetOutcome::Overwritten);
+        assert_eq!(config.shortcuts.len(), 1);
+        assert_eq!(config.shortcuts["PROJ"].path, "/srv/other");
+
+        // With case-sensitive names the variants are separate shortcuts
+        config.behavior.case_sensitive = true;
+        assert_eq!(config.set_shortcut("proj", "/srv/proj", false).unwrap(), SetOutcome::Inserted);
+        assert_eq!(config.shortcuts.len(), 2);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_shortcut_name_validation() {
+        assert!(validate_shortcut_name("proj").is_ok());
+        assert!(validate_shortcut_name("my-proj_2").is_ok());
+
+        assert!(validate_shortcut_name("").is_err());
+        assert!(validate_shortcut_name("a/b").is_err());
+        assert!(validate_shortcut_name("a\\b").is_err());
+        assert!(validate_shortcut_name("--list").is_err());
+        assert!(validate_shortcut_name("my proj").is_err());
+        assert!(validate_shortcut_name("..").is_err());
+
+        let mut config = AppConfig::default();
+        assert!(config.set_shortcut("a/b", "/tmp", false).is_err());
+        assert!(config.shortcuts.is_empty());
+    }
+
     #[test]
     fn test_case_insensitive_matching() {
         let mut config = AppConfig::default();
         config.behavior.case_sensitive = false;
-        config.set_shortcut("Test", "/tmp/test").unwrap();
+        config.set_shortcut("Test", "/tmp/test", false).unwrap();
         
-        assert!(config.get_shortcut("test").is_some());
-        assert!(config.get_shortcut("TEST").is_some());
-        assert!(config.get_shortcut("Test").is_some());
+        assert!(config.get_shortcut("test").unwrap().is_some());
+        assert!(config.get_shortcut("TEST").unwrap().is_some());
+        assert!(config.get_shortcut("Test").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_case_insensitive_remove() {
+        let mut config = AppConfig::default();
+        config.set_dry_run(true);
+        config.behavior.case_sensitive = false;
+        config.set_shortcut("Test", "/tmp/test", false).unwrap();
+
+        assert!(config.remove_shortcut("test").unwrap());
+        assert!(!config.shortcuts.contains_key("Test"));
     }
 
     #[test]
     fn test_case_sensitive_matching() {
         let mut config = AppConfig::default();
         config.behavior.case_sensitive = true;
-        config.set_shortcut("Test", "/tmp/test").unwrap();
+        config.set_shortcut("Test", "/tmp/test", false).unwrap();
         
-        assert!(config.get_shortcut("Test").is_some());
-        assert!(config.get_shortcut("test").is_none());
+        assert!(config.get_shortcut("Test").unwrap().is_some());
+        assert!(config.get_shortcut("test").unwrap().is_none());
     }
 
     #[test]
     fn test_config_serialization() {
         let mut config = AppConfig::default();
-        config.set_shortcut("home", "/home/user").unwrap();
+        config.set_shortcut("home", "/home/user", false).unwrap();
         config.default_file_manager = Some("dolphin".to_string());
         
         // Serialize to TOML
@@ -71,10 +153,440 @@ mod tests {
         assert_eq!(parsed.default_file_manager, Some("dolphin".to_string()));
     }
 
+    #[test]
+    fn test_shortcut_metadata_serialization() {
+        let toml_str = r#"
+            [shortcuts]
+            home = "/home/user"
+
+            [shortcuts.proj]
+            path = "/home/user/projects"
+            description = "Work projects"
+            tags = ["dev", "work"]
+        "#;
+
+        let config: AppConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.shortcuts["home"], Shortcut::new("/home/user"));
+
+        let proj = &config.shortcuts["proj"];
+        assert_eq!(proj.path, "/home/user/projects");
+        assert_eq!(proj.description.as_deref(), Some("Work projects"));
+        assert!(proj.has_tag("DEV"));
+        assert!(!proj.has_tag("personal"));
+
+        // Plain shortcuts stay bare strings; annotated ones round-trip
+        let serialized = toml::to_string(&config).unwrap();
+        assert!(serialized.contains("home = \"/home/user\""));
+        let parsed: AppConfig = toml::from_str(&serialized).unwrap();
+        assert_eq!(parsed.shortcuts["proj"], *proj);
+    }
+
+    #[test]
+    fn test_shortcut_timestamps_round_trip() {
+        let mut config = AppConfig::default();
+        config.shortcuts.clear();
+        config.shortcuts.insert("old".to_string(), Shortcut::new("/old"));
+        config.shortcuts.insert("new".to_string(), Shortcut::created_now("/new"));
+
+        let toml_str = toml::to_string(&config).unwrap();
+        assert!(toml_str.contains("old = \"/old\""));
+
+        let loaded: AppConfig = toml::from_str(&toml_str).unwrap();
+        assert!(loaded.shortcuts["old"].created_at.is_none());
+        assert_eq!(loaded.shortcuts["new"].created_at, config.shortcuts["new"].created_at);
+    }
+
+    #[test]
+    fn test_platform_overrides() {
+        let toml_str = r#"
+            [shortcuts.projects]
+            path = "/home/user/dev"
+
+            [shortcuts.projects.platform_overrides]
+            windows = 'D:\dev'
+        "#;
+        let config: AppConfig = toml::from_str(toml_str).unwrap();
+        let projects = &config.shortcuts["projects"];
+
+        assert_eq!(projects.path_for("windows"), "D:\\dev");
+        assert_eq!(projects.path_for("linux"), "/home/user/dev");
+
+        let expected = if cfg!(target_os = "windows") { "D:\\dev" } else { "/home/user/dev" };
+        assert_eq!(config.get_shortcut("projects").unwrap().map(String::as_str), Some(expected));
+
+        // Overrides survive a round trip
+        let mut reloaded: AppConfig = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(reloaded.shortcuts["projects"], *projects);
+
+        // The override for the running platform wins
+        reloaded
+            .shortcuts
+            .get_mut("projects")
+            .unwrap()
+            .platform_overrides
+            .insert(std::env::consts::OS.to_string(), "/srv/dev".to_string());
+        assert_eq!(reloaded.get_shortcut("projects").unwrap().map(String::as_str), Some("/srv/dev"));
+    }
+
+    #[test]
+    fn test_numeric_settings_are_range_checked() {
+        let dir = std::env::temp_dir().join(format!("navr-ranges-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        let mut config = AppConfig::default();
+        config.set_file_path(&path);
+        for bad in ["0", "1000001", "99999999999999999999999", "-5", "many"] {
+            let err = config.set_value("shell.max_history", bad).unwrap_err();
+            assert!(err.to_string().contains("from 1 to 1000000"), "{}", err);
+        }
+        assert_eq!(config.shell.max_history, 1000);
+        config.set_value("shell.max_history", "500").unwrap();
+        assert_eq!(config.shell.max_history, 500);
+        config.set_value("behavior.max_suggestions", "0").unwrap();
+
+        // Hand-edited files are brought back into range on load
+        std::fs::write(&path, "[shell]\nmax_history = 0\nmax_stack = 50000000\n").unwrap();
+        let loaded = AppConfig::load_from_path(&path).unwrap();
+        assert_eq!(loaded.shell.max_history, 1);
+        assert_eq!(loaded.shell.max_stack, 10_000);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_migrate_v0_1_0_config() {
+        let dir = std::env::temp_dir().join(format!("navr-migrate-{}", std::process::id()));
+        let path = dir.join("config.toml");
+        std::fs::create_dir_all(&dir).unwrap();
+        let home = defaults::default_shortcuts()["home"].path.clone();
+        let old = format!(
+            "version = \"0.1.0\"\n\n[shortcuts]\nhome = {:?}\nh = \"@home\"\nproj = \"/srv/proj\"\n",
+            home
+        );
+        std::fs::write(&path, old).unwrap();
+
+        let config = AppConfig::load_from_path(&path).unwrap();
+        assert_eq!(config.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(config.shortcuts["home"].group(), defaults::SYSTEM_GROUP);
+        assert_eq!(config.shortcuts["h"].group(), defaults::SYSTEM_GROUP);
+        assert_eq!(config.shortcuts["h"].alias_target(), Some("home"));
+        assert_eq!(config.shortcuts["home"].path, home);
+        assert_eq!(config.shortcuts["proj"].group, None);
+
+        // The new version is written with the next save
+        config.save().unwrap();
+        let saved = std::fs::read_to_string(&path).unwrap();
+        assert!(saved.contains(&format!("version = \"{}\"", env!("CARGO_PKG_VERSION"))));
+
+        // Configs from a newer navr are not touched
+        let mut newer = AppConfig { version: "99.0.0".to_string(), ..AppConfig::default() };
+        newer.shortcuts.insert("home".to_string(), Shortcut::new(home));
+        assert!(!migrate::migrate(&mut newer));
+        assert_eq!(newer.version, "99.0.0");
+        assert_eq!(newer.shortcuts["home"].group, None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_migrate_unversioned_config() {
+        let dir = std::env::temp_dir().join(format!("navr-migrate-unversioned-{}", std::process::id()));
+        let path = dir.join("config.toml");
+        std::fs::create_dir_all(&dir).unwrap();
+        let home = defaults::default_shortcuts()["home"].path.clone();
+        std::fs::write(&path, format!("[shortcuts]\nhome = {:?}\n", home)).unwrap();
+
+        let config = AppConfig::load_from_path(&path).unwrap();
+        assert_eq!(config.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(config.shortcuts["home"].group(), defaults::SYSTEM_GROUP);
+
+        // Configs written by 0.1.13 predate the groups as well
+        std::fs::write(&path, format!("version = \"0.1.13\"\n[shortcuts]\nhome = {:?}\n", home)).unwrap();
+        let config = AppConfig::load_from_path(&path).unwrap();
+        assert_eq!(config.shortcuts["home"].group(), defaults::SYSTEM_GROUP);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_save_to_loaded_path() {
+        let dir = std::env::temp_dir().join(format!("navr-save-{}", std::process::id()));
+        let path = dir.join("config.toml");
+
+        let mut config = AppConfig::default();
+        config.set_file_path(&path);
+        config.set_shortcut("tmp", "/tmp", false).unwrap();
+
+        let loaded = AppConfig::load_from_path(&path).unwrap();
+        assert!(loaded.shortcuts.contains_key("tmp"));
+        assert_eq!(loaded.file_path().unwrap(), path);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_dry_run_save_writes_nothing() {
+        let dir = std::env::temp_dir().join(format!("navr-dry-run-{}", std::process::id()));
+        let path = dir.join("config.toml");
+
+        let mut config = AppConfig::default();
+        config.set_file_path(&path);
+        config.set_dry_run(true);
+        config.set_shortcut("tmp", "/tmp", false).unwrap();
+
+        // The change is kept in memory only, and survives replacing the settings
+        assert!(config.shortcuts.contains_key("tmp"));
+        assert!(!dir.exists());
+        config.replace_settings(AppConfig::default());
+        assert!(config.is_dry_run());
+        assert_eq!(config.file_path().unwrap(), path);
+    }
+
+    #[test]
+    fn test_marking_use_keeps_backup_and_other_changes() {
+        let dir = std::env::temp_dir().join(format!("navr-mark-used-{}", std::process::id()));
+        let path = dir.join("config.toml");
+
+        let mut config = AppConfig::default();
+        config.set_file_path(&path);
+        config.set_shortcut("work", "/srv/work", false).unwrap();
+        let backup = std::fs::read_to_string(backup_path(&path)).unwrap_or_default();
+
+        // Another shell adds a shortcut after this one loaded the config
+        let mut other = AppConfig::load_from_path(&path).unwrap();
+        other.set_shortcut("docs", "/srv/docs", false).unwrap();
+        let backup_after_other = std::fs::read_to_string(backup_path(&path)).unwrap();
+        assert_ne!(backup, backup_after_other);
+
+        config.mark_shortcut_used("WORK").unwrap();
+        assert!(config.shortcuts["work"].last_used.is_some());
+
+        let loaded = AppConfig::load_from_path(&path).unwrap();
+        assert_eq!(loaded.shortcuts["work"].last_used, config.shortcuts["work"].last_used);
+        assert!(loaded.shortcuts.contains_key("docs"));
+        assert_eq!(std::fs::read_to_string(backup_path(&path)).unwrap(), backup_after_other);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_backup_recovers_from_corrupt_config() {
+        let dir = std::env::temp_dir().join(format!("navr-backup-{}", std::process::id()));
+        let path = dir.join("config.toml");
+
+        let mut config = AppConfig::default();
+        config.set_file_path(&path);
+        config.set_shortcut("first", "/tmp", false).unwrap();
+        config.set_shortcut("second", "/tmp", false).unwrap();
+
+        // Simulate a crash that left the config truncated
+        std::fs::write(&path, "[shortcuts\nfirst = ").unwrap();
+        let err = AppConfig::load_from_path(&path).unwrap_err();
+        assert!(format!("{:#}", err).contains("config.toml.bak"));
+        assert!(matches!(err.downcast_ref(), Some(NavrError::ConfigParse { .. })));
+
+        let recovered = AppConfig::load_from_path(backup_path(&path)).unwrap();
+        assert!(recovered.shortcuts.contains_key("first"));
+        assert!(!recovered.shortcuts.contains_key("second"));
+
+        // No temporary files are left behind
+        let leftovers = std::fs::read_dir(&dir)
+            .unwrap()
+            .filter(|e| e.as_ref().unwrap().file_name().to_string_lossy().ends_with(".tmp"))
+            .count();
+        assert_eq!(leftovers, 0);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_shortcut_aliases() {
+        let mut config = AppConfig::default();
+        config.shortcuts.insert("home".to_string(), Shortcut::new("/home/user"));
+        config.shortcuts.insert("h".to_string(), Shortcut::alias_of("home"));
+        config.shortcuts.insert("hh".to_string(), Shortcut::alias_of("h"));
+
+        assert_eq!(config.get_shortcut("hh").unwrap().map(String::as_str), Some("/home/user"));
+
+        config.shortcuts.insert("home".to_string(), Shortcut::alias_of("hh"));
+        let err = config.get_shortcut("h").unwrap_err();
+        assert!(err.to_string().contains("cycle"));
+
+        config.shortcuts.insert("h".to_string(), Shortcut::alias_of("missing"));
+        assert!(config.get_shortcut("h").is_err());
+        assert!(config.get_shortcut("unknown").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_alias_is_stored_apart_from_path() {
+        let mut config = AppConfig::default();
+        config.shortcuts.insert("home".to_string(), Shortcut::new("/home/user"));
+        config.shortcuts.insert("h".to_string(), Shortcut::alias_of("home"));
+        config.shortcuts.insert("at".to_string(), Shortcut::new("@home"));
+
+        let toml = toml::to_string(&config).unwrap();
+        assert!(toml.contains(r#"alias_of = "home""#), "{}", toml);
+        let loaded: AppConfig = toml::from_str(&toml).unwrap();
+        assert_eq!(loaded.get_shortcut("h").unwrap().map(String::as_str), Some("/home/user"));
+        // A directory that happens to start with '@' is just a path
+        assert_eq!(loaded.get_shortcut("at").unwrap().map(String::as_str), Some("@home"));
+    }
+
+    #[test]
+    fn test_set_alias_rejects_cycles() {
+        let dir = std::env::temp_dir().join(format!("navr-alias-{}", std::process::id()));
+        let mut config = AppConfig::default();
+        config.set_file_path(dir.join("config.toml"));
+        config.shortcuts.insert("home".to_string(), Shortcut::new("/home/user"));
+
+        config.set_alias("h", "home").unwrap();
+        assert_eq!(config.get_shortcut("h").unwrap().map(String::as_str), Some("/home/user"));
+
+        assert!(config.set_alias("home", "h").is_err());
+        assert_eq!(config.shortcuts["home"].path, "/home/user");
+        assert!(config.set_alias("x", "missing").is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_set_shortcut_honors_follow_symlinks() {
+        let dir = std::env::temp_dir().join(format!("navr-set-symlink-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("real")).unwrap();
+        let dir = std::fs::canonicalize(&dir).unwrap();
+        let link = dir.join("link");
+        let _ = std::fs::remove_file(&link);
+        std::os::unix::fs::symlink(dir.join("real"), &link).unwrap();
+
+        let mut config = AppConfig::default();
+        config.set_file_path(dir.join("config.toml"));
+        config.set_shortcut("followed", &link.to_string_lossy(), false).unwrap();
+        assert_eq!(config.shortcuts["followed"].path, dir.join("real").to_string_lossy());
+
+        config.behavior.follow_symlinks = false;
+        config.set_shortcut("kept", &link.to_string_lossy(), false).unwrap();
+        assert_eq!(config.shortcuts["kept"].path, link.to_string_lossy());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_session_shortcut_takes_precedence() {
+        let mut config = AppConfig::default();
+        config.set_dry_run(true);
+        config.set_shortcut("proj", "/home/user/projects", false).unwrap();
+        config.set_session_shortcuts(HashMap::from([
+            ("proj".to_string(), "/tmp/scratch".to_string()),
+            ("build".to_string(), "/tmp/build".to_string()),
+        ]));
+
+        assert_eq!(config.get_shortcut("proj").unwrap().map(String::as_str), Some("/tmp/scratch"));
+        assert_eq!(config.get_shortcut("BUILD").unwrap().map(String::as_str), Some("/tmp/build"));
+        // The persistent shortcut is untouched and comes back without the session
+        config.mark_shortcut_used("proj").unwrap();
+        assert_eq!(config.shortcuts["proj"].path, "/home/user/projects");
+        assert!(config.shortcuts["proj"].last_used.is_none());
+        config.set_session_shortcuts(HashMap::new());
+        assert_eq!(
+            config.get_shortcut("proj").unwrap().map(String::as_str),
+            Some("/home/user/projects")
+        );
+    }
+
+    #[test]
+    fn test_shortcut_sigil() {
+        let mut config = AppConfig::default();
+        config.set_dry_run(true);
+        assert_eq!(config.strip_shortcut_sigil("@proj"), None);
+
+        config.set_value("behavior.shortcut_sigil", "@").unwrap();
+        assert_eq!(config.strip_shortcut_sigil("@proj"), Some("proj"));
+        assert_eq!(config.strip_shortcut_sigil("proj"), None);
+        assert!(config.set_value("behavior.shortcut_sigil", "a/").is_err());
+
+        assert!(config.set_value("behavior.resolution_order", "paths").is_err());
+        config.set_value("behavior.resolution_order", "PATH_FIRST").unwrap();
+        assert_eq!(config.behavior.resolution_order(), ResolutionOrder::PathFirst);
+    }
+
+    #[test]
+    fn test_rename_shortcut_keeps_metadata() {
+        let dir = std::env::temp_dir().join(format!("navr-rename-{}", std::process::id()));
+        let mut config = AppConfig::default();
+        config.set_file_path(dir.join("config.toml"));
+        let mut work = Shortcut::created_now("/srv/work");
+        work.description = Some("Day job".to_string());
+        work.tags = vec!["job".to_string()];
+        work.last_used = Some(chrono::Utc::now());
+        config.shortcuts.insert("Work".to_string(), work.clone());
+        config.shortcuts.insert("w".to_string(), Shortcut::alias_of("Work"));
+
+        config.rename_shortcut("work", "office").unwrap();
+        assert!(!config.shortcuts.contains_key("Work"));
+        let office = &config.shortcuts["office"];
+        assert_eq!(office.path, work.path);
+        assert_eq!(office.description, work.description);
+        assert_eq!(office.tags, work.tags);
+        assert_eq!(office.created_at, work.created_at);
+        assert_eq!(office.last_used, work.last_used);
+        assert_eq!(config.get_shortcut("w").unwrap().map(String::as_str), Some("/srv/work"));
+
+        let err = config.rename_shortcut("missing", "other").unwrap_err();
+        assert!(matches!(err.downcast_ref(), Some(NavrError::ShortcutNotFound(name)) if name == "missing"));
+        assert!(config.rename_shortcut("office", "bad/name").is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn merge_fixture() -> (AppConfig, AppConfig) {
+        let mut existing = AppConfig::default();
+        existing.shortcuts.insert("work".to_string(), Shortcut::new("/old/work"));
+        existing.file_managers.insert("fm".to_string(), "old-fm".to_string());
+
+        let mut incoming = AppConfig::default();
+        incoming.shortcuts.insert("work".to_string(), Shortcut::new("/new/work"));
+        incoming.shortcuts.insert("extra".to_string(), Shortcut::new("/new/extra"));
+        incoming.file_managers.insert("fm".to_string(), "new-fm".to_string());
+
+        (existing, incoming)
+    }
+
+    #[test]
+    fn test_merge_keeps_existing() {
+        let (mut config, incoming) = merge_fixture();
+        assert!(config.merge(incoming, MergeStrategy::KeepExisting, false).unwrap().is_empty());
+
+        assert_eq!(config.shortcuts["work"].path, "/old/work");
+        assert_eq!(config.shortcuts["extra"].path, "/new/extra");
+        assert_eq!(config.file_managers["fm"], "old-fm");
+    }
+
+    #[test]
+    fn test_merge_prefers_incoming() {
+        let (mut config, incoming) = merge_fixture();
+        config.merge(incoming, MergeStrategy::PreferIncoming, true).unwrap();
+
+        assert_eq!(config.shortcuts["work"].path, "/new/work");
+        assert_eq!(config.shortcuts["extra"].path, "/new/extra");
+        assert_eq!(config.file_managers["fm"], "new-fm");
+    }
+
+    #[test]
+    fn test_merge_refuses_overwrite_without_force() {
+        let (mut config, incoming) = merge_fixture();
+        assert!(config.behavior.confirm_overwrite);
+        let refused = config.merge(incoming, MergeStrategy::PreferIncoming, false).unwrap();
+
+        assert_eq!(refused, ["work"]);
+        assert_eq!(config.shortcuts["work"].path, "/old/work");
+        assert_eq!(config.shortcuts["extra"].path, "/new/extra");
+    }
+
     #[test]
     fn test_json_serialization() {
         let mut config = AppConfig::default();
-        config.set_shortcut("work", "/home/user/work").unwrap();
+        config.set_shortcut("work", "/home/user/work", false).unwrap();
         
         let json = config.to_json().unwrap();
         assert!(json.contains("work"));
@@ -86,13 +598,13 @@ mod tests {
     #[test]
     fn test_config_merge() {
         let mut config1 = AppConfig::default();
-        config1.set_shortcut("a", "/path/a").unwrap();
+        config1.set_shortcut("a", "/path/a", false).unwrap();
         
         let mut config2 = AppConfig::default();
-        config2.set_shortcut("b", "/path/b").unwrap();
+        config2.set_shortcut("b", "/path/b", false).unwrap();
         config2.default_file_manager = Some("nautilus".to_string());
         
-        config1.merge(config2);
+        config1.merge(config2, MergeStrategy::KeepExisting, false).unwrap();
         
         assert!(config1.shortcuts.contains_key("a"));
         assert!(config1.shortcuts.contains_key("b"));
@@ -118,6 +630,71 @@ mod tests {
         assert!(config.get_value("invalid.key").is_err());
     }
 
+    #[test]
+    fn test_config_keys_are_gettable() {
+        let config = AppConfig::default();
+
+        for key in CONFIG_KEYS {
+            assert!(config.get_value(key.name).is_ok(), "key {} not gettable", key.name);
+        }
+
+        assert_eq!(
+            find_config_key("behavior.case_sensitive").map(|k| k.kind),
+            Some(ConfigValueKind::Bool)
+        );
+        assert!(find_config_key("invalid.key").is_none());
+    }
+
+    #[test]
+    fn test_all_values() {
+        let mut config = AppConfig::default();
+        config.behavior.case_sensitive = true;
+        config.file_managers.insert("fm".to_string(), "thunar".to_string());
+
+        let values = config.all_values().unwrap();
+        assert_eq!(values.len(), CONFIG_KEYS.len() + 1);
+        assert!(values.contains(&("behavior.case_sensitive".to_string(), "true".to_string())));
+        assert_eq!(values.last(), Some(&("file_managers.fm".to_string(), "thunar".to_string())));
+    }
+
+    #[test]
+    fn test_set_platform_key() {
+        let dir = std::env::temp_dir().join(format!("navr-platform-key-{}", std::process::id()));
+        let mut config = AppConfig::default();
+        config.set_file_path(dir.join("config.toml"));
+
+        config.set_value("platform.linux.file_manager", "thunar").unwrap();
+        assert_eq!(config.platform.linux.file_manager.as_deref(), Some("thunar"));
+        assert_eq!(config.get_value("platform.linux.file_manager").unwrap(), "thunar");
+
+        config.set_value("platform.linux.file_manager", "").unwrap();
+        assert!(config.platform.linux.file_manager.is_none());
+
+        assert!(config.set_value("platform.linux.unknown", "x").is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_set_file_manager_alias() {
+        let dir = std::env::temp_dir().join(format!("navr-fm-alias-{}", std::process::id()));
+        let mut config = AppConfig::default();
+        config.set_file_path(dir.join("config.toml"));
+
+        config.set_value("file_managers.yazi", "yazi").unwrap();
+        assert_eq!(config.file_managers.get("yazi").map(String::as_str), Some("yazi"));
+        assert_eq!(config.get_value("file_managers.yazi").unwrap(), "yazi");
+
+        let loaded = AppConfig::load_from_path(dir.join("config.toml")).unwrap();
+        assert!(loaded.file_managers.contains_key("yazi"));
+
+        assert!(config.get_value("file_managers.missing").is_err());
+        assert!(config.set_value("file_managers.", "x").is_err());
+        assert!(config.set_value("file_managers.a.b", "x").is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_file_manager_detection() {
         let config = AppConfig::default();
@@ -133,4 +710,126 @@ mod tests {
         ];
         assert!(known.contains(&fm.as_str()));
     }
+
+    #[test]
+    fn test_missing_file_manager_falls_back() {
+        let mut config = AppConfig::default();
+        assert!(matches!(
+            config.resolve_file_manager(),
+            Ok(FileManagerSource::Detected(_))
+        ));
+
+        config.default_file_manager = Some("navr-missing-file-manager --flag".to_string());
+        let common = defaults::common_file_managers(std::env::consts::OS)
+            .iter()
+            .find(|name| which::which(name).is_ok());
+        match (config.resolve_file_manager(), common) {
+            (Ok(FileManagerSource::Fallback { missing, name }), Some(common)) => {
+                assert_eq!(missing, "navr-missing-file-manager --flag");
+                assert_eq!(name, *common);
+                assert_eq!(config.get_file_manager(), *common);
+            }
+            (Err(_), None) => {
+                assert_eq!(config.get_file_manager(), defaults::detect_best_file_manager())
+            }
+            (other, _) => panic!("unexpected resolution {:?}", other),
+        }
+
+        let exe = std::env::current_exe().unwrap().to_string_lossy().to_string();
+        config.default_file_manager = Some(exe.clone());
+        assert_eq!(
+            config.resolve_file_manager().unwrap(),
+            FileManagerSource::Configured(exe)
+        );
+    }
+
+    #[test]
+    fn test_ignore_patterns() {
+        let mut shell = ShellConfig {
+            ignore_patterns: vec!["/mnt/backup-*".to_string(), "node_modules".to_string()],
+            ..Default::default()
+        };
+
+        assert!(shell.ignores(Path::new("/mnt/backup-2024")));
+        assert!(shell.ignores(Path::new("/mnt/backup-2024/photos")));
+        assert!(shell.ignores(Path::new("/home/user/app/node_modules/pkg")));
+        assert!(!shell.ignores(Path::new("/mnt/media")));
+        assert!(!shell.ignores(Path::new("/home/user/app")));
+
+        shell.ignore_patterns = vec![
+            "/srv/{cache,tmp}".to_string(),
+            "/mnt/disk[0-9]/**/.git".to_string(),
+        ];
+        assert!(shell.ignores(Path::new("/srv/tmp/x")));
+        assert!(!shell.ignores(Path::new("/srv/data")));
+        assert!(shell.ignores(Path::new("/mnt/disk1/src/navr/.git/objects")));
+        assert!(shell.ignores(Path::new("/mnt/disk1/.git")));
+        assert!(!shell.ignores(Path::new("/mnt/diskA/.git")));
+        assert!(ShellConfig::ignore_glob("/mnt/backup-[0-9").is_err());
+
+        if let Some(home) = dirs::home_dir() {
+            shell.ignore_patterns = vec!["~/secrets".to_string()];
+            assert!(shell.ignores(&home.join("secrets").join("keys")));
+            assert!(!shell.ignores(&home.join("projects")));
+        }
+    }
+
+    #[test]
+    fn test_save_preserves_comments() {
+        let path = std::env::temp_dir().join(format!("navr-comments-{}.toml", std::process::id()));
+        std::fs::write(
+            &path,
+            "# Work projects\n[shortcuts]\ndocs = \"/srv/docs\" # shared\nwiki = \"/srv/wiki\"\n\n[shell]\nmax_history = 1000 # plenty\n",
+        )
+        .unwrap();
+
+        let mut config = AppConfig::load_from_path(&path).unwrap();
+        config.set_value("shell.max_stack", "20").unwrap();
+        config.set_shortcut("code", "/srv/code", false).unwrap();
+        config.remove_shortcut("wiki").unwrap();
+
+        let saved = std::fs::read_to_string(&path).unwrap();
+        assert!(saved.contains("# Work projects\n[shortcuts]\ndocs = \"/srv/docs\" # shared\n"));
+        assert!(saved.contains("max_history = 1000 # plenty"));
+        assert!(saved.contains("max_stack = 20"));
+
+        let reloaded = AppConfig::load_from_path(&path).unwrap();
+        assert_eq!(reloaded.shell.max_stack, 20);
+        assert!(reloaded.shortcuts.contains_key("code"));
+        assert!(!reloaded.shortcuts.contains_key("wiki"));
+
+        std::fs::remove_file(&path).unwrap();
+        let _ = std::fs::remove_file(backup_path(&path));
+    }
+
+    #[test]
+    fn test_create_missing_mode_is_octal_string() {
+        let config: AppConfig = toml::from_str("[behavior]\ncreate_missing_mode = \"0700\"\n").unwrap();
+        assert_eq!(config.behavior.create_missing_mode, Some(0o700));
+
+        let saved = toml::to_string(&config).unwrap();
+        assert!(saved.contains("create_missing_mode = \"0700\""));
+        assert_eq!(config.get_value("behavior.create_missing_mode").unwrap(), "0700");
+
+        assert!(toml::from_str::<AppConfig>("[behavior]\ncreate_missing_mode = \"0799\"\n").is_err());
+    }
+
+    #[test]
+    fn test_shortcut_name_for() {
+        assert_eq!(shortcut_name_for(Path::new("/home/user/My Project")).as_deref(), Some("my-project"));
+        assert_eq!(shortcut_name_for(Path::new("/srv/navr")).as_deref(), Some("navr"));
+        assert_eq!(shortcut_name_for(Path::new("/")), None);
+    }
+
+    #[test]
+    fn test_remote_uri_detection() {
+        assert!(is_remote_uri("ssh://build-box/srv/app"));
+        assert!(is_remote_uri("sftp://user@host:2222/home/user"));
+        assert!(is_remote_uri("dav+s://files.example.com/team"));
+        assert!(!is_remote_uri("/home/user/projects"));
+        assert!(!is_remote_uri("~/projects"));
+        assert!(!is_remote_uri(r"C:\Users\me"));
+        assert!(!is_remote_uri("C://Users/me"));
+        assert!(!is_remote_uri("notes/ssh://host"));
+    }
 }
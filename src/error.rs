@@ -0,0 +1,59 @@
+//! Error kinds that callers and the exit status can tell apart
+//!
+//! Commands still return `anyhow::Result`; these are raised where the kind
+//! of failure matters and can be recovered with `downcast_ref`.
+
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum NavrError {
+    #[error("Shortcut '{0}' not found")]
+    ShortcutNotFound(String),
+
+    #[error("Path not found: {0}")]
+    PathNotFound(String),
+
+    #[error("'{0}' is a file, not a directory")]
+    NotADirectory(String),
+
+    #[error("Configured file manager '{0}' is not installed")]
+    FileManagerNotInstalled(String),
+
+    /// Every candidate failed; holds the collected failure messages
+    #[error("No file manager could open the path ({0})")]
+    FileManagerUnavailable(String),
+
+    /// A file manager run with `--wait` exited unsuccessfully
+    #[error("{program} exited with status {code}")]
+    FileManagerExited { program: String, code: i32 },
+
+    #[error(
+        "Failed to parse config from {path:?}{}",
+        backup.as_ref().map(|b| format!(" (the previous config is saved at {:?})", b)).unwrap_or_default()
+    )]
+    ConfigParse {
+        path: PathBuf,
+        backup: Option<PathBuf>,
+        #[source]
+        source: toml::de::Error,
+    },
+}
+
+impl NavrError {
+    /// Process exit status for this kind of failure; other errors exit with 1
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::ShortcutNotFound(_) | Self::PathNotFound(_) => 3,
+            Self::NotADirectory(_) => 4,
+            Self::FileManagerNotInstalled(_) | Self::FileManagerUnavailable(_) => 5,
+            Self::ConfigParse { .. } => 6,
+            Self::FileManagerExited { code, .. } => *code,
+        }
+    }
+}
+
+/// Exit status for an error returned from `run()`
+pub fn exit_code(error: &anyhow::Error) -> i32 {
+    error.downcast_ref::<NavrError>().map_or(1, NavrError::exit_code)
+}
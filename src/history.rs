@@ -0,0 +1,388 @@
+//! Navigation history storage
+//!
+//! Data files live under the platform data directory, one entry per line.
+//! History lines are `<unix seconds>\t<path>`; lines written before visits
+//! were timestamped hold just the path.
+//!
+//! Visits are appended in place; a file is only rewritten when it has to be
+//! trimmed, and then through a temporary file, so a crash never leaves a
+//! truncated file behind.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::config::paths::data_dir;
+use crate::lock::with_lock;
+
+/// Get the back stack file path
+fn stack_path() -> Result<PathBuf> {
+    Ok(data_dir()?.join("stack.txt"))
+}
+
+/// Get the file holding the directory `navr jump -` returns to
+fn last_dir_path() -> Result<PathBuf> {
+    Ok(data_dir()?.join("last_dir.txt"))
+}
+
+/// Get the visited-directory history file path
+pub fn history_path() -> Result<PathBuf> {
+    Ok(data_dir()?.join("history.txt"))
+}
+
+/// A recorded directory visit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryEntry {
+    pub path: String,
+    /// When the visit happened; unknown for entries from older versions
+    pub visited_at: Option<DateTime<Utc>>,
+}
+
+impl HistoryEntry {
+    fn parse(line: &str) -> Self {
+        let timestamped = line.split_once('\t').and_then(|(secs, path)| {
+            let visited_at = DateTime::from_timestamp(secs.parse().ok()?, 0)?;
+            Some(Self {
+                path: path.to_string(),
+                visited_at: Some(visited_at),
+            })
+        });
+
+        timestamped.unwrap_or_else(|| Self {
+            path: line.to_string(),
+            visited_at: None,
+        })
+    }
+
+    fn to_line(&self) -> String {
+        match self.visited_at {
+            Some(visited_at) => format!("{}\t{}", visited_at.timestamp(), self.path),
+            None => self.path.clone(),
+        }
+    }
+}
+
+/// Record a visited directory in the history file, keeping at most `max` entries
+pub fn add_history(path: &Path, max: usize) -> Result<()> {
+    let entry = HistoryEntry {
+        path: path.to_string_lossy().to_string(),
+        visited_at: Some(Utc::now()),
+    };
+    append_history(&history_path()?, entry, max)
+}
+
+fn append_history(file: &Path, entry: HistoryEntry, max: usize) -> Result<()> {
+    if entry.path.is_empty() {
+        return Ok(());
+    }
+
+    with_lock(file, || {
+        let mut lines = read_entries(file)?;
+
+        // Repeated hook calls for the same directory carry no information
+        if lines.last().is_some_and(|last| HistoryEntry::parse(last).path == entry.path) {
+            return Ok(());
+        }
+
+        if lines.len() < max {
+            return append_entry(file, &entry.to_line());
+        }
+        lines.push(entry.to_line());
+        keep_last(&mut lines, max);
+        write_entries(file, &lines)
+    })
+}
+
+/// Append directories visited at unknown times, oldest first
+pub fn import_history(paths: &[String], max: usize) -> Result<()> {
+    let file = history_path()?;
+    with_lock(&file, || {
+        let mut lines = read_entries(&file)?;
+        lines.extend(paths.iter().cloned());
+        keep_last(&mut lines, max);
+        write_entries(&file, &lines)
+    })
+}
+
+/// Read the history, oldest entry first
+pub fn read_history() -> Result<Vec<HistoryEntry>> {
+    Ok(read_entries(&history_path()?)?
+        .iter()
+        .map(|line| HistoryEntry::parse(line))
+        .collect())
+}
+
+/// Remove all recorded history
+pub fn clear_history() -> Result<()> {
+    let file = history_path()?;
+    if file.exists() {
+        std::fs::remove_file(&file).with_context(|| format!("Failed to remove {:?}", file))?;
+    }
+    Ok(())
+}
+
+/// Order entries most-recent-first, optionally collapsing duplicates
+/// and keeping at most `limit`
+pub fn recent_entries(entries: &[HistoryEntry], unique: bool, limit: Option<usize>) -> Vec<&str> {
+    let limit = limit.unwrap_or(usize::MAX);
+    let mut recent: Vec<&str> = Vec::new();
+    for entry in entries.iter().rev() {
+        if recent.len() >= limit {
+            break;
+        }
+        if unique && recent.contains(&entry.path.as_str()) {
+            continue;
+        }
+        recent.push(&entry.path);
+    }
+    recent
+}
+
+/// Push a directory onto the back stack
+pub fn push_stack(path: &Path, max: usize) -> Result<()> {
+    let file = stack_path()?;
+    with_lock(&file, || {
+        let mut entries = read_entries(&file)?;
+        push_entry(&mut entries, path.to_string_lossy().to_string(), max);
+        write_entries(&file, &entries)
+    })
+}
+
+/// Pop `count` directories off the back stack, returning the last one popped
+pub fn pop_stack(count: usize) -> Result<String> {
+    let file = stack_path()?;
+    with_lock(&file, || {
+        let mut entries = read_entries(&file)?;
+        let entry = pop_entries(&mut entries, count)?;
+        write_entries(&file, &entries)?;
+        Ok(entry)
+    })
+}
+
+/// Return the directory `pop_stack(count)` would return, leaving the stack intact
+pub fn peek_stack(count: usize) -> Result<String> {
+    let mut entries = read_entries(&stack_path()?)?;
+    pop_entries(&mut entries, count)
+}
+
+/// Remember `path` as the directory `navr jump -` returns to
+pub fn set_last_dir(path: &Path) -> Result<()> {
+    let file = last_dir_path()?;
+    with_lock(&file, || write_entries(&file, &[path.to_string_lossy().to_string()]))
+}
+
+/// The directory left by the most recent jump or directory change
+pub fn last_dir() -> Result<Option<String>> {
+    Ok(read_entries(&last_dir_path()?)?.pop())
+}
+
+/// Push an entry, collapsing an existing occurrence and keeping at most `max`
+fn push_entry(entries: &mut Vec<String>, entry: String, max: usize) {
+    entries.retain(|e| *e != entry);
+    entries.push(entry);
+    keep_last(entries, max);
+}
+
+/// Drop the oldest entries so at most `max` remain
+fn keep_last(entries: &mut Vec<String>, max: usize) {
+    if entries.len() > max {
+        let excess = entries.len() - max;
+        entries.drain(..excess);
+    }
+}
+
+/// Remove the `count` most recent entries, returning the oldest of them
+fn pop_entries(entries: &mut Vec<String>, count: usize) -> Result<String> {
+    if count == 0 {
+        anyhow::bail!("Cannot go back 0 directories");
+    }
+    if count > entries.len() {
+        anyhow::bail!(
+            "Cannot go back {} directories: only {} on the stack",
+            count,
+            entries.len()
+        );
+    }
+
+    let mut popped = entries.split_off(entries.len() - count);
+    Ok(popped.swap_remove(0))
+}
+
+fn read_entries(file: &Path) -> Result<Vec<String>> {
+    if !file.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {:?}", file))?;
+
+    Ok(content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Add one line at the end of `file` with a single `O_APPEND` write
+fn append_entry(file: &Path, entry: &str) -> Result<()> {
+    if let Some(parent) = file.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut handle = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(file)
+        .with_context(|| format!("Failed to open {:?}", file))?;
+    handle
+        .write_all(format!("{}\n", entry).as_bytes())
+        .with_context(|| format!("Failed to write {:?}", file))
+}
+
+/// Replace the contents of `file`, writing a temporary file and renaming it
+/// over the old one so readers see either the old or the new entries
+fn write_entries(file: &Path, entries: &[String]) -> Result<()> {
+    if let Some(parent) = file.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut content = entries.join("\n");
+    if !content.is_empty() {
+        content.push('\n');
+    }
+
+    let mut temp_name = file.file_name().context("Data file has no file name")?.to_os_string();
+    temp_name.push(".tmp");
+    let temp = file.with_file_name(temp_name);
+    std::fs::write(&temp, content).with_context(|| format!("Failed to write {:?}", temp))?;
+    std::fs::rename(&temp, file).with_context(|| format!("Failed to replace {:?}", file))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_push_collapses_duplicates_and_caps() {
+        let mut stack = entries(&["/a", "/b", "/c"]);
+
+        push_entry(&mut stack, "/a".to_string(), 3);
+        assert_eq!(stack, entries(&["/b", "/c", "/a"]));
+
+        push_entry(&mut stack, "/d".to_string(), 3);
+        assert_eq!(stack, entries(&["/c", "/a", "/d"]));
+    }
+
+    #[test]
+    fn test_history_is_capped() {
+        let dir = std::env::temp_dir().join(format!("navr-history-{}", std::process::id()));
+        let file = dir.join("history.txt");
+
+        let visit = |path: String| HistoryEntry {
+            path,
+            visited_at: Some(Utc::now()),
+        };
+        append_history(&file, visit(String::new()), 1000).unwrap();
+        assert!(!file.exists(), "a blank path must not be recorded");
+
+        for i in 0..2000 {
+            append_history(&file, visit(format!("/dir/{}", i)), 1000).unwrap();
+        }
+        append_history(&file, visit("/dir/1999".to_string()), 1000).unwrap();
+
+        let history: Vec<_> = read_entries(&file)
+            .unwrap()
+            .iter()
+            .map(|line| HistoryEntry::parse(line).path)
+            .collect();
+        assert_eq!(history.len(), 1000);
+        assert_eq!(history.first().map(String::as_str), Some("/dir/1000"));
+        assert_eq!(history.last().map(String::as_str), Some("/dir/1999"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_concurrent_appends_keep_every_entry() {
+        let dir = std::env::temp_dir().join(format!("navr-history-lock-{}", std::process::id()));
+        let file = dir.join("history.txt");
+
+        let writers: Vec<_> = ["a", "b"]
+            .into_iter()
+            .map(|writer| {
+                let file = file.clone();
+                std::thread::spawn(move || {
+                    for i in 0..100 {
+                        let entry = HistoryEntry {
+                            path: format!("/{}/{}", writer, i),
+                            visited_at: Some(Utc::now()),
+                        };
+                        append_history(&file, entry, 1000).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let lines = read_entries(&file).unwrap();
+        assert_eq!(lines.len(), 200);
+        for writer in ["a", "b"] {
+            let paths: Vec<String> = lines
+                .iter()
+                .map(|line| HistoryEntry::parse(line).path)
+                .filter(|path| path.starts_with(&format!("/{}/", writer)))
+                .collect();
+            let expected: Vec<String> = (0..100).map(|i| format!("/{}/{}", writer, i)).collect();
+            assert_eq!(paths, expected);
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_history_entry_lines() {
+        let entry = HistoryEntry::parse("1700000000\t/home/user/a\tb");
+        assert_eq!(entry.path, "/home/user/a\tb");
+        assert_eq!(entry.visited_at.map(|t| t.timestamp()), Some(1_700_000_000));
+        assert_eq!(HistoryEntry::parse(&entry.to_line()), entry);
+
+        // Lines from before timestamps were recorded are bare paths
+        let legacy = HistoryEntry::parse("/home/user/old");
+        assert_eq!(legacy.path, "/home/user/old");
+        assert!(legacy.visited_at.is_none());
+        assert_eq!(legacy.to_line(), "/home/user/old");
+    }
+
+    #[test]
+    fn test_recent_entries() {
+        let history: Vec<_> = ["/a", "/b", "/a", "/c"]
+            .iter()
+            .map(|line| HistoryEntry::parse(line))
+            .collect();
+
+        assert_eq!(recent_entries(&history, false, None), vec!["/c", "/a", "/b", "/a"]);
+        assert_eq!(recent_entries(&history, true, None), vec!["/c", "/a", "/b"]);
+        assert_eq!(recent_entries(&history, true, Some(2)), vec!["/c", "/a"]);
+        assert!(recent_entries(&history, false, Some(0)).is_empty());
+    }
+
+    #[test]
+    fn test_pop_entries() {
+        let mut stack = entries(&["/a", "/b", "/c"]);
+
+        assert_eq!(pop_entries(&mut stack, 2).unwrap(), "/b");
+        assert_eq!(stack, entries(&["/a"]));
+
+        assert!(pop_entries(&mut stack, 2).is_err());
+        assert!(pop_entries(&mut stack, 0).is_err());
+        assert_eq!(pop_entries(&mut stack, 1).unwrap(), "/a");
+        assert!(stack.is_empty());
+    }
+}
@@ -0,0 +1,38 @@
+//! Advisory locks around read-modify-write updates of data files
+//!
+//! Shell hooks record history in the background, so several navr processes
+//! can update the same file at once. An update holds an exclusive lock on a
+//! sibling `.<name>.lock` file from the read until the write completes.
+
+use anyhow::{Context, Result};
+use std::fs::OpenOptions;
+use std::path::{Path, PathBuf};
+
+/// Run `update` while holding the exclusive lock for `file`
+pub fn with_lock<T>(file: &Path, update: impl FnOnce() -> Result<T>) -> Result<T> {
+    let lock_path = lock_path(file)?;
+    if let Some(parent) = lock_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let lock = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .with_context(|| format!("Failed to open lock file {:?}", lock_path))?;
+    lock.lock()
+        .with_context(|| format!("Failed to lock {:?}", lock_path))?;
+
+    // The lock is released when `lock` is dropped
+    update()
+}
+
+/// The lock file guarding `file`
+pub fn lock_path(file: &Path) -> Result<PathBuf> {
+    let name = file.file_name().context("Locked path has no file name")?;
+    let mut lock_name = std::ffi::OsString::from(".");
+    lock_name.push(name);
+    lock_name.push(".lock");
+    Ok(file.with_file_name(lock_name))
+}
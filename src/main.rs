@@ -4,16 +4,24 @@
 //! and cross-platform shell support.
 
 use anyhow::Result;
-use clap::{CommandFactory, Parser, Subcommand};
-use owo_colors::OwoColorize;
+use clap::{Args, CommandFactory, Parser, Subcommand};
+use owo_colors::{OwoColorize, Stream::Stderr, Style};
+use std::io::IsTerminal;
+use std::path::PathBuf;
 use std::process;
 
+mod archive;
+mod cache;
 mod commands;
 mod config;
+mod error;
+mod history;
+mod lock;
 mod platform;
+mod session;
 mod shell;
 
-use commands::{config::{ConfigCommand, ConfigSubCommand, ShellSubCommand}, jump::JumpCommand, open::OpenCommand};
+use commands::{complete::CompleteKind, config::{ConfigCommand, ConfigSubCommand, ShellSubCommand}, export::ExportSection, import::ImportSource, history::{HistoryCommand, HistorySubCommand}, jump::{JumpCommand, ListOptions, ListSort}, open::OpenCommand};
 use config::AppConfig;
 
 /// Navr - Fast directory navigation tool
@@ -38,6 +46,10 @@ struct Cli {
     #[arg(short, long, global = true, help = "Enable verbose logging")]
     verbose: bool,
 
+    /// Show what would be done without doing it
+    #[arg(long, global = true, help = "Print intended changes instead of making them")]
+    dry_run: bool,
+
     /// Configuration file path
     #[arg(short, long, global = true, help = "Path to custom config file")]
     config: Option<String>,
@@ -46,9 +58,28 @@ struct Cli {
     #[arg(
         short = 'k',
         long = "quick",
-        help = "Quickly open a directory or shortcut"
+        help = "Quickly open directories or shortcuts",
+        num_args = 1..
     )]
-    quick: Option<String>,
+    quick: Option<Vec<String>>,
+
+    /// Disable colored output
+    #[arg(long, global = true, help = "Disable colored output (also set by NO_COLOR)")]
+    no_color: bool,
+
+    /// Keep config and data next to the navr executable
+    #[arg(long, global = true, help = "Use the executable's directory for config and data")]
+    portable: bool,
+
+    /// Config profile to use
+    #[arg(
+        long,
+        global = true,
+        value_name = "NAME",
+        conflicts_with = "config",
+        help = "Use the config and history of profile NAME"
+    )]
+    profile: Option<String>,
 
     /// Subcommands
     #[command(subcommand)]
@@ -59,33 +90,62 @@ struct Cli {
 enum Commands {
     /// Jump to a directory or shortcut
     #[command(visible_alias = "j")]
-    Jump {
-        /// Target directory or shortcut name
-        target: Option<String>,
-
-        /// List all available shortcuts
-        #[arg(short, long)]
-        list: bool,
-
-        /// Add current directory as shortcut
-        #[arg(short, long, value_name = "NAME")]
-        add: Option<String>,
-
-        /// Remove a shortcut
-        #[arg(short, long, value_name = "NAME")]
-        remove: Option<String>,
-    },
+    Jump(Box<JumpArgs>),
 
     /// Open directory in file manager
     #[command(visible_alias = "o")]
     Open {
-        /// Directory or shortcut to open
-        target: Option<String>,
+        /// Directories or shortcuts to open (defaults to the current directory)
+        targets: Vec<String>,
+
+        /// Open the Nth most recent directory from history instead
+        #[arg(long, value_name = "N", num_args = 0..=1, default_missing_value = "1", conflicts_with = "targets")]
+        recent: Option<usize>,
 
         /// Open with specific file manager
         #[arg(short, long)]
         with: Option<String>,
-},
+
+        /// Reveal the target in its folder instead of opening it
+        #[arg(short = 'R', long)]
+        reveal: bool,
+
+        /// Open a terminal in the directory instead of a file manager
+        #[arg(short, long, conflicts_with_all = ["with", "reveal"])]
+        terminal: bool,
+
+        /// Open the directory in this application, e.g. an editor or IDE (or a file_managers alias)
+        #[arg(long, value_name = "CMD", conflicts_with_all = ["with", "reveal", "terminal", "wait"])]
+        app: Option<String>,
+
+        /// Extra argument for the file manager (repeatable)
+        #[arg(long = "arg", value_name = "ARG", allow_hyphen_values = true, conflicts_with = "terminal")]
+        extra_args: Vec<String>,
+
+        /// Wait for a terminal file manager (ranger, vifm, mc) to exit and return its status
+        #[arg(long, conflicts_with = "terminal")]
+        wait: bool,
+
+        /// Print the command that would be run instead of running it
+        #[arg(long, conflicts_with = "wait")]
+        print_cmd: bool,
+
+        /// Do not record the opened directory in the history
+        #[arg(long)]
+        no_track: bool,
+
+        /// Reuse an open file manager window (overrides behavior.reuse_window)
+        #[arg(long, conflicts_with_all = ["new_window", "terminal"])]
+        reuse: bool,
+
+        /// Open a new file manager window (overrides behavior.reuse_window)
+        #[arg(long, conflicts_with = "terminal")]
+        new_window: bool,
+
+        /// Detach the opened program so it outlives the terminal (see behavior.detach_children)
+        #[arg(long, conflicts_with = "wait")]
+        detached: bool,
+    },
 
     /// Configuration management
     #[command(visible_alias = "cfg")]
@@ -101,6 +161,13 @@ enum Commands {
         action: ShellSubCommand,
     },
 
+    /// Show and manage visited-directory history
+    #[command(visible_alias = "hist")]
+    History {
+        #[command(subcommand)]
+        action: HistorySubCommand,
+    },
+
     /// Import/Export configuration
     #[command(visible_alias = "exp")]
     Export {
@@ -108,35 +175,302 @@ enum Commands {
         #[arg(short, long, default_value = "toml")]
         format: String,
 
-        /// Output file path
+        /// Output file path, or - to print to stdout
         #[arg(short, long)]
         output: Option<String>,
+
+        /// Only export these sections (comma-separated)
+        #[arg(long, value_enum, value_delimiter = ',', value_name = "SECTIONS")]
+        only: Vec<ExportSection>,
     },
 
     /// Import configuration
     #[command(visible_alias = "imp")]
     Import {
-        /// Input file path
-        input: String,
+        /// Input file path, or - to read from stdin
+        #[arg(required_unless_present = "from")]
+        input: Option<String>,
 
-        /// Merge with existing config
+        /// Merge with existing config, keeping existing entries on conflict
         #[arg(short, long)]
         merge: bool,
+
+        /// When merging, replace existing shortcuts and file managers
+        #[arg(long, requires = "merge")]
+        overwrite: bool,
+
+        /// Replace without asking: the whole configuration, or with
+        /// --merge --overwrite, existing shortcuts
+        #[arg(short, long)]
+        force: bool,
+
+        /// Import visited directories from another tool instead of a config file
+        #[arg(long, value_enum, conflicts_with_all = ["input", "merge"])]
+        from: Option<ImportSource>,
+
+        /// With --from, also add shortcuts for the N highest-ranked directories
+        #[arg(long, value_name = "N", default_value_t = 0, requires = "from")]
+        shortcuts: usize,
+    },
+
+    /// Show the directory a jump target resolves to and where it was found
+    Which {
+        /// Shortcut name or path
+        target: String,
+    },
+
+    /// Remove shortcuts pointing at missing directories (use --dry-run to only list them)
+    Prune,
+
+    /// Summarize navigation history
+    Stats {
+        /// Only count visits on or after this date (YYYY-MM-DD or RFC 3339)
+        #[arg(long, value_name = "DATE")]
+        since: Option<String>,
+    },
+
+    /// Diagnose the installation, configuration and shell integration
+    Doctor,
+
+    /// Run a self-test of the config and open pipeline in a sandbox
+    #[command(name = "selftest")]
+    SelfTest,
+
+    /// Dynamic completion backend used by shell integration
+    #[command(name = "_complete", alias = "__complete", hide = true)]
+    Complete {
+        /// Kind of candidates to list
+        #[arg(value_enum)]
+        kind: CompleteKind,
+
+        /// Context argument (e.g. the config key for values)
+        arg: Option<String>,
     },
 }
 
+/// Arguments of `navr jump`, boxed so they do not size every `Commands` value
+#[derive(Args, Debug)]
+struct JumpArgs {
+    /// Target directory or shortcut name
+    target: Option<String>,
+
+    /// List all available shortcuts
+    #[arg(short, long)]
+    list: bool,
+
+    /// Add current directory as shortcut, named after the directory if NAME is omitted
+    #[arg(short, long, value_name = "NAME", num_args = 0..=1)]
+    add: Option<Option<String>>,
+
+    /// With --add, bookmark this directory instead of the current one
+    #[arg(long, value_name = "DIR", requires = "add", conflicts_with = "alias_of")]
+    add_path: Option<String>,
+
+    /// With --add, bookmark the root of the enclosing git repository
+    #[arg(long, requires = "add", conflicts_with = "alias_of")]
+    git_root: bool,
+
+    /// With --add, keep the shortcut for this shell session only
+    #[arg(long, requires = "add", conflicts_with = "alias_of")]
+    temp: bool,
+
+    /// Add the shortcuts listed in FILE, one `name = path` or `name<TAB>path` per line
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["target", "list", "add", "remove", "rename"])]
+    add_from: Option<PathBuf>,
+
+    /// With --add, make the new shortcut an alias of an existing one
+    #[arg(long, value_name = "SHORTCUT", requires = "add")]
+    alias_of: Option<String>,
+
+    /// Remove a shortcut
+    #[arg(short, long, value_name = "NAME")]
+    remove: Option<String>,
+
+    /// Rename a shortcut, keeping its metadata
+    #[arg(long, num_args = 2, value_names = ["OLD", "NEW"], conflicts_with_all = ["list", "add", "remove"])]
+    rename: Option<Vec<String>>,
+
+    /// Jump back N directories on the navigation stack
+    #[arg(short, long, value_name = "N", num_args = 0..=1, default_missing_value = "1")]
+    back: Option<usize>,
+
+    /// Print the resolved path of the target without jumping
+    #[arg(long, requires = "target", conflicts_with_all = ["list", "add", "remove", "back"])]
+    resolve: bool,
+
+    /// Choose the destination from an interactive picker
+    #[arg(short, long, conflicts_with_all = ["list", "add", "remove", "back", "resolve"])]
+    pick: bool,
+
+    /// Browse shortcuts and recent directories full-screen, filtering by TARGET
+    #[arg(short, long, conflicts_with_all = ["list", "add", "remove", "back", "resolve", "pick", "copy"])]
+    interactive: bool,
+
+    /// Copy the resolved path of the target to the clipboard without jumping
+    #[arg(long, requires = "target", conflicts_with_all = ["list", "add", "remove", "back", "resolve", "pick"])]
+    copy: bool,
+
+    /// Do not record the destination in the history
+    #[arg(long)]
+    no_track: bool,
+
+    /// List close matches for the shell integration to choose from (NAVR_JUMP_MENU protocol)
+    #[arg(long, hide = true, requires = "target", conflicts_with_all = ["resolve", "pick", "interactive"])]
+    menu: bool,
+
+    /// Only list shortcuts carrying this tag
+    #[arg(long, value_name = "TAG", requires = "list")]
+    tag: Option<String>,
+
+    /// Only list shortcuts in this group
+    #[arg(long, value_name = "NAME", requires = "list")]
+    group: Option<String>,
+
+    /// Only list shortcuts whose name or path contains this text
+    #[arg(long, value_name = "TEXT", requires = "list")]
+    filter: Option<String>,
+
+    /// Print the shortcut list as JSON
+    #[arg(long, requires = "list")]
+    json: bool,
+
+    /// Print only the target paths, one per line, without color
+    #[arg(long, requires = "list", conflicts_with_all = ["json", "names_only"])]
+    paths_only: bool,
+
+    /// Print only the shortcut names, one per line, without color
+    #[arg(long, requires = "list", conflicts_with = "json")]
+    names_only: bool,
+
+    /// Check shortcut targets again instead of using cached results
+    #[arg(long, requires = "list")]
+    no_cache: bool,
+
+    /// Only list shortcuts whose target is missing or unusable
+    #[arg(long, requires = "list")]
+    broken: bool,
+
+    /// Order of the listed shortcuts
+    #[arg(long, value_enum, default_value_t = ListSort::Name, requires = "list")]
+    sort: ListSort,
+
+    /// List shortcuts in one alphabetical section instead of by group
+    #[arg(long, requires = "list", conflicts_with_all = ["json", "paths_only", "names_only"])]
+    flat: bool,
+
+    /// Mark each listed shortcut with the status of its target
+    #[arg(long, requires = "list", conflicts_with_all = ["json", "paths_only", "names_only", "broken"])]
+    check: bool,
+
+    /// Find git projects under ROOT and offer to add shortcuts for them
+    #[arg(
+        long,
+        value_name = "ROOT",
+        conflicts_with_all = ["target", "list", "add", "remove", "back", "pick"]
+    )]
+    scan: Option<String>,
+
+    /// With --scan, how many levels below ROOT to search
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = commands::scan::DEFAULT_SCAN_DEPTH,
+        requires = "scan"
+    )]
+    depth: usize,
+
+    /// With --scan, also search hidden directories
+    #[arg(long, requires = "scan")]
+    hidden: bool,
+}
+
 fn main() {
     if let Err(e) = run() {
-        eprintln!("{} {}", "Error:".red().bold(), e);
-        process::exit(1);
+        eprintln!(
+            "{} {}",
+            "Error:".if_supports_color(Stderr, |t| t.style(Style::new().red().bold())),
+            e
+        );
+        process::exit(error::exit_code(&e));
     }
 }
 
 fn run() -> Result<()> {
     let cli = Cli::parse();
 
+    // Bare listings are meant for other programs
+    let bare_list = matches!(
+        &cli.command,
+        Some(Commands::Jump(args)) if args.paths_only || args.names_only
+    );
+    // https://no-color.org: any non-empty value disables color. Otherwise
+    // owo-colors colors each stream only while it is a terminal.
+    let no_color = cli.no_color
+        || bare_list
+        || std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    if no_color {
+        owo_colors::set_override(false);
+    }
+
     // Initialize logging
-    init_logging(cli.verbose);
+    init_logging(cli.verbose, !no_color);
+
+    if cli.portable {
+        config::paths::use_portable_dir()?;
+    }
+    if let Some(profile) = &cli.profile {
+        config::paths::use_profile(profile)?;
+    }
+
+    // The self-test uses its own sandbox and never touches the real config
+    if let Some(Commands::SelfTest) = cli.command {
+        return commands::selftest::execute();
+    }
+
+    // The doctor reports config problems instead of failing to load it
+    if let Some(Commands::Doctor) = cli.command {
+        return commands::doctor::execute(cli.config.as_deref());
+    }
+
+    // Validating a file must not depend on the active config loading
+    if let Some(Commands::Config {
+        action: ConfigSubCommand::Validate { path },
+    }) = &cli.command
+    {
+        return commands::config::validate_file(path);
+    }
+    if let Some(Commands::Config {
+        action: ConfigSubCommand::Schema,
+    }) = &cli.command
+    {
+        return commands::config::print_schema();
+    }
+
+    // Profiles are managed without loading one, so a missing profile can be created
+    if let Some(Commands::Config {
+        action: ConfigSubCommand::Profile { action },
+    }) = &cli.command
+    {
+        return commands::profile::execute(action, cli.dry_run);
+    }
+
+    // Locating and hand-editing the file must work even when the config does not load
+    let config_file = || match &cli.config {
+        Some(path) => Ok(PathBuf::from(path)),
+        None => AppConfig::config_path(),
+    };
+    if let Some(Commands::Config {
+        action: ConfigSubCommand::Path { json },
+    }) = &cli.command
+    {
+        return commands::config::print_paths(&config_file()?, *json);
+    }
+    if let Some(Commands::Config {
+        action: ConfigSubCommand::Edit { raw: true },
+    }) = &cli.command
+    {
+        return commands::config::edit_file(&config_file()?);
+    }
 
     // Load configuration
     let mut config = if let Some(config_path) = &cli.config {
@@ -144,27 +478,120 @@ fn run() -> Result<()> {
     } else {
         AppConfig::load()?
     };
+    config.set_dry_run(cli.dry_run);
+    match session::read_shortcuts() {
+        Ok(shortcuts) => config.set_session_shortcuts(shortcuts),
+        Err(e) => tracing::warn!("Ignoring session shortcuts: {:#}", e),
+    }
 
     // Handle quick mode (-k/--quick)
-    if let Some(quick_target) = cli.quick {
-        let cmd = OpenCommand::new(quick_target);
+    if let Some(quick_targets) = cli.quick {
+        let cmd = OpenCommand::new(quick_targets);
         return cmd.execute(&config);
     }
 
     // Execute subcommand or show help
     match cli.command {
-        Some(Commands::Jump {
-            target,
-            list,
-            add,
-            remove,
-        }) => {
-            let cmd = JumpCommand::new(target, list, add, remove);
+        Some(Commands::Jump(args)) => {
+            let JumpArgs {
+                target,
+                list,
+                add,
+                add_path,
+                git_root,
+                temp,
+                add_from,
+                alias_of,
+                remove,
+                rename,
+                back,
+                resolve,
+                pick,
+                interactive,
+                menu,
+                copy,
+                no_track,
+                tag,
+                group,
+                filter,
+                json,
+                paths_only,
+                names_only,
+                no_cache,
+                broken,
+                sort,
+                flat,
+                check,
+                scan,
+                depth,
+                hidden,
+            } = *args;
+            if let Some(root) = scan {
+                return commands::scan::execute(&mut config, &root, depth, hidden);
+            }
+            let rename = rename.map(|names| (names[0].clone(), names[1].clone()));
+            let auto_add = matches!(add, Some(None));
+            let cmd = JumpCommand::new(target, list, add.flatten(), remove)
+                .with_auto_add(auto_add)
+                .with_rename(rename)
+                .with_back(back)
+                .with_alias_of(alias_of)
+                .with_add_path(add_path)
+                .with_git_root(git_root)
+                .with_temp(temp)
+                .with_add_from(add_from)
+                .with_resolve(resolve)
+                .with_pick(pick)
+                .with_interactive(interactive)
+                .with_menu(menu)
+                .with_copy(copy)
+                .with_track(!no_track)
+                .with_verbose(cli.verbose)
+                .with_list_options(ListOptions {
+                    tag,
+                    group,
+                    filter,
+                    json,
+                    paths_only,
+                    names_only,
+                    no_cache,
+                    sort,
+                    broken,
+                    flat,
+                    check,
+                });
             cmd.execute(&mut config)?;
         }
-        Some(Commands::Open { target, with }) => {
-            let target = target.unwrap_or_else(|| ".".to_string());
-            let cmd = OpenCommand::with_manager(target, with);
+        Some(Commands::Open {
+            mut targets,
+            recent,
+            with,
+            reveal,
+            terminal,
+            app,
+            extra_args,
+            wait,
+            print_cmd,
+            reuse,
+            new_window,
+            no_track,
+            detached,
+        }) => {
+            if let Some(n) = recent {
+                targets.push(commands::open::recent_target(&config, n)?);
+            } else if targets.is_empty() {
+                targets.push(".".to_string());
+            }
+            let cmd = OpenCommand::with_manager(targets, with)
+                .with_reveal(reveal)
+                .with_terminal(terminal)
+                .with_app(app)
+                .with_extra_args(extra_args)
+                .with_wait(wait)
+                .with_print_cmd(print_cmd)
+                .with_reuse_window((reuse || new_window).then_some(reuse))
+                .with_track(!no_track)
+                .with_detached(detached);
             cmd.execute(&config)?;
         }
         Some(Commands::Config { action }) => {
@@ -172,13 +599,43 @@ fn run() -> Result<()> {
             cmd.execute(&mut config)?;
         }
         Some(Commands::Shell { action }) => {
-            handle_shell_command(action)?;
+            handle_shell_command(action, &config)?;
         }
-        Some(Commands::Export { format, output }) => {
-            commands::export::execute(&config, &format, output.as_deref())?;
+        Some(Commands::History { action }) => {
+            let cmd = HistoryCommand::new(action);
+            cmd.execute(&config)?;
+        }
+        Some(Commands::Export { format, output, only }) => {
+            commands::export::execute(&config, &format, output.as_deref(), &only)?;
+        }
+        Some(Commands::Import {
+            input,
+            merge,
+            overwrite,
+            force,
+            from,
+            shortcuts,
+        }) => match (from, input) {
+            (Some(source), _) => commands::import::import_from(&mut config, source, shortcuts)?,
+            (None, Some(input)) => {
+                commands::import::execute(&mut config, &input, merge, overwrite, force)?
+            }
+            (None, None) => unreachable!("clap requires an input file without --from"),
+        },
+        Some(Commands::Which { target }) => {
+            JumpCommand::new(Some(target), false, None, None)
+                .with_which(true)
+                .execute(&mut config)?;
         }
-        Some(Commands::Import { input, merge }) => {
-            commands::import::execute(&mut config, &input, merge)?;
+        Some(Commands::Prune) => {
+            commands::prune::execute(&mut config)?;
+        }
+        Some(Commands::Stats { since }) => {
+            commands::stats::execute(since.as_deref())?;
+        }
+        Some(Commands::SelfTest | Commands::Doctor) => unreachable!("handled before loading config"),
+        Some(Commands::Complete { kind, arg }) => {
+            commands::complete::execute(&config, kind, arg.as_deref())?;
         }
         None => {
             // No subcommand - interactive mode or show help
@@ -190,22 +647,35 @@ fn run() -> Result<()> {
     Ok(())
 }
 
-fn handle_shell_command(action: ShellSubCommand) -> Result<()> {
+fn handle_shell_command(action: ShellSubCommand, config: &AppConfig) -> Result<()> {
     match action {
-        ShellSubCommand::Complete { shell } => {
-            shell::generate_completions(shell)?;
+        ShellSubCommand::Complete { shell, dynamic, output } => {
+            let shell = shell.map_or_else(shell::detect_current_shell, Ok)?;
+            match output {
+                Some(dir) => shell::write_completions(shell, dynamic, dir.as_deref(), config)?,
+                None => shell::generate_completions(shell, dynamic)?,
+            }
         }
         ShellSubCommand::Install { shell, path } => {
+            let shell = shell.map_or_else(shell::detect_current_shell, Ok)?;
             shell::install_integration(shell, path.as_deref())?;
         }
-        ShellSubCommand::Init { shell } => {
-            shell::print_init_script(shell)?;
+        ShellSubCommand::Uninstall { shell, path } => {
+            let shell = shell.map_or_else(shell::detect_current_shell, Ok)?;
+            shell::uninstall_integration(shell, path.as_deref())?;
+        }
+        ShellSubCommand::Init { shell, output } => {
+            let shell = shell.map_or_else(shell::detect_current_shell, Ok)?;
+            match output {
+                Some(dir) => shell::write_init_script(shell, dir.as_deref(), config)?,
+                None => shell::print_init_script(shell)?,
+            }
         }
     }
     Ok(())
 }
 
-fn init_logging(verbose: bool) {
+fn init_logging(verbose: bool, color: bool) {
     let filter = if verbose {
         "debug"
     } else {
@@ -218,5 +688,8 @@ fn init_logging(verbose: bool) {
         .with_thread_ids(false)
         .with_file(false)
         .with_line_number(false)
+        .with_ansi(color && std::io::stderr().is_terminal())
+        // stdout carries jump targets for the shell wrappers
+        .with_writer(std::io::stderr)
         .init();
 }
@@ -0,0 +1,125 @@
+//! Copying text to the system clipboard with the platform's clipboard tools
+
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use super::wsl;
+
+/// Copy `text` to the clipboard with the first clipboard tool that works
+///
+/// Fails when none is installed or there is no display to own the
+/// clipboard, as on a headless Linux machine.
+pub fn copy(text: &str) -> Result<()> {
+    let tools = clipboard_tools(
+        std::env::consts::OS,
+        std::env::var_os("WAYLAND_DISPLAY").is_some(),
+        std::env::var_os("DISPLAY").is_some(),
+        wsl::is_wsl(),
+    );
+    if tools.is_empty() {
+        anyhow::bail!("No display to hold the clipboard");
+    }
+
+    let mut failures = Vec::new();
+    for tool in &tools {
+        if which::which(tool[0]).is_err() {
+            continue;
+        }
+        match run_tool(tool, text) {
+            Ok(()) => return Ok(()),
+            Err(e) => failures.push(format!("{}: {:#}", tool[0], e)),
+        }
+    }
+
+    if failures.is_empty() {
+        let names: Vec<&str> = tools.iter().map(|tool| tool[0]).collect();
+        anyhow::bail!("No clipboard tool found (tried {})", names.join(", "));
+    }
+    anyhow::bail!("Could not copy to the clipboard ({})", failures.join("; "))
+}
+
+/// Clipboard tools to try in order; empty when nothing can hold a clipboard
+fn clipboard_tools(
+    os: &str,
+    wayland: bool,
+    x11: bool,
+    wsl: bool,
+) -> Vec<&'static [&'static str]> {
+    let mut tools: Vec<&'static [&'static str]> = Vec::new();
+    match os {
+        "macos" => tools.push(&["pbcopy"]),
+        "windows" => tools.push(&["clip"]),
+        _ => {
+            if wayland {
+                tools.push(&["wl-copy"]);
+            }
+            if x11 {
+                tools.push(&["xclip", "-selection", "clipboard"]);
+                tools.push(&["xsel", "--clipboard", "--input"]);
+            }
+            if wsl {
+                tools.push(&["clip.exe"]);
+            }
+        }
+    }
+    tools
+}
+
+fn run_tool(tool: &[&str], text: &str) -> Result<()> {
+    let mut child = Command::new(tool[0])
+        .args(&tool[1..])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to run {}", tool[0]))?;
+
+    child
+        .stdin
+        .take()
+        .context("No stdin to write to")?
+        .write_all(&encode_for(tool[0], text))?;
+    let status = child.wait()?;
+    if !status.success() {
+        anyhow::bail!("exited with {}", status);
+    }
+    Ok(())
+}
+
+/// The bytes `tool` expects on stdin for `text`
+///
+/// Windows' `clip` reads input in the console code page unless it starts
+/// with a UTF-16LE byte order mark, so non-ASCII paths need that encoding.
+fn encode_for(tool: &str, text: &str) -> Vec<u8> {
+    if !matches!(tool, "clip" | "clip.exe") {
+        return text.as_bytes().to_vec();
+    }
+    let mut bytes = vec![0xFF, 0xFE];
+    bytes.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clipboard_tools_follow_display() {
+        let first = |tools: Vec<&'static [&'static str]>| tools.first().map(|tool| tool[0]);
+
+        assert_eq!(first(clipboard_tools("macos", false, false, false)), Some("pbcopy"));
+        assert_eq!(first(clipboard_tools("linux", true, true, false)), Some("wl-copy"));
+        assert_eq!(first(clipboard_tools("linux", false, true, false)), Some("xclip"));
+        assert_eq!(first(clipboard_tools("linux", false, false, true)), Some("clip.exe"));
+        // Headless: nothing to copy to
+        assert!(clipboard_tools("linux", false, false, false).is_empty());
+    }
+
+    #[test]
+    fn test_clip_gets_utf16_with_bom() {
+        assert_eq!(encode_for("pbcopy", "C:\\Café"), "C:\\Café".as_bytes());
+        assert_eq!(encode_for("clip.exe", "Cé"), [0xFF, 0xFE, b'C', 0, 0xE9, 0]);
+        assert_eq!(encode_for("clip", ""), [0xFF, 0xFE]);
+    }
+}
@@ -1,230 +1,653 @@
 //! File manager integration for different platforms
 
 use anyhow::{Context, Result};
+use owo_colors::{OwoColorize, Stream::Stderr};
 use std::path::Path;
-use std::process::Command;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
 
 use crate::config::AppConfig;
+use crate::config::defaults::detect_best_file_manager;
+use crate::error::NavrError;
+
+/// File managers with dedicated handling in `build_command`
+pub const BUILTIN_FILE_MANAGERS: &[&str] = &[
+    "explorer", "open", "finder", "xdg-open", "nautilus", "dolphin", "thunar", "pcmanfm", "nemo",
+    "caja", "ranger", "vifm", "mc",
+];
+
+/// File managers that run inside a terminal emulator
+const TERMINAL_FILE_MANAGERS: &[&str] = &["ranger", "vifm", "mc"];
+
+/// How often the monitoring thread checks whether a waited-for child exited
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(50);
 
 /// File manager handler
 pub struct FileManager {
     command: String,
+    extra_args: Vec<String>,
+    wait: bool,
+    reuse_window: bool,
+    detached: bool,
 }
 
 impl FileManager {
     pub fn new(command: &str) -> Self {
         Self {
             command: command.to_string(),
+            extra_args: Vec::new(),
+            wait: false,
+            reuse_window: false,
+            detached: false,
         }
     }
 
+    /// Pass these arguments after the built-in ones, before the target path
+    pub fn with_args(mut self, extra_args: Vec<String>) -> Self {
+        self.extra_args = extra_args;
+        self
+    }
+
+    /// Block until a terminal file manager exits instead of detaching
+    pub fn with_wait(mut self, wait: bool) -> Self {
+        self.wait = wait;
+        self
+    }
+
+    /// Ask the file manager to reuse an open window instead of starting a new one
+    pub fn with_reuse_window(mut self, reuse_window: bool) -> Self {
+        self.reuse_window = reuse_window;
+        self
+    }
+
+    /// Start the file manager detached from navr and the terminal: in a new
+    /// session on Unix, a new process group on Windows; see `detach`
+    pub fn with_detached(mut self, detached: bool) -> Self {
+        self.detached = detached;
+        self
+    }
+
     /// Open a path with the configured file manager
     pub fn open(&self, path: &Path, config: &AppConfig) -> Result<()> {
+        if self.command == "explorer" && config.platform.windows.use_shell_execute {
+            return shell_execute_open(path, config);
+        }
+        let cmd = self.build_command(path, config)?;
+        self.launch(cmd, config)
+    }
+
+    /// Open the containing folder with `path` selected
+    ///
+    /// Falls back to opening the parent directory for file managers that
+    /// have no selection syntax.
+    pub fn reveal(&self, path: &Path, config: &AppConfig) -> Result<()> {
+        let cmd = self.build_reveal_command(path, config)?;
+        self.launch(cmd, config)
+    }
+
+    fn launch(&self, cmd: Command, config: &AppConfig) -> Result<()> {
+        if self.wait {
+            if TERMINAL_FILE_MANAGERS.contains(&self.command.as_str()) {
+                return run_and_wait(cmd, config);
+            }
+            tracing::debug!("--wait has no effect for GUI file manager '{}'", self.command);
+        }
+        spawn(cmd, self.detached, config)
+    }
+
+    /// Build the command that opens `path`, without spawning it
+    pub fn build_command(&self, path: &Path, config: &AppConfig) -> Result<Command> {
         match self.command.as_str() {
-            "explorer" => self.open_windows_explorer(path),
-            "open" => self.open_macos_finder(path),
-            "finder" => self.open_macos_finder(path),
-            "xdg-open" => self.open_linux_xdg(path),
-            "nautilus" => self.open_with_args(path, &["nautilus", "--new-window"]),
-            "dolphin" => self.open_with_args(path, &["dolphin", "--new-window"]),
-            "thunar" => self.open_with_args(path, &["thunar"]),
-            "pcmanfm" => self.open_with_args(path, &["pcmanfm"]),
-            "nemo" => self.open_with_args(path, &["nemo"]),
-            "caja" => self.open_with_args(path, &["caja"]),
-            "ranger" => self.open_terminal_file_manager(path, "ranger"),
-            "vifm" => self.open_terminal_file_manager(path, "vifm"),
-            "mc" => self.open_terminal_file_manager(path, "mc"),
-            custom => self.open_custom(path, custom, config),
+            "explorer" => self.windows_explorer_command(path),
+            "open" | "finder" => self.command_with_args(path, &["open"]),
+            "xdg-open" => self.command_with_args(path, &["xdg-open"]),
+            fm @ ("nautilus" | "dolphin" | "nemo") => {
+                let mut args = vec![fm];
+                args.extend(self.window_flag());
+                self.command_with_args(path, &args)
+            }
+            "thunar" => self.command_with_args(path, &["thunar"]),
+            "pcmanfm" => self.command_with_args(path, &["pcmanfm"]),
+            "caja" => self.command_with_args(path, &["caja"]),
+            "ranger" => self.terminal_file_manager_command(path, "ranger", config),
+            "vifm" => self.terminal_file_manager_command(path, "vifm", config),
+            "mc" => self.terminal_file_manager_command(path, "mc", config),
+            custom => self.custom_command(path, custom, config),
         }
     }
 
-    fn open_windows_explorer(&self, path: &Path) -> Result<()> {
+    /// The flag selecting a new or reused window, for file managers that
+    /// do not already behave as asked
+    ///
+    /// nautilus reuses a window unless passed `--new-window`, as does dolphin
+    /// when set to open folders in tabs; nemo opens a new one unless passed
+    /// `--existing-window`.
+    fn window_flag(&self) -> Option<&'static str> {
+        match (self.command.as_str(), self.reuse_window) {
+            ("nautilus" | "dolphin", false) => Some("--new-window"),
+            ("nemo", true) => Some("--existing-window"),
+            _ => None,
+        }
+    }
+
+    /// Build the command that reveals `path`, without spawning it
+    pub fn build_reveal_command(&self, path: &Path, config: &AppConfig) -> Result<Command> {
+        match self.command.as_str() {
+            "explorer" => self.windows_reveal_command(path),
+            "open" | "finder" => self.command_with_args(path, &["open", "-R"]),
+            "nautilus" => self.command_with_args(path, &["nautilus", "--select"]),
+            "dolphin" => self.command_with_args(path, &["dolphin", "--select"]),
+            _ => {
+                let parent = path
+                    .parent()
+                    .filter(|p| !p.as_os_str().is_empty())
+                    .unwrap_or(path);
+                tracing::debug!(
+                    "{} cannot select files, opening {} instead",
+                    self.command,
+                    parent.display()
+                );
+                self.build_command(parent, config)
+            }
+        }
+    }
+
+    fn windows_reveal_command(&self, path: &Path) -> Result<Command> {
         #[cfg(target_os = "windows")]
         {
             use std::os::windows::process::CommandExt;
-            const CREATE_NO_WINDOW: u32 = 0x08000000;
-
-            Command::new("explorer")
-                .arg(path)
-                .creation_flags(CREATE_NO_WINDOW)
-                .spawn()
-                .context("Failed to open Windows Explorer")?;
 
-            Ok(())
+            // explorer expects `/select,<path>` as a single, unsplit argument
+            let mut cmd = Command::new("explorer");
+            cmd.raw_arg(format!("/select,\"{}\"", path.display()));
+            Ok(cmd)
         }
 
         #[cfg(not(target_os = "windows"))]
         {
-            anyhow::bail!("Windows Explorer is only available on Windows")
+            anyhow::bail!("Cannot reveal {}: Windows Explorer is only available on Windows", path.display())
         }
     }
 
-    fn open_macos_finder(&self, path: &Path) -> Result<()> {
-        #[cfg(target_os = "macos")]
+    fn windows_explorer_command(&self, path: &Path) -> Result<Command> {
+        #[cfg(target_os = "windows")]
         {
-            Command::new("xdg-open")
-                .arg(path)
-                .spawn()
-                .context("Failed to open Finder")?;
+            use std::os::windows::process::CommandExt;
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
 
-            Ok(())
+            let mut cmd = Command::new("explorer");
+            cmd.args(&self.extra_args).arg(path).creation_flags(CREATE_NO_WINDOW);
+            Ok(cmd)
         }
 
-        #[cfg(not(target_os = "macos"))]
+        #[cfg(not(target_os = "windows"))]
         {
-            // Try using open command anyway (might be available on some systems)
-            Command::new("open")
-                .arg(path)
-                .spawn()
-                .context("Failed to open with 'open' command")?;
-
-            Ok(())
+            let _ = path;
+            anyhow::bail!("Windows Explorer is only available on Windows")
         }
     }
 
-    fn open_linux_xdg(&self, path: &Path) -> Result<()> {
-        Command::new("xdg-open")
-            .arg(path)
-            .spawn()
-            .context("Failed to open with xdg-open. Is it installed?")?;
-
-        Ok(())
-    }
-
-    fn open_with_args(&self, path: &Path, args: &[&str]) -> Result<()> {
+    fn command_with_args(&self, path: &Path, args: &[&str]) -> Result<Command> {
         if args.is_empty() {
             anyhow::bail!("No command specified");
         }
 
         let mut cmd = Command::new(args[0]);
-        cmd.args(&args[1..]).arg(path);
-
-        cmd.spawn()
-            .with_context(|| format!("Failed to open with {}", args[0]))?;
+        cmd.args(&args[1..]).args(&self.extra_args).arg(path);
 
-        Ok(())
+        Ok(cmd)
     }
 
-    fn open_terminal_file_manager(&self, path: &Path, fm: &str) -> Result<()> {
+    fn terminal_file_manager_command(&self, path: &Path, fm: &str, config: &AppConfig) -> Result<Command> {
         // Detect available terminal emulator
-        let terminal = self.detect_terminal()?;
+        let terminal = detect_terminal(config)?;
 
         let mut cmd = Command::new(&terminal);
 
         // Add terminal-specific arguments
         match terminal.as_str() {
             "gnome-terminal" => {
-                cmd.args(&["--window", "--"]).arg(fm).arg(path);
+                cmd.args(["--window", "--"]).arg(fm).args(&self.extra_args).arg(path);
             }
             "konsole" => {
-                cmd.args(&["--new-tab", "-e"]).arg(fm).arg(path);
+                cmd.args(["--new-tab", "-e"]).arg(fm).args(&self.extra_args).arg(path);
             }
             "xfce4-terminal" => {
-                cmd.args(&["--command", &format!("{} '{}'", fm, path.display())]);
+                let mut command = fm.to_string();
+                for arg in &self.extra_args {
+                    command.push_str(&format!(" '{}'", arg));
+                }
+                cmd.args(["--command", &format!("{} '{}'", command, path.display())]);
             }
             "alacritty" => {
-                cmd.args(&["--command", fm, &path.to_string_lossy()]);
+                cmd.args(["--command", fm]).args(&self.extra_args).arg(path);
             }
             "kitty" => {
-                cmd.args(&["--", fm]).arg(path);
+                cmd.args(["--", fm]).args(&self.extra_args).arg(path);
             }
             "wezterm" => {
-                cmd.args(&["start", "--", fm]).arg(path);
+                cmd.args(["start", "--", fm]).args(&self.extra_args).arg(path);
             }
             "xterm" | "rxvt" | "urxvt" => {
-                cmd.arg("-e").arg(fm).arg(path);
+                cmd.arg("-e").arg(fm).args(&self.extra_args).arg(path);
             }
             _ => {
                 // Generic fallback
-                cmd.arg("-e").arg(fm).arg(path);
+                cmd.arg("-e").arg(fm).args(&self.extra_args).arg(path);
             }
         }
 
-        cmd.spawn()
-            .with_context(|| format!("Failed to open {} in terminal", fm))?;
+        Ok(cmd)
+    }
+
+    fn custom_command(&self, path: &Path, command: &str, _config: &AppConfig) -> Result<Command> {
+        build_app_command(path, command, &self.extra_args)
+    }
+}
+
+/// Build the command that opens `path` in an arbitrary application
+///
+/// `command` may carry its own arguments, e.g. `code --new-window`; they
+/// come first, then `extra_args`, then the path.
+pub fn build_app_command(path: &Path, command: &str, extra_args: &[String]) -> Result<Command> {
+    // Parse command string (may contain arguments)
+    let parts: Vec<&str> = command.split_whitespace().collect();
 
-        Ok(())
+    if parts.is_empty() {
+        anyhow::bail!("Empty custom command");
     }
 
-    fn open_custom(&self, path: &Path, command: &str, _config: &AppConfig) -> Result<()> {
-        // Parse command string (may contain arguments)
-        let parts: Vec<&str> = command.split_whitespace().collect();
-        
-        if parts.is_empty() {
-            anyhow::bail!("Empty custom command");
-        }
+    let mut cmd = Command::new(parts[0]);
+
+    // Add any arguments from the command string
+    if parts.len() > 1 {
+        cmd.args(&parts[1..]);
+    }
 
-        let mut cmd = Command::new(parts[0]);
-        
-        // Add any arguments from the command string
-        if parts.len() > 1 {
-            cmd.args(&parts[1..]);
+    cmd.args(extra_args).arg(path);
+
+    Ok(cmd)
+}
+
+/// Open `path` in an application such as an editor or IDE
+pub fn open_app(
+    path: &Path,
+    command: &str,
+    extra_args: &[String],
+    detached: bool,
+    config: &AppConfig,
+) -> Result<()> {
+    spawn(build_app_command(path, command, extra_args)?, detached, config)
+}
+
+/// Open a remote location such as `ssh://host/path`
+pub fn open_remote(uri: &str, detached: bool, config: &AppConfig) -> Result<()> {
+    spawn(build_remote_command(uri, config)?, detached, config)
+}
+
+/// Build the command that opens `uri`: `remote_opener` if set, else the
+/// system URI handler
+pub fn build_remote_command(uri: &str, config: &AppConfig) -> Result<Command> {
+    if let Some(opener) = &config.remote_opener {
+        let parts: Vec<&str> = opener.split_whitespace().collect();
+        let (program, args) = parts.split_first().context("remote_opener is empty")?;
+        let mut cmd = Command::new(program);
+        cmd.args(args).arg(uri);
+        return Ok(cmd);
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/c", "start", ""]).arg(uri);
+        Ok(cmd)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let mut cmd = Command::new("open");
+        cmd.arg(uri);
+        Ok(cmd)
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        // GNOME and KDE hand ssh:// and sftp:// to their file managers
+        let mut cmd = Command::new("xdg-open");
+        cmd.arg(uri);
+        Ok(cmd)
+    }
+}
+
+/// Open a new terminal window with `path` as its working directory
+pub fn open_terminal(path: &Path, detached: bool, config: &AppConfig) -> Result<()> {
+    spawn(build_terminal_command(path, config)?, detached, config)
+}
+
+/// Build the command that opens a terminal in `path`, without spawning it
+pub fn build_terminal_command(path: &Path, config: &AppConfig) -> Result<Command> {
+    #[cfg(target_os = "windows")]
+    {
+        // Open a tab in the most recent Windows Terminal window, which
+        // starts a new window when none is running
+        if config.platform.windows.use_windows_terminal && which::which("wt").is_ok() {
+            let mut cmd = Command::new("wt");
+            cmd.args(["-w", "0", "new-tab", "-d"]).arg(path);
+            return Ok(cmd);
         }
-        
-        cmd.arg(path);
-
-        cmd.spawn()
-            .with_context(|| format!("Failed to execute custom command: {}", command))?;
-
-        Ok(())
-    }
-
-    fn detect_terminal(&self) -> Result<String> {
-        let terminals = vec![
-            "Terminal.app",
-            "iTerm.app",
-            "gnome-terminal",
-            "konsole",
-            "xfce4-terminal",
-            "alacritty",
-            "kitty",
-            "wezterm",
-            "terminator",
-            "tilix",
-            "xterm",
-            "rxvt",
-            "urxvt",
-        ];
-
-        for term in terminals {
-            if which::which(term).is_ok() {
-                return Ok(term.to_string());
+
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/c", "start", "", "/D"]).arg(path).arg("cmd");
+        Ok(cmd)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let app = if config.platform.macos.prefer_iterm2 && Path::new("/Applications/iTerm.app").exists() {
+            "iTerm"
+        } else {
+            "Terminal"
+        };
+
+        let mut cmd = Command::new("open");
+        cmd.args(["-a", app]).arg(path);
+        Ok(cmd)
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        let terminal = detect_terminal(config)?;
+
+        let mut cmd = Command::new(&terminal);
+        cmd.current_dir(path);
+
+        // Terminals that hand off to a running server ignore the spawn
+        // directory, so pass it explicitly where supported
+        match terminal.as_str() {
+            "gnome-terminal" | "xfce4-terminal" | "terminator" | "tilix" => {
+                cmd.arg(format!("--working-directory={}", path.display()));
+            }
+            "konsole" => {
+                cmd.arg("--workdir").arg(path);
+            }
+            "alacritty" => {
+                cmd.arg("--working-directory").arg(path);
             }
+            "kitty" => {
+                cmd.arg("--directory").arg(path);
+            }
+            "wezterm" => {
+                cmd.args(["start", "--cwd"]).arg(path);
+            }
+            _ => {}
         }
 
-        // Check environment variables
-        if let Ok(term) = std::env::var("TERM") {
-            if term != "dumb" && which::which(&term).is_ok() {
-                return Ok(term);
+        Ok(cmd)
+    }
+}
+
+/// Render `cmd` as a single shell-quoted line, program first
+pub fn format_command(cmd: &Command) -> String {
+    std::iter::once(cmd.get_program())
+        .chain(cmd.get_args())
+        .map(|arg| shell_quote(&arg.to_string_lossy()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn shell_quote(arg: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "-_./:=,@%+".contains(c);
+    if !arg.is_empty() && arg.chars().all(safe) {
+        return arg.to_string();
+    }
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
+
+fn spawn(mut cmd: Command, detached: bool, config: &AppConfig) -> Result<()> {
+    if config.skip_for_dry_run(|| format!("run {:?}", cmd)) {
+        return Ok(());
+    }
+    if detached {
+        detach(&mut cmd);
+    }
+
+    let program = cmd.get_program().to_string_lossy().to_string();
+    cmd.spawn()
+        .with_context(|| format!("Failed to open with {}. Is it installed?", program))?;
+
+    Ok(())
+}
+
+/// Start `cmd` with no stdio and, on Unix, in a new session without a
+/// controlling terminal, so closing the terminal or killing navr does not
+/// take it down
+fn detach(cmd: &mut Command) {
+    cmd.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null());
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        // SAFETY: setsid is async-signal-safe and touches no memory of the
+        // parent, so it may run between fork and exec
+        unsafe {
+            cmd.pre_exec(|| {
+                if libc::setsid() == -1 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const DETACHED_PROCESS: u32 = 0x00000008;
+        const CREATE_NEW_PROCESS_GROUP: u32 = 0x00000200;
+        cmd.creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP);
+    }
+}
+
+/// Open `path` with its registered handler through `ShellExecuteW`
+///
+/// Unlike spawning explorer.exe, this works under replacement shells that
+/// register their own folder handler. No console window is created.
+#[cfg(target_os = "windows")]
+fn shell_execute_open(path: &Path, config: &AppConfig) -> Result<()> {
+    use windows::core::PCWSTR;
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::Shell::ShellExecuteW;
+    use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+
+    if config.skip_for_dry_run(|| format!("open {} with ShellExecuteW", path.display())) {
+        return Ok(());
+    }
+
+    let verb = to_wide(std::ffi::OsStr::new("open"));
+    let file = to_wide(path.as_os_str());
+    // SAFETY: both strings are NUL-terminated and outlive the call
+    let result = unsafe {
+        ShellExecuteW(
+            HWND(0),
+            PCWSTR(verb.as_ptr()),
+            PCWSTR(file.as_ptr()),
+            PCWSTR::null(),
+            PCWSTR::null(),
+            SW_SHOWNORMAL,
+        )
+    };
+
+    // Values of 32 and below are error codes
+    if result.0 <= 32 {
+        anyhow::bail!("ShellExecuteW could not open {} (error {})", path.display(), result.0);
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn shell_execute_open(path: &Path, _config: &AppConfig) -> Result<()> {
+    anyhow::bail!("Cannot open {}: ShellExecuteW is only available on Windows", path.display())
+}
+
+/// `s` as a NUL-terminated UTF-16 string for Win32 calls
+#[cfg(target_os = "windows")]
+fn to_wide(s: &std::ffi::OsStr) -> Vec<u16> {
+    use std::os::windows::ffi::OsStrExt;
+
+    s.encode_wide().chain(std::iter::once(0)).collect()
+}
+
+/// Run `cmd` to completion, failing with its exit code when it is nonzero
+fn run_and_wait(mut cmd: Command, config: &AppConfig) -> Result<()> {
+    if config.skip_for_dry_run(|| format!("run {:?} and wait for it", cmd)) {
+        return Ok(());
+    }
+
+    let program = cmd.get_program().to_string_lossy().to_string();
+    let child = cmd
+        .spawn()
+        .with_context(|| format!("Failed to open with {}. Is it installed?", program))?;
+
+    let timeout = config.behavior.spawn_timeout_secs;
+    let Some(status) = wait_with_timeout(child, Duration::from_secs(timeout))? else {
+        eprintln!(
+            "{} {} is still running after {}s and may be stuck; no longer waiting for it",
+            "⚠".if_supports_color(Stderr, |t| t.yellow()),
+            program,
+            timeout
+        );
+        return Ok(());
+    };
+
+    match status.code() {
+        _ if status.success() => Ok(()),
+        Some(code) => Err(NavrError::FileManagerExited { program, code }.into()),
+        None => anyhow::bail!("{} was terminated by a signal", program),
+    }
+}
+
+/// Wait for `child` on a monitoring thread, giving up after `timeout`
+///
+/// A zero timeout waits indefinitely. The child keeps running when the
+/// timeout expires.
+fn wait_with_timeout(mut child: Child, timeout: Duration) -> Result<Option<ExitStatus>> {
+    if timeout.is_zero() {
+        return Ok(Some(child.wait()?));
+    }
+
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || loop {
+        match child.try_wait() {
+            Ok(None) => thread::sleep(WAIT_POLL_INTERVAL),
+            result => {
+                let _ = tx.send(result);
+                return;
             }
         }
+    });
 
-        anyhow::bail!("No suitable terminal emulator found")
+    match rx.recv_timeout(timeout) {
+        Ok(result) => Ok(result?),
+        Err(_) => Ok(None),
     }
 }
 
-// /// Get the default file manager for the current platform
-// pub fn default_file_manager() -> &'static str {
-//     #[cfg(target_os = "windows")]
-//     {
-//         "explorer"
-//     }
+fn detect_terminal(config: &AppConfig) -> Result<String> {
+    // A configured terminal always wins
+    if let Some(term) = &config.platform.linux.terminal {
+        return Ok(term.clone());
+    }
 
-//     #[cfg(target_os = "macos")]
-//     {
-//         "open"
-//     }
+    let terminals = vec![
+        "Terminal.app",
+        "iTerm.app",
+        "gnome-terminal",
+        "konsole",
+        "xfce4-terminal",
+        "alacritty",
+        "kitty",
+        "wezterm",
+        "terminator",
+        "tilix",
+        "xterm",
+        "rxvt",
+        "urxvt",
+    ];
+
+    for term in terminals {
+        if which::which(term).is_ok() {
+            return Ok(term.to_string());
+        }
+    }
 
-//     #[cfg(target_os = "linux")]
-//     {
-//         "xdg-open"
-//     }
+    // Check environment variables
+    if let Ok(term) = std::env::var("TERM") {
+        if term != "dumb" && which::which(&term).is_ok() {
+            return Ok(term);
+        }
+    }
 
-//     #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
-//     {
-//         "xdg-open"
-//     }
-// }
+    anyhow::bail!("No suitable terminal emulator found")
+}
+
+/// Get the default file manager for the current platform
+pub fn default_file_manager() -> &'static str {
+    #[cfg(target_os = "windows")]
+    {
+        "explorer"
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        "open"
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        "xdg-open"
+    }
+}
+
+/// Try `preferred`, then the auto-detected file manager, then the platform default
+///
+/// Returns the file manager that succeeded. Fails only when every one of
+/// them does, listing each failure.
+pub fn open_with_fallback(
+    preferred: &str,
+    attempt: impl FnMut(&str) -> Result<()>,
+) -> Result<String> {
+    let mut candidates = vec![preferred.to_string()];
+    for fm in [detect_best_file_manager(), default_file_manager().to_string()] {
+        if !candidates.contains(&fm) {
+            candidates.push(fm);
+        }
+    }
+    try_in_order(&candidates, attempt)
+}
+
+fn try_in_order(
+    candidates: &[String],
+    mut attempt: impl FnMut(&str) -> Result<()>,
+) -> Result<String> {
+    let mut failures = Vec::new();
+    for fm in candidates {
+        tracing::debug!("Trying file manager '{}'", fm);
+        match attempt(fm) {
+            Ok(()) => return Ok(fm.clone()),
+            // It ran, so another file manager would not do better
+            Err(e) if matches!(e.downcast_ref(), Some(NavrError::FileManagerExited { .. })) => {
+                return Err(e);
+            }
+            Err(e) => {
+                tracing::warn!("Could not open with '{}': {:#}", fm, e);
+                failures.push(format!("{}: {:#}", fm, e));
+            }
+        }
+    }
+    Err(NavrError::FileManagerUnavailable(failures.join("; ")).into())
+}
 
 // /// Check if a file manager is available
 // pub fn is_file_manager_available(fm: &str) -> bool {
@@ -257,3 +680,195 @@ impl FileManager {
 //         .filter(|fm| is_file_manager_available(fm))
 //         .collect()
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn test_to_wide_is_nul_terminated() {
+        let path = Path::new(r"C:\Users\navr\文档");
+        let wide = to_wide(path.as_os_str());
+        assert_eq!(wide.last(), Some(&0));
+        assert!(!wide[..wide.len() - 1].contains(&0));
+        assert_eq!(String::from_utf16(&wide[..wide.len() - 1]).unwrap(), r"C:\Users\navr\文档");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_remote_opener_command() {
+        let mut config = AppConfig::default();
+        config.remote_opener = Some("nautilus --new-window".to_string());
+
+        let cmd = build_remote_command("sftp://host/srv", &config).unwrap();
+        assert_eq!(cmd.get_program(), "nautilus");
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args, ["--new-window", "sftp://host/srv"]);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_terminal_command_uses_configured_terminal() {
+        let mut config = AppConfig::default();
+        config.platform.linux.terminal = Some("kitty".to_string());
+
+        let dir = std::env::temp_dir();
+        let cmd = build_terminal_command(&dir, &config).unwrap();
+
+        assert_eq!(cmd.get_program(), "kitty");
+        assert_eq!(cmd.get_current_dir(), Some(dir.as_path()));
+        assert!(cmd.get_args().any(|a| a == dir.as_os_str()));
+    }
+
+    #[test]
+    fn test_extra_args_precede_path() {
+        let config = AppConfig::default();
+        let path = Path::new("/tmp/navr target");
+        let args = |cmd: Command| -> Vec<String> {
+            cmd.get_args().map(|a| a.to_string_lossy().into_owned()).collect()
+        };
+
+        let cmd = FileManager::new("nautilus")
+            .with_args(vec!["--no-desktop".to_string()])
+            .build_command(path, &config)
+            .unwrap();
+        assert_eq!(args(cmd), ["--new-window", "--no-desktop", "/tmp/navr target"]);
+
+        let cmd = FileManager::new("myfm -x")
+            .with_args(vec!["--select".to_string(), "2".to_string()])
+            .build_command(path, &config)
+            .unwrap();
+        assert_eq!(args(cmd), ["-x", "--select", "2", "/tmp/navr target"]);
+    }
+
+    #[test]
+    fn test_app_command_puts_path_last() {
+        let cmd = build_app_command(
+            Path::new("/srv/proj"),
+            "code --new-window",
+            &["--goto".to_string()],
+        )
+        .unwrap();
+        assert_eq!(cmd.get_program(), "code");
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args, ["--new-window", "--goto", "/srv/proj"]);
+
+        assert!(build_app_command(Path::new("/srv/proj"), "  ", &[]).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_detached_child_starts_its_own_session() {
+        let session_of = |pid: u32| -> u32 {
+            let output = Command::new("ps")
+                .args(["-o", "sid=", "-p", &pid.to_string()])
+                .output()
+                .unwrap();
+            String::from_utf8_lossy(&output.stdout).trim().parse().unwrap()
+        };
+
+        let mut cmd = Command::new("sleep");
+        cmd.arg("5");
+        detach(&mut cmd);
+        let mut child = cmd.spawn().unwrap();
+        let sid = session_of(child.id());
+        child.kill().unwrap();
+        child.wait().unwrap();
+
+        assert_eq!(sid, child.id());
+        assert_ne!(sid, session_of(std::process::id()));
+    }
+
+    #[test]
+    fn test_format_command_quotes_args() {
+        let mut cmd = Command::new("myfm");
+        cmd.args(["--title=a b", "it's", "", "/srv/plain-dir"]);
+        assert_eq!(
+            format_command(&cmd),
+            r"myfm '--title=a b' 'it'\''s' '' /srv/plain-dir"
+        );
+    }
+
+    #[test]
+    fn test_reuse_window_drops_new_window_flag() {
+        let config = AppConfig::default();
+        let path = Path::new("/tmp/navr");
+        let args = |fm: FileManager| -> Vec<String> {
+            let cmd = fm.build_command(path, &config).unwrap();
+            cmd.get_args().map(|a| a.to_string_lossy().into_owned()).collect()
+        };
+
+        assert_eq!(args(FileManager::new("nautilus")), ["--new-window", "/tmp/navr"]);
+        assert_eq!(args(FileManager::new("nautilus").with_reuse_window(true)), ["/tmp/navr"]);
+        assert_eq!(args(FileManager::new("nemo")), ["/tmp/navr"]);
+        assert_eq!(
+            args(FileManager::new("nemo").with_reuse_window(true)),
+            ["--existing-window", "/tmp/navr"]
+        );
+    }
+
+    #[test]
+    fn test_failed_spawn_falls_back() {
+        let candidates = ["dolphin", "nautilus", "xdg-open"].map(String::from).to_vec();
+        let mut attempted = Vec::new();
+
+        let used = try_in_order(&candidates, |fm| {
+            attempted.push(fm.to_string());
+            match fm {
+                "xdg-open" => Ok(()),
+                _ => anyhow::bail!("{} not found", fm),
+            }
+        })
+        .unwrap();
+        assert_eq!(used, "xdg-open");
+        assert_eq!(attempted, candidates);
+
+        let err = try_in_order(&candidates, |fm| anyhow::bail!("{} crashed", fm)).unwrap_err();
+        assert!(err.to_string().contains("dolphin: dolphin crashed"));
+        assert!(err.to_string().contains("xdg-open: xdg-open crashed"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_and_wait_propagates_exit_code() {
+        let config = AppConfig::default();
+        assert!(run_and_wait(Command::new("true"), &config).is_ok());
+
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "exit 7"]);
+        let err = run_and_wait(cmd, &config).unwrap_err();
+        assert_eq!(crate::error::exit_code(&err), 7);
+
+        // A manager that ran and failed is not retried with another one
+        let candidates = ["ranger", "xdg-open"].map(String::from).to_vec();
+        let mut attempted = 0;
+        let err = try_in_order(&candidates, |_| {
+            attempted += 1;
+            run_and_wait(Command::new("false"), &config)
+        })
+        .unwrap_err();
+        assert_eq!(attempted, 1);
+        assert_eq!(crate::error::exit_code(&err), 1);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_wait_gives_up_on_stuck_command() {
+        let child = Command::new("sleep").arg("5").spawn().unwrap();
+        let waited = wait_with_timeout(child, Duration::from_millis(200)).unwrap();
+        assert!(waited.is_none());
+
+        let child = Command::new("true").spawn().unwrap();
+        let waited = wait_with_timeout(child, Duration::from_secs(5)).unwrap();
+        assert!(waited.is_some_and(|status| status.success()));
+
+        let mut config = AppConfig::default();
+        config.behavior.spawn_timeout_secs = 1;
+        let mut cmd = Command::new("sleep");
+        cmd.arg("5");
+        let started = std::time::Instant::now();
+        assert!(run_and_wait(cmd, &config).is_ok());
+        assert!(started.elapsed() < Duration::from_secs(4));
+    }
+}
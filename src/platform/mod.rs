@@ -1,10 +1,15 @@
 //! Platform-specific implementations
 
+pub mod clipboard;
 pub mod file_manager;
+pub mod wsl;
 
 use anyhow::Result;
-use std::path::PathBuf;
+use owo_colors::{OwoColorize, Stream::Stderr};
+use std::io::{self, IsTerminal};
+use std::path::{Component, Path, PathBuf};
 
+use crate::config::BehaviorConfig;
 
 /// Get shell configuration path
 pub fn shell_config_path(shell: &str) -> Result<PathBuf> {
@@ -35,3 +40,309 @@ pub fn shell_config_path(shell: &str) -> Result<PathBuf> {
         _ => anyhow::bail!("Unsupported shell: {}", shell),
     }
 }
+
+/// Get the per-user directory the shell loads completion scripts from
+pub fn shell_completion_dir(shell: &str) -> Result<PathBuf> {
+    let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?;
+
+    match shell {
+        "bash" => Ok(home.join(".local").join("share").join("bash-completion").join("completions")),
+        // Not searched by default; the directory has to be added to $fpath
+        "zsh" => Ok(home.join(".zsh").join("completions")),
+        "fish" => {
+            let config_dir = dirs::config_dir()
+                .ok_or_else(|| anyhow::anyhow!("Could not find config directory"))?;
+            Ok(config_dir.join("fish").join("completions"))
+        }
+        _ => shell_script_dir(shell),
+    }
+}
+
+/// Get the directory for a standalone shell integration script
+///
+/// fish sources everything in `conf.d` on startup; other shells get the
+/// script next to their startup file, to be sourced from it.
+pub fn shell_init_dir(shell: &str) -> Result<PathBuf> {
+    match shell {
+        "fish" => {
+            let config_dir = dirs::config_dir()
+                .ok_or_else(|| anyhow::anyhow!("Could not find config directory"))?;
+            Ok(config_dir.join("fish").join("conf.d"))
+        }
+        _ => shell_script_dir(shell),
+    }
+}
+
+/// The directory holding the shell's startup file
+fn shell_script_dir(shell: &str) -> Result<PathBuf> {
+    let config_path = shell_config_path(shell)?;
+    config_path
+        .parent()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| anyhow::anyhow!("{} has no parent directory", config_path.display()))
+}
+
+/// Create a directory and any missing parents with the configured mode
+///
+/// The mode only applies on Unix; elsewhere it is ignored. When permission
+/// is denied, `allow_privileged_mkdir` is set and navr runs interactively,
+/// the user is offered a retry through `sudo` or `doas`.
+pub fn create_dir_all(path: &Path, behavior: &BehaviorConfig) -> io::Result<()> {
+    create_or_escalate(
+        path,
+        behavior,
+        io::stdin().is_terminal(),
+        |path| create_dir_with_mode(path, behavior),
+        |path| privileged_mkdir(path, behavior),
+    )
+}
+
+/// Run `create`, falling back to `escalate` on a permission error when allowed
+///
+/// `escalate` returns `Ok(false)` when the user declines, which reports the
+/// original error.
+fn create_or_escalate(
+    path: &Path,
+    behavior: &BehaviorConfig,
+    interactive: bool,
+    create: impl FnOnce(&Path) -> io::Result<()>,
+    escalate: impl FnOnce(&Path) -> io::Result<bool>,
+) -> io::Result<()> {
+    let denied = match create(path) {
+        Err(e) if e.kind() == io::ErrorKind::PermissionDenied => e,
+        result => return result,
+    };
+
+    if behavior.allow_privileged_mkdir && interactive && escalate(path)? {
+        return Ok(());
+    }
+    Err(denied)
+}
+
+/// Ask to create `path` with `sudo mkdir -p` (or `doas`), returning whether it ran
+#[cfg(unix)]
+fn privileged_mkdir(path: &Path, behavior: &BehaviorConfig) -> io::Result<bool> {
+    let Some(tool) = ["sudo", "doas"].into_iter().find(|tool| which::which(tool).is_ok()) else {
+        return Ok(false);
+    };
+
+    let question = format!("Permission denied creating {}. Retry with {}?", path.display(), tool);
+    if !inquire::Confirm::new(&question).with_default(false).prompt().unwrap_or(false) {
+        return Ok(false);
+    }
+
+    let mut cmd = std::process::Command::new(tool);
+    cmd.args(["mkdir", "-p"]);
+    if let Some(mode) = behavior.create_missing_mode {
+        cmd.arg("-m").arg(format!("{:o}", mode));
+    }
+    let status = cmd.arg("--").arg(path).status()?;
+    if !status.success() {
+        return Err(io::Error::other(format!("{} mkdir failed ({})", tool, status)));
+    }
+    Ok(true)
+}
+
+#[cfg(not(unix))]
+fn privileged_mkdir(_path: &Path, _behavior: &BehaviorConfig) -> io::Result<bool> {
+    Ok(false)
+}
+
+fn create_dir_with_mode(path: &Path, behavior: &BehaviorConfig) -> io::Result<()> {
+    let mut builder = std::fs::DirBuilder::new();
+    builder.recursive(true);
+
+    if let Some(mode) = behavior.create_missing_mode {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::DirBuilderExt;
+            builder.mode(mode);
+        }
+        #[cfg(not(unix))]
+        tracing::debug!("Ignoring create_missing_mode {:o} on this platform", mode);
+    }
+
+    builder.create(path)
+}
+
+/// Check whether a shortcut target exists
+///
+/// When symlinks are not followed, a dangling link still counts as present.
+pub fn path_exists(path: &Path, follow_symlinks: bool) -> bool {
+    if follow_symlinks {
+        path.exists()
+    } else {
+        std::fs::symlink_metadata(path).is_ok()
+    }
+}
+
+/// Make `path` absolute, resolving symlinks only when `follow_symlinks` is set
+///
+/// Without following, `.` and `..` are removed lexically so a symlinked
+/// directory stays under the name it was reached by.
+pub fn normalize_path(path: &Path, follow_symlinks: bool) -> PathBuf {
+    if follow_symlinks && let Ok(canonical) = std::fs::canonicalize(path) {
+        return canonical;
+    }
+
+    let absolute = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut normalized = PathBuf::new();
+    for component in absolute.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// If `path` is a symlink pointing at a regular file, return the resolved file
+pub fn symlink_file_target(path: &Path) -> Option<PathBuf> {
+    let is_symlink = std::fs::symlink_metadata(path)
+        .map(|m| m.file_type().is_symlink())
+        .unwrap_or(false);
+
+    if is_symlink && path.is_file() {
+        std::fs::canonicalize(path).ok()
+    } else {
+        None
+    }
+}
+
+/// Resolve a symlink-to-file into the directory that should be used instead
+///
+/// Returns `Ok(None)` when `path` is not a symlink to a file. Whether the
+/// link is followed at all is governed by `follow_symlinks`, and using the
+/// file's parent directory requires `allow_file_shortcuts`.
+pub fn resolve_symlinked_file(path: &Path, behavior: &BehaviorConfig) -> Result<Option<PathBuf>> {
+    let Some(file) = symlink_file_target(path) else {
+        return Ok(None);
+    };
+
+    if !behavior.follow_symlinks {
+        anyhow::bail!(
+            "'{}' is a symlink to a file ({}) and behavior.follow_symlinks is disabled",
+            path.display(),
+            file.display()
+        );
+    }
+
+    if !behavior.allow_file_shortcuts {
+        anyhow::bail!(
+            "'{}' is a symlink to a file ({}), not a directory. \
+             Set behavior.allow_file_shortcuts to use its parent directory",
+            path.display(),
+            file.display()
+        );
+    }
+
+    let parent = file
+        .parent()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| anyhow::anyhow!("'{}' has no parent directory", file.display()))?;
+
+    eprintln!(
+        "{} '{}' is a symlink to file {}; using {}",
+        "ℹ".if_supports_color(Stderr, |t| t.blue()),
+        path.display(),
+        file.display().to_string().if_supports_color(Stderr, |t| t.dimmed()),
+        parent.display().to_string().if_supports_color(Stderr, |t| t.cyan())
+    );
+
+    Ok(Some(parent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_privileged_mkdir_requires_opt_in() {
+        let denied = |_: &Path| Err(io::Error::from(io::ErrorKind::PermissionDenied));
+        let path = Path::new("/root-owned/new");
+        let mut behavior = BehaviorConfig::default();
+
+        let never = |_: &Path| -> io::Result<bool> { panic!("escalated without being allowed") };
+        let err = create_or_escalate(path, &behavior, true, denied, never).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+
+        behavior.allow_privileged_mkdir = true;
+        assert!(create_or_escalate(path, &behavior, false, denied, never).is_err());
+        assert!(create_or_escalate(path, &behavior, true, denied, |_| Ok(true)).is_ok());
+        assert!(create_or_escalate(path, &behavior, true, denied, |_| Ok(false)).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_symlinked_file() {
+        let dir = std::env::temp_dir().join(format!("navr-symlink-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("notes.txt");
+        let link = dir.join("notes-link");
+        std::fs::write(&file, "notes").unwrap();
+        let _ = std::fs::remove_file(&link);
+        std::os::unix::fs::symlink(&file, &link).unwrap();
+
+        let mut behavior = BehaviorConfig {
+            follow_symlinks: true,
+            ..Default::default()
+        };
+
+        // Plain directories and files are not affected
+        assert!(resolve_symlinked_file(&dir, &behavior).unwrap().is_none());
+        assert!(resolve_symlinked_file(&file, &behavior).unwrap().is_none());
+
+        // Refused unless file shortcuts are allowed
+        assert!(resolve_symlinked_file(&link, &behavior).is_err());
+
+        behavior.allow_file_shortcuts = true;
+        let parent = resolve_symlinked_file(&link, &behavior).unwrap().unwrap();
+        assert_eq!(parent, std::fs::canonicalize(&dir).unwrap());
+
+        behavior.follow_symlinks = false;
+        assert!(resolve_symlinked_file(&link, &behavior).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_normalize_path_symlinks() {
+        let dir = std::env::temp_dir().join(format!("navr-normalize-{}", std::process::id()));
+        let real = dir.join("real");
+        let link = dir.join("link");
+        std::fs::create_dir_all(real.join("sub")).unwrap();
+        let _ = std::fs::remove_file(&link);
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+        let dir = std::fs::canonicalize(&dir).unwrap();
+        let link = dir.join("link");
+
+        assert_eq!(normalize_path(&link, true), dir.join("real"));
+        assert_eq!(normalize_path(&link, false), link);
+        assert_eq!(normalize_path(&link.join("sub/./.."), false), link);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_create_dir_all_applies_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let root = std::env::temp_dir().join(format!("navr-mode-{}", std::process::id()));
+        let dir = root.join("private").join("project");
+        let behavior = BehaviorConfig {
+            create_missing_mode: Some(0o700),
+            ..Default::default()
+        };
+
+        create_dir_all(&dir, &behavior).unwrap();
+        let mode = std::fs::metadata(&dir).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,94 @@
+//! Opening paths from inside WSL with the Windows file manager
+//!
+//! Linux file managers are rarely installed in WSL, and Windows programs
+//! cannot read `/mnt/c/...` paths, so paths are translated first.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::Command;
+
+use crate::config::{AppConfig, FileManagerSource};
+
+/// Whether navr is running inside the Windows Subsystem for Linux
+pub fn is_wsl() -> bool {
+    cfg!(target_os = "linux")
+        && std::fs::read_to_string("/proc/version")
+            .is_ok_and(|version| version.to_lowercase().contains("microsoft"))
+}
+
+/// Whether `open` should hand paths to Windows Explorer
+///
+/// Only when `platform.linux.wsl_interop` is on and no file manager is configured.
+pub fn use_explorer(config: &AppConfig) -> bool {
+    config.platform.linux.wsl_interop
+        && matches!(config.resolve_file_manager(), Ok(FileManagerSource::Detected(_)))
+        && is_wsl()
+}
+
+/// Open `path` in Windows Explorer, or select it there when `reveal` is set
+pub fn open_in_explorer(path: &Path, reveal: bool, config: &AppConfig) -> Result<()> {
+    let mut cmd = explorer_command(path, reveal);
+    if config.skip_for_dry_run(|| format!("run {:?}", cmd)) {
+        return Ok(());
+    }
+    cmd.spawn()
+        .context("Failed to run explorer.exe. Is WSL interop enabled?")?;
+    Ok(())
+}
+
+/// Build the `explorer.exe` command for `path`, without spawning it
+pub fn explorer_command(path: &Path, reveal: bool) -> Command {
+    let target = windows_path(path);
+    let mut cmd = Command::new("explorer.exe");
+    if reveal {
+        cmd.arg(format!("/select,{}", target));
+    } else {
+        cmd.arg(target);
+    }
+    cmd
+}
+
+/// The Windows form of a WSL path, from `wslpath -w` when it is available
+pub fn windows_path(path: &Path) -> String {
+    match Command::new("wslpath").arg("-w").arg(path).output() {
+        Ok(output) if output.status.success() => {
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        }
+        _ => {
+            let distro = std::env::var("WSL_DISTRO_NAME").unwrap_or_else(|_| "Ubuntu".to_string());
+            translate_path(&path.to_string_lossy(), &distro)
+        }
+    }
+}
+
+/// `/mnt/c/Users` becomes `C:\Users`; paths inside the distribution use the
+/// `\\wsl$\<distro>\` share
+fn translate_path(path: &str, distro: &str) -> String {
+    if let Some(rest) = path.strip_prefix("/mnt/") {
+        let (drive, rest) = rest.split_once('/').unwrap_or((rest, ""));
+        if drive.len() == 1 && drive.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            return format!("{}:\\{}", drive.to_ascii_uppercase(), rest.replace('/', "\\"));
+        }
+    }
+    format!("\\\\wsl$\\{}{}", distro, path.replace('/', "\\"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translate_path() {
+        assert_eq!(
+            translate_path("/mnt/c/Users/me/Documents", "Ubuntu"),
+            "C:\\Users\\me\\Documents"
+        );
+        assert_eq!(translate_path("/mnt/d", "Ubuntu"), "D:\\");
+        assert_eq!(translate_path("/home/me/code", "Debian"), "\\\\wsl$\\Debian\\home\\me\\code");
+        // Only single-letter directories under /mnt are drives
+        assert_eq!(
+            translate_path("/mnt/wsl/shared", "Ubuntu"),
+            "\\\\wsl$\\Ubuntu\\mnt\\wsl\\shared"
+        );
+    }
+}
@@ -0,0 +1,139 @@
+//! Session-only shortcuts added with `jump --add --temp`
+//!
+//! Each shell session gets its own file under `<data dir>/sessions`, named
+//! after the `NAVR_SESSION` id the integration script exports. Lines are
+//! `<name>\t<path>`. Files untouched for a day are removed the next time a
+//! session shortcut is added.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::config::paths::data_dir;
+use crate::lock::with_lock;
+
+/// Environment variable holding the current shell session id
+pub const SESSION_ENV: &str = "NAVR_SESSION";
+
+/// Session files older than this belong to shells that have ended
+const STALE_AFTER: Duration = Duration::from_secs(24 * 60 * 60);
+
+fn sessions_dir() -> Result<PathBuf> {
+    Ok(data_dir()?.join("sessions"))
+}
+
+/// The current session id, if the shell integration set a usable one
+fn session_id() -> Option<String> {
+    std::env::var(SESSION_ENV)
+        .ok()
+        .filter(|id| !id.is_empty())
+        .filter(|id| id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+}
+
+fn session_file() -> Result<Option<PathBuf>> {
+    match session_id() {
+        Some(id) => Ok(Some(sessions_dir()?.join(format!("{}.txt", id)))),
+        None => Ok(None),
+    }
+}
+
+/// Shortcuts added to the current session, keyed by name
+pub fn read_shortcuts() -> Result<HashMap<String, String>> {
+    match session_file()? {
+        Some(file) => read_file(&file),
+        None => Ok(HashMap::new()),
+    }
+}
+
+/// Add or replace a shortcut in the current session
+pub fn add_shortcut(name: &str, path: &str) -> Result<()> {
+    let file = session_file()?.with_context(|| {
+        format!("{} is not set; --temp needs the navr shell integration", SESSION_ENV)
+    })?;
+    if let Some(dir) = file.parent() {
+        std::fs::create_dir_all(dir)?;
+        if let Err(e) = remove_stale(dir, &file, SystemTime::now()) {
+            tracing::debug!("Failed to clean up old session files: {}", e);
+        }
+    }
+
+    with_lock(&file, || {
+        let mut shortcuts = read_file(&file)?;
+        shortcuts.insert(name.to_string(), path.to_string());
+        write_file(&file, &shortcuts)
+    })
+}
+
+fn read_file(file: &Path) -> Result<HashMap<String, String>> {
+    if !file.exists() {
+        return Ok(HashMap::new());
+    }
+    let content = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {:?}", file))?;
+    Ok(content
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(name, path)| (name.to_string(), path.to_string()))
+        .collect())
+}
+
+fn write_file(file: &Path, shortcuts: &HashMap<String, String>) -> Result<()> {
+    let mut names: Vec<&String> = shortcuts.keys().collect();
+    names.sort();
+    let content: String = names
+        .into_iter()
+        .map(|name| format!("{}\t{}\n", name, shortcuts[name]))
+        .collect();
+    std::fs::write(file, content).with_context(|| format!("Failed to write {:?}", file))
+}
+
+/// Delete session files and their locks in `dir` not modified within
+/// `STALE_AFTER` of `now`, except those of `current`
+fn remove_stale(dir: &Path, current: &Path, now: SystemTime) -> Result<usize> {
+    let current = current.file_name().unwrap_or_default().to_string_lossy();
+    let mut removed = 0;
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if name == current || name == format!(".{}.lock", current) {
+            continue;
+        }
+        let modified = std::fs::metadata(&path)?.modified()?;
+        if now.duration_since(modified).is_ok_and(|age| age > STALE_AFTER) {
+            std::fs::remove_file(&path)?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_file_round_trip_and_cleanup() {
+        let dir = std::env::temp_dir().join(format!("navr-sessions-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let current = dir.join("101-7.txt");
+        let old = dir.join("99-3.txt");
+
+        let shortcuts = HashMap::from([
+            ("tmp".to_string(), "/tmp/build".to_string()),
+            ("logs".to_string(), "/var/log/my app".to_string()),
+        ]);
+        write_file(&current, &shortcuts).unwrap();
+        write_file(&old, &shortcuts).unwrap();
+        assert_eq!(read_file(&current).unwrap(), shortcuts);
+        assert!(read_file(&dir.join("missing.txt")).unwrap().is_empty());
+
+        // A day later only the other session's file goes
+        let later = SystemTime::now() + STALE_AFTER + Duration::from_secs(60);
+        assert_eq!(remove_stale(&dir, &current, SystemTime::now()).unwrap(), 0);
+        assert_eq!(remove_stale(&dir, &current, later).unwrap(), 1);
+        assert!(current.exists() && !old.exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
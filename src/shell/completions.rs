@@ -0,0 +1,67 @@
+//! Shortcut-aware additions to the clap-generated completion scripts
+//!
+//! Each snippet is appended after the static script; for zsh it replaces
+//! the trailing dispatch block instead. It completes shortcut names for
+//! `navr jump` and `navr open` through `navr _complete shortcuts`, which
+//! honors `shell.completion_style`, and defers to the static completions
+//! everywhere else.
+
+/// First line of the dispatch block ending clap's zsh script
+pub const ZSH_DISPATCH: &str = r#"if [ "$funcstack[1]" = "_navr" ]; then"#;
+
+/// Bash: wrap the clap `_navr` function
+pub fn generate_bash_dynamic_completion() -> &'static str {
+    r#"
+# Shortcut names for navr jump/open, clap completions otherwise
+_navr_dynamic() {
+    local cur="${COMP_WORDS[COMP_CWORD]}"
+    if [[ $COMP_CWORD -eq 2 && "$cur" != -* ]]; then
+        case "${COMP_WORDS[1]}" in
+            jump|j|open|o)
+                COMPREPLY=($(navr _complete shortcuts "$cur" 2>/dev/null) $(compgen -d -- "$cur"))
+                return 0
+                ;;
+        esac
+    fi
+    _navr "$@"
+}
+complete -F _navr_dynamic -o bashdefault -o default navr
+"#
+}
+
+/// Zsh: wrap the clap `_navr` function
+///
+/// When the script is autoloaded as `_navr`, the first completion runs the
+/// file itself, so the wrapper has to be defined and dispatched to there.
+pub fn generate_zsh_dynamic_completion() -> &'static str {
+    r#"
+# Shortcut names for navr jump/open, clap completions otherwise
+_navr_dynamic() {
+    if (( CURRENT == 3 )) && [[ "$PREFIX" != -* ]]; then
+        case "${words[2]}" in
+            jump|j|open|o)
+                local -a shortcuts
+                shortcuts=(${(f)"$(navr _complete shortcuts "$PREFIX" 2>/dev/null)"})
+                compadd -U -a shortcuts
+                _path_files -/
+                return
+                ;;
+        esac
+    fi
+    _navr "$@"
+}
+
+compdef _navr_dynamic navr
+if [ "$funcstack[1]" = "_navr" ]; then
+    _navr_dynamic "$@"
+fi
+"#
+}
+
+/// Fish: add shortcut candidates next to the clap completions
+pub fn generate_fish_dynamic_completion() -> &'static str {
+    r#"
+# Shortcut names for navr jump/open
+complete -c navr -n "__fish_seen_subcommand_from jump j open o" -a "(navr _complete shortcuts (commandline -ct) 2>/dev/null)" -d 'Shortcut'
+"#
+}
@@ -1,27 +1,69 @@
 //! Shell integration scripts
 
+/// First line of every integration script, used to detect an installation
+pub const INTEGRATION_MARKER: &str = "# Navr Shell Integration";
+
+/// Lines delimiting the block `shell install` adds to a startup file
+pub const BLOCK_START: &str = "# >>> navr initialize >>>";
+pub const BLOCK_END: &str = "# <<< navr initialize <<<";
+
 /// Bash integration script
 pub const BASH_INTEGRATION: &str = r#"
 # Navr Shell Integration
 # Generated by navr shell install bash
 
+# Mark the integration as active for `navr doctor`
+export NAVR_ACTIVE=1
+
+# Identify this shell session for `navr jump --add --temp`
+export NAVR_SESSION="$$-$RANDOM"
+
 # Enable completion
 if command -v navr &> /dev/null; then
     eval "$(navr shell complete bash)"
 fi
 
+# Ask which of the matches navr listed after NAVR_JUMP_MENU:<count> to use
+# (one "name<TAB>path" line each) and print its path
+_navr_menu() {
+    local -a names paths
+    local name path choice i
+    while IFS=$'\t' read -r name path; do
+        names+=("$name")
+        paths+=("$path")
+    done < <(printf '%s\n' "$1" | tail -n +2)
+    for i in "${!names[@]}"; do
+        printf '%d) %s  %s\n' $((i + 1)) "${names[$i]}" "${paths[$i]}" >&2
+    done
+    read -r -p "Jump to [1-${#names[@]}]: " choice
+    [[ "$choice" =~ ^[0-9]+$ ]] && (( choice >= 1 && choice <= ${#names[@]} )) || return 1
+    printf '%s\n' "${paths[$((choice - 1))]}"
+}
+
 # Navr cd wrapper function
 qn_cd() {
     local target="$1"
     
     if [[ -z "$target" ]]; then
         builtin cd ~
+    elif [[ "$target" == "-" ]]; then
+        # Return to the directory navr saw last, or the shell's OLDPWD
+        local previous
+        previous=$(navr jump --no-track - 2>/dev/null)
+        if [[ -n "$previous" ]]; then
+            builtin cd "$previous"
+        else
+            builtin cd -
+        fi
     elif [[ -d "$target" ]]; then
         builtin cd "$target"
     else
         # Try to resolve via navr
         local resolved
-        resolved=$(navr jump "$target" 2>/dev/null)
+        resolved=$(navr jump --no-track --menu "$target" 2>/dev/null)
+        if [[ "$resolved" == NAVR_JUMP_MENU:* ]]; then
+            resolved=$(_navr_menu "$resolved") || return 1
+        fi
         if [[ -n "$resolved" ]]; then
             # Check if output has NAVR_JUMP prefix
             if [[ "$resolved" =~ ^NAVR_JUMP:(.+)$ ]]; then
@@ -35,9 +77,11 @@ qn_cd() {
     fi
 }
 
-# Override cd command
+# Override cd command, recording the new directory in navr history in the background
 cd() {
-    qn_cd "$@"
+    qn_cd "$@" || return
+    ( navr history add --from "$OLDPWD" -- "$PWD" &> /dev/null & )
+    return 0
 }
 
 # Navr aliases
@@ -54,12 +98,44 @@ jcd() {
 # Tab completion for navr
 _navr_jump_complete() {
     local cur="${COMP_WORDS[COMP_CWORD]}"
-    local shortcuts
-    shortcuts=$(navr jump --list 2>/dev/null | awk '/^  [a-zA-Z]/ {print $1}')
-    COMPREPLY=($(compgen -W "$shortcuts" -- "$cur"))
+    # navr filters by shell.completion_style, so no compgen filtering here
+    COMPREPLY=($(navr _complete shortcuts "$cur" 2>/dev/null))
+}
+
+# Config key/value completion for navr config set/get
+_navr_config_complete() {
+    local cur="${COMP_WORDS[COMP_CWORD]}"
+    local candidates
+    if [[ $COMP_CWORD -eq 3 ]]; then
+        candidates=$(navr _complete config-keys 2>/dev/null)
+    elif [[ $COMP_CWORD -eq 4 && "${COMP_WORDS[2]}" == "set" ]]; then
+        candidates=$(navr _complete config-values "${COMP_WORDS[3]}" 2>/dev/null)
+    fi
+    COMPREPLY=($(compgen -W "$candidates" -- "$cur"))
+}
+
+# Dispatch navr completion by subcommand
+_navr_dispatch_complete() {
+    case "${COMP_WORDS[1]}" in
+        jump|j)
+            _navr_jump_complete
+            ;;
+        config|cfg)
+            if [[ "${COMP_WORDS[2]}" == "set" || "${COMP_WORDS[2]}" == "get" ]] && [[ $COMP_CWORD -ge 3 ]]; then
+                _navr_config_complete
+            elif declare -F _navr &> /dev/null; then
+                _navr "$@"
+            fi
+            ;;
+        *)
+            if declare -F _navr &> /dev/null; then
+                _navr "$@"
+            fi
+            ;;
+    esac
 }
 
-complete -F _navr_jump_complete navr jump
+complete -F _navr_dispatch_complete navr
 complete -F _navr_jump_complete j
 "#;
 
@@ -68,23 +144,55 @@ pub const ZSH_INTEGRATION: &str = r#"
 # Navr Shell Integration
 # Generated by navr shell install zsh
 
+# Mark the integration as active for `navr doctor`
+export NAVR_ACTIVE=1
+
+# Identify this shell session for `navr jump --add --temp`
+export NAVR_SESSION="$$-$RANDOM"
+
 # Enable completion
 if (( $+commands[navr] )); then
     eval "$(navr shell complete zsh)"
 fi
 
+# Ask which of the matches navr listed after NAVR_JUMP_MENU:<count> to use
+# (one "name<TAB>path" line each) and print its path
+_navr_menu() {
+    local -a entries
+    local choice i
+    entries=(${(f)"$(print -r -- "$1" | tail -n +2)"})
+    for (( i = 1; i <= ${#entries}; i++ )); do
+        print -r -u2 -- "$i) ${entries[$i]%%$'\t'*}  ${entries[$i]#*$'\t'}"
+    done
+    read -r "choice?Jump to [1-${#entries}]: "
+    [[ "$choice" == <-> ]] && (( choice >= 1 && choice <= ${#entries} )) || return 1
+    print -r -- "${entries[$choice]#*$'\t'}"
+}
+
 # Navr cd wrapper function
 qn_cd() {
     local target="$1"
     
     if [[ -z "$target" ]]; then
         builtin cd ~
+    elif [[ "$target" == "-" ]]; then
+        # Return to the directory navr saw last, or the shell's OLDPWD
+        local previous
+        previous=$(navr jump --no-track - 2>/dev/null)
+        if [[ -n "$previous" ]]; then
+            builtin cd "$previous"
+        else
+            builtin cd -
+        fi
     elif [[ -d "$target" ]]; then
         builtin cd "$target"
     else
         # Try to resolve via navr
         local resolved
-        resolved=$(navr jump "$target" 2>/dev/null)
+        resolved=$(navr jump --no-track --menu "$target" 2>/dev/null)
+        if [[ "$resolved" == NAVR_JUMP_MENU:* ]]; then
+            resolved=$(_navr_menu "$resolved") || return 1
+        fi
         if [[ -n "$resolved" ]]; then
             # Check if output has NAVR_JUMP prefix
             if [[ "$resolved" =~ ^NAVR_JUMP:(.+)$ ]]; then
@@ -103,6 +211,13 @@ cd() {
     qn_cd "$@"
 }
 
+# Record directory changes in navr history in the background
+_navr_chpwd_hook() {
+    navr history add --from "$OLDPWD" -- "$PWD" &> /dev/null &!
+}
+autoload -Uz add-zsh-hook
+add-zsh-hook chpwd _navr_chpwd_hook
+
 # Navr aliases
 alias j='navr jump'
 alias jo='navr open'
@@ -117,13 +232,45 @@ jcd() {
 # Zsh completion function
 _navr_complete() {
     local -a shortcuts
-    local lines
-    lines=(${(f)"$(navr jump --list 2>/dev/null | awk '/^  [a-zA-Z]/ {print $1}')"})
-    shortcuts=($lines)
-    _describe -t shortcuts 'shortcut' shortcuts
+    # navr filters by shell.completion_style, so skip zsh's own matching
+    shortcuts=(${(f)"$(navr _complete shortcuts "$PREFIX" 2>/dev/null)"})
+    compadd -U -- $shortcuts
 }
 
-compdef _navr_complete navr jump
+# Config key/value completion for navr config set/get
+_navr_config_complete() {
+    local -a candidates
+    if (( CURRENT == 4 )); then
+        candidates=(${(f)"$(navr _complete config-keys 2>/dev/null)"})
+        _describe -t keys 'config key' candidates
+    elif (( CURRENT == 5 )) && [[ "${words[3]}" == set ]]; then
+        candidates=(${(f)"$(navr _complete config-values "${words[4]}" 2>/dev/null)"})
+        _describe -t values 'value' candidates
+    fi
+}
+
+# Dispatch navr completion by subcommand
+_navr_dispatch_complete() {
+    case "${words[2]}" in
+        jump|j)
+            _navr_complete
+            ;;
+        config|cfg)
+            if [[ "${words[3]}" == (set|get) ]] && (( CURRENT >= 4 )); then
+                _navr_config_complete
+            elif (( $+functions[_navr] )); then
+                _navr "$@"
+            fi
+            ;;
+        *)
+            if (( $+functions[_navr] )); then
+                _navr "$@"
+            fi
+            ;;
+    esac
+}
+
+compdef _navr_dispatch_complete navr
 compdef _navr_complete j
 "#;
 
@@ -132,22 +279,52 @@ pub const FISH_INTEGRATION: &str = r#"
 # Navr Shell Integration
 # Generated by navr shell install fish
 
+# Mark the integration as active for `navr doctor`
+set -gx NAVR_ACTIVE 1
+
+# Identify this shell session for `navr jump --add --temp`
+set -gx NAVR_SESSION "$fish_pid-"(random)
+
 # Enable completion
 if command -sq navr
     navr shell complete fish | source
 end
 
+# Ask which of the matches navr listed after NAVR_JUMP_MENU:<count> to use
+# (one "name<TAB>path" line each) and print its path
+function __navr_menu
+    for i in (seq (count $argv))
+        set -l parts (string split -m 1 \t -- $argv[$i])
+        printf '%d) %s  %s\n' $i $parts[1] $parts[2] >&2
+    end
+    read -l -P "Jump to [1-"(count $argv)"]: " choice
+    string match -qr '^[0-9]+$' -- "$choice"; or return 1
+    test $choice -ge 1 -a $choice -le (count $argv); or return 1
+    string split -m 1 \t -- $argv[$choice] | tail -n 1
+end
+
 # Navr cd wrapper function
 function qn_cd
     set -l target $argv[1]
     
     if test -z "$target"
         builtin cd ~
+    else if test "$target" = "-"
+        # Return to the directory navr saw last, or fish's previous directory
+        set -l previous (navr jump --no-track - 2>/dev/null)
+        if test -n "$previous"
+            builtin cd "$previous"
+        else
+            builtin cd -
+        end
     else if test -d "$target"
         builtin cd "$target"
     else
         # Try to resolve via navr
-        set -l resolved (navr jump "$target" 2>/dev/null)
+        set -l resolved (navr jump --no-track --menu "$target" 2>/dev/null)
+        if string match -q 'NAVR_JUMP_MENU:*' -- "$resolved[1]"
+            set resolved (__navr_menu $resolved[2..-1]); or return 1
+        end
         if test -n "$resolved"
             # Check if output has NAVR_JUMP prefix
             if string match -q 'NAVR_JUMP:*' "$resolved"
@@ -168,6 +345,11 @@ function cd
     qn_cd $argv
 end
 
+# Record directory changes in navr history in the background
+function __navr_pwd_hook --on-variable PWD
+    navr history add --from "$dirprev[-1]" -- "$PWD" &> /dev/null & disown
+end
+
 # Navr aliases
 alias j 'navr jump'
 alias jo 'navr open'
@@ -189,12 +371,25 @@ complete -c navr -n '__fish_use_subcommand' -a 'export' -d 'Export configuration
 complete -c navr -n '__fish_use_subcommand' -a 'import' -d 'Import configuration'
 
 # Dynamic shortcut completion
-complete -c navr -n '__fish_seen_subcommand_from jump' -a '(navr jump --list 2>/dev/null | string match -r "^  (\w+)" | string replace -r "^  " "")'
-complete -c j -a '(navr jump --list 2>/dev/null | string match -r "^  (\w+)" | string replace -r "^  " "")'
+complete -c navr -n '__fish_seen_subcommand_from jump' -a '(navr _complete shortcuts (commandline -ct) 2>/dev/null)'
+complete -c j -a '(navr _complete shortcuts (commandline -ct) 2>/dev/null)'
+
+# Config key/value completion for navr config set/get
+complete -c navr -n '__fish_seen_subcommand_from config cfg; and __fish_seen_subcommand_from set get; and test (count (commandline -opc)) -eq 3' -a '(navr _complete config-keys 2>/dev/null)'
+complete -c navr -n '__fish_seen_subcommand_from config cfg; and __fish_seen_subcommand_from set; and test (count (commandline -opc)) -eq 4' -a '(navr _complete config-values (commandline -opc)[4] 2>/dev/null)'
 "#;
 
 /// PowerShell integration script
 pub const POWERSHELL_INTEGRATION: &str = r#"
+# Navr Shell Integration
+# Generated by navr shell install powershell
+
+# Mark the integration as active for `navr doctor`
+$env:NAVR_ACTIVE = "1"
+
+# Identify this shell session for `navr jump --add --temp`
+$env:NAVR_SESSION = "$PID-$(Get-Random)"
+
 # =============================================================================
 #
 # Utility functions for navr.
@@ -248,7 +443,11 @@ function global:__navr_hook {
     $result = __navr_pwd
     if ($result -ne $global:__navr_oldpwd) {
         if ($null -ne $result) {
-            navr add "--" $result
+            if ($null -ne $global:__navr_oldpwd) {
+                navr history add "--from" $global:__navr_oldpwd "--" $result
+            } else {
+                navr history add "--" $result
+            }
         }
         $global:__navr_oldpwd = $result
     }
@@ -290,10 +489,10 @@ function global:__navr_jump {
     else {
         $result = __navr_pwd
         if ($null -ne $result) {
-            $result = __navr_bin jump "--" @args
+            $result = __navr_bin jump --no-track "--" @args
         }
         else {
-            $result = __navr_bin jump "--" @args
+            $result = __navr_bin jump --no-track "--" @args
         }
         if ($LASTEXITCODE -eq 0) {
             __navr_cd $result $true
@@ -351,22 +550,36 @@ Set-Alias -Name cd -Value Set-LocationNavr -Option AllScope -Scope Global -Force
 Register-ArgumentCompleter -CommandName navr -ParameterName target -ScriptBlock {
     param($commandName, $parameterName, $wordToComplete, $commandAst, $fakeBoundParameters)
     
-    $shortcuts = & navr jump --list 2>$null | 
-        Select-String -Pattern '^  (\w+)' | 
-        ForEach-Object { $_.Matches.Groups[1].Value }
-    
-    $shortcuts | Where-Object { $_ -like "$wordToComplete*" } | 
+    & navr _complete shortcuts $wordToComplete 2>$null |
         ForEach-Object { [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterValue', $_) }
 }
 
 Register-ArgumentCompleter -CommandName j -ScriptBlock {
     param($commandName, $parameterName, $wordToComplete, $commandAst, $fakeBoundParameters)
     
-    $shortcuts = & navr jump --list 2>$null | 
-        Select-String -Pattern '^  (\w+)' | 
-        ForEach-Object { $_.Matches.Groups[1].Value }
-    
-    $shortcuts | Where-Object { $_ -like "$wordToComplete*" } | 
+    & navr _complete shortcuts $wordToComplete 2>$null |
+        ForEach-Object { [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterValue', $_) }
+}
+
+# Config key/value completion for navr config set/get
+Register-ArgumentCompleter -Native -CommandName navr -ScriptBlock {
+    param($wordToComplete, $commandAst, $cursorPosition)
+
+    $words = @($commandAst.CommandElements | ForEach-Object { $_.ToString() })
+    if ($wordToComplete -ne '') {
+        $words = $words[0..($words.Count - 2)]
+    }
+    if ($words.Count -lt 3 -or $words[1] -notin 'config', 'cfg' -or $words[2] -notin 'set', 'get') {
+        return
+    }
+
+    $candidates = if ($words.Count -eq 3) {
+        & navr _complete config-keys 2>$null
+    } elseif ($words.Count -eq 4 -and $words[2] -eq 'set') {
+        & navr _complete config-values $words[3] 2>$null
+    }
+
+    $candidates | Where-Object { $_ -like "$wordToComplete*" } |
         ForEach-Object { [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterValue', $_) }
 }
 
@@ -383,6 +596,12 @@ pub const ELVISH_INTEGRATION: &str = r#"
 # Navr Shell Integration
 # Generated by navr shell install elvish
 
+# Mark the integration as active for `navr doctor`
+set-env NAVR_ACTIVE 1
+
+# Identify this shell session for `navr jump --add --temp`
+set-env NAVR_SESSION (to-string $pid)-(randint 0 32768)
+
 # Enable completion
 if (has-external navr) {
     eval (navr shell complete elvish | slurp)
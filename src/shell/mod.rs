@@ -1,68 +1,151 @@
 //! Shell integration and completion generation
-use clap::CommandFactory;
 use anyhow::{Context, Result};
-use clap_complete::{generate, Shell};
-use owo_colors::OwoColorize;
+use clap::CommandFactory;
+use clap_complete::{generate, Generator, Shell};
+use inquire::Confirm;
+use owo_colors::{OwoColorize, Stream::{Stderr, Stdout}};
 use std::fs;
-use std::io;
-use std::path::PathBuf;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 
 use crate::Cli;
-use crate::platform::shell_config_path;
+use crate::config::{paths, AppConfig};
+use crate::platform::{shell_completion_dir, shell_config_path, shell_init_dir};
 
+mod completions;
 pub mod integration;
 
 /// Generate shell completion scripts
-pub fn generate_completions(shell: Shell) -> Result<()> {
-    let mut cmd = Cli::command();
-    let bin_name = cmd.get_name().to_string();
-
-    println!(
+///
+/// Only the script goes to stdout so it can be redirected or evaluated;
+/// notes are printed to stderr.
+pub fn generate_completions(shell: Shell, dynamic: bool) -> Result<()> {
+    eprintln!(
         "{} Generating {} completions...",
-        "→".blue(),
-        format!("{:?}", shell).cyan()
+        "→".if_supports_color(Stderr, |t| t.blue()),
+        format!("{:?}", shell).if_supports_color(Stdout, |t| t.cyan())
     );
 
-    generate(shell, &mut cmd, bin_name, &mut io::stdout());
+    io::stdout().write_all(completion_script(shell, dynamic)?.as_bytes())?;
 
-    println!();
-    println!(
+    eprintln!();
+    eprintln!(
         "{} Save this output to your shell's completion directory",
-        "ℹ".blue()
+        "ℹ".if_supports_color(Stderr, |t| t.blue())
     );
-    
+    if let Ok(dir) = shell_completion_dir(&shell_name(shell)) {
+        eprintln!("  Typical location: {}", dir.display());
+    }
+    eprintln!("  Or let 'navr shell complete --output' write it there");
+
+    Ok(())
+}
+
+/// Write the completion script into `dir`, or the shell's completion
+/// directory, under the file name the shell looks for
+pub fn write_completions(
+    shell: Shell,
+    dynamic: bool,
+    dir: Option<&Path>,
+    config: &AppConfig,
+) -> Result<()> {
+    let dir = match dir {
+        Some(dir) => dir.to_path_buf(),
+        None => shell_completion_dir(&shell_name(shell))?,
+    };
+    let script = completion_script(shell, dynamic)?;
+    let Some(file) = write_script(&dir, &completion_file_name(shell), &script, config)? else {
+        return Ok(());
+    };
+
+    println!(
+        "{} Wrote {:?} completions to {}",
+        "✓".if_supports_color(Stdout, |t| t.green()),
+        shell,
+        file.display()
+    );
+    if shell == Shell::Zsh {
+        println!("  Make sure {} is in your $fpath before compinit runs", dir.display());
+    }
+    Ok(())
+}
+
+/// The file name a shell loads completions for navr from
+fn completion_file_name(shell: Shell) -> String {
     match shell {
-        Shell::Bash => {
-            println!("  Typical location: /etc/bash_completion.d/ or ~/.local/share/bash-completion/completions/");
-        }
-        Shell::Zsh => {
-            println!("  Typical location: /usr/local/share/zsh/site-functions/ or ~/.zsh/completions/");
-        }
-        Shell::Fish => {
-            println!("  Typical location: ~/.config/fish/completions/");
-        }
-        Shell::PowerShell => {
-            println!("  Typical location: $PROFILE directory");
+        // bash-completion loads completions by command name
+        Shell::Bash => "navr".to_string(),
+        _ => shell.file_name("navr"),
+    }
+}
+
+/// `Shell` as named by `shell_config_path` and friends
+fn shell_name(shell: Shell) -> String {
+    format!("{:?}", shell).to_lowercase()
+}
+
+/// Write `script` to `dir/file_name`, creating `dir` if needed
+///
+/// Returns the file written, or `None` in dry-run mode.
+fn write_script(
+    dir: &Path,
+    file_name: &str,
+    script: &str,
+    config: &AppConfig,
+) -> Result<Option<PathBuf>> {
+    let file = dir.join(file_name);
+    if config.skip_for_dry_run(|| format!("write {} bytes to {}", script.len(), file.display())) {
+        return Ok(None);
+    }
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create directory: {:?}", dir))?;
+    fs::write(&file, script).with_context(|| format!("Failed to write {:?}", file))?;
+    Ok(Some(file))
+}
+
+/// Static clap completions, followed by shortcut completion when `dynamic`
+fn completion_script(shell: Shell, dynamic: bool) -> Result<String> {
+    let mut cmd = Cli::command();
+    let bin_name = cmd.get_name().to_string();
+    let mut script = Vec::new();
+    generate(shell, &mut cmd, bin_name, &mut script);
+    let mut script = String::from_utf8(script)?;
+
+    if dynamic {
+        match shell {
+            Shell::Bash => script.push_str(completions::generate_bash_dynamic_completion()),
+            Shell::Zsh => {
+                // The wrapper takes over clap's dispatch to `_navr`
+                if let Some(dispatch) = script.rfind(completions::ZSH_DISPATCH) {
+                    let end = script[..dispatch].trim_end().len();
+                    script.truncate(end);
+                    script.push('\n');
+                }
+                script.push_str(completions::generate_zsh_dynamic_completion())
+            }
+            Shell::Fish => script.push_str(completions::generate_fish_dynamic_completion()),
+            _ => anyhow::bail!("Dynamic completions are not available for {:?}", shell),
         }
-        _ => {}
     }
 
-    Ok(())
+    Ok(script)
 }
 
 /// Install shell integration
 pub fn install_integration(shell: Shell, path: Option<&str>) -> Result<()> {
     let config_path = match path {
         Some(p) => PathBuf::from(p),
-        None => shell_config_path(&format!("{:?}", shell).to_lowercase())?,
+        None => shell_config_path(&shell_name(shell))?,
     };
 
     println!(
         "{} Installing {} integration...",
-        "→".blue(),
-        format!("{:?}", shell).cyan()
+        "→".if_supports_color(Stdout, |t| t.blue()),
+        format!("{:?}", shell).if_supports_color(Stdout, |t| t.cyan())
+    );
+    println!(
+        "  Target: {}",
+        config_path.display().to_string().if_supports_color(Stdout, |t| t.dimmed())
     );
-    println!("  Target: {}", config_path.display().to_string().dimmed());
 
     // Ensure parent directory exists
     if let Some(parent) = config_path.parent() {
@@ -73,29 +156,129 @@ pub fn install_integration(shell: Shell, path: Option<&str>) -> Result<()> {
     // Generate integration script
     let script = generate_integration_script(shell)?;
 
-    // Check if already installed
-    if config_path.exists() {
-        let existing = fs::read_to_string(&config_path)?;
-        if existing.contains("navr") {
-            println!("{} Navr integration already exists", "ℹ".yellow());
-            println!("  Run 'navr shell init {:?}' to see the initialization script", shell);
+    let existing = if config_path.exists() {
+        fs::read_to_string(&config_path)?
+    } else {
+        String::new()
+    };
+
+    // Refresh a block installed earlier so script changes are picked up
+    if let Some(updated) = replace_block(&existing, &script) {
+        if updated == existing {
+            println!(
+                "{} Navr integration is already up to date",
+                "ℹ".if_supports_color(Stdout, |t| t.yellow())
+            );
             return Ok(());
         }
+        fs::write(&config_path, updated)
+            .with_context(|| format!("Failed to write {:?}", config_path))?;
+        println!("{} Integration updated in place", "✓".if_supports_color(Stdout, |t| t.green()));
+        print_reload_hint(shell);
+        return Ok(());
     }
 
-    // Append to config file
-    let mut file = fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(&config_path)
-        .with_context(|| format!("Failed to open {:?}", config_path))?;
+    if is_integration_installed(&existing) {
+        println!(
+            "{} Navr integration already exists",
+            "ℹ".if_supports_color(Stdout, |t| t.yellow())
+        );
+        println!(
+            "  Run 'navr shell uninstall {:?}' and install again to switch to an updatable block",
+            shell
+        );
+        return Ok(());
+    }
+
+    append_integration(&config_path, &script)?;
+
+    println!(
+        "{} Integration installed successfully!",
+        "✓".if_supports_color(Stdout, |t| t.green())
+    );
+    print_reload_hint(shell);
+
+    Ok(())
+}
+
+/// Remove the shell integration from a startup file
+pub fn uninstall_integration(shell: Shell, path: Option<&str>) -> Result<()> {
+    let config_path = match path {
+        Some(p) => PathBuf::from(p),
+        None => shell_config_path(&shell_name(shell))?,
+    };
+
+    println!(
+        "{} Removing {} integration...",
+        "→".if_supports_color(Stdout, |t| t.blue()),
+        format!("{:?}", shell).if_supports_color(Stdout, |t| t.cyan())
+    );
+    println!(
+        "  Target: {}",
+        config_path.display().to_string().if_supports_color(Stdout, |t| t.dimmed())
+    );
 
-    use std::io::Write;
-    writeln!(file, "\n{}", script)?;
+    let content = if config_path.exists() {
+        fs::read_to_string(&config_path)
+            .with_context(|| format!("Failed to read {:?}", config_path))?
+    } else {
+        String::new()
+    };
+
+    let script = generate_integration_script(shell)?;
+    let updated = match remove_integration(&content, &script) {
+        Some(updated) => updated,
+        None => {
+            // Installed by hand or edited since: offer to drop anything mentioning navr
+            let lines: Vec<&str> = content
+                .lines()
+                .filter(|l| l.to_lowercase().contains("navr"))
+                .collect();
+            if lines.is_empty() {
+                println!(
+                    "{} No navr integration found",
+                    "ℹ".if_supports_color(Stdout, |t| t.yellow())
+                );
+                return Ok(());
+            }
 
-    println!("{} Integration installed successfully!", "✓".green());
+            println!(
+                "{} No navr block found; these lines mention navr:",
+                "!".if_supports_color(Stdout, |t| t.yellow())
+            );
+            for line in &lines {
+                println!("  {}", line.if_supports_color(Stdout, |t| t.dimmed()));
+            }
+            let confirm = Confirm::new(&format!("Remove these {} lines?", lines.len()))
+                .with_default(false)
+                .prompt()?;
+            if !confirm {
+                println!("{} Cancelled", "✗".if_supports_color(Stdout, |t| t.red()));
+                return Ok(());
+            }
+
+            let mut kept: String = content
+                .lines()
+                .filter(|l| !l.to_lowercase().contains("navr"))
+                .collect::<Vec<_>>()
+                .join("\n");
+            kept.push('\n');
+            kept
+        }
+    };
+
+    fs::write(&config_path, updated)
+        .with_context(|| format!("Failed to write {:?}", config_path))?;
+
+    println!("{} Integration removed", "✓".if_supports_color(Stdout, |t| t.green()));
+    print_reload_hint(shell);
+
+    Ok(())
+}
+
+fn print_reload_hint(shell: Shell) {
     println!();
-    println!("{} Please restart your shell or run:", "→".blue());
+    println!("{} Please restart your shell or run:", "→".if_supports_color(Stdout, |t| t.blue()));
     match shell {
         Shell::Bash => println!("  source ~/.bashrc"),
         Shell::Zsh => println!("  source ~/.zshrc"),
@@ -103,10 +286,123 @@ pub fn install_integration(shell: Shell, path: Option<&str>) -> Result<()> {
         Shell::PowerShell => println!("  . $PROFILE"),
         _ => {}
     }
+}
+
+/// Append the integration script to a startup file between block markers
+fn append_integration(config_path: &Path, script: &str) -> Result<()> {
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(config_path)
+        .with_context(|| format!("Failed to open {:?}", config_path))?;
 
+    writeln!(file, "\n{}", render_block(script))?;
     Ok(())
 }
 
+/// The integration script wrapped in block markers
+fn render_block(script: &str) -> String {
+    format!(
+        "{}\n{}\n{}",
+        integration::BLOCK_START,
+        script.trim_matches('\n'),
+        integration::BLOCK_END
+    )
+}
+
+/// Find the byte range of the marked block, from the start marker to the end marker
+fn find_block(content: &str) -> Option<(usize, usize)> {
+    let start = content.find(integration::BLOCK_START)?;
+    let end = start
+        + content[start..].find(integration::BLOCK_END)?
+        + integration::BLOCK_END.len();
+    Some((start, end))
+}
+
+/// Replace an existing marked block with the current script
+fn replace_block(content: &str, script: &str) -> Option<String> {
+    let (start, end) = find_block(content)?;
+    Some(format!("{}{}{}", &content[..start], render_block(script), &content[end..]))
+}
+
+/// Remove the integration from startup file content
+///
+/// Looks for the marked block first, then for the unmarked script written
+/// by older versions. Returns `None` when neither is present.
+fn remove_integration(content: &str, script: &str) -> Option<String> {
+    if let Some((start, end)) = find_block(content) {
+        // Also drop the blank line added before the block and its line break
+        let start = if content[..start].ends_with("\n\n") { start - 1 } else { start };
+        let end = if content[end..].starts_with('\n') { end + 1 } else { end };
+        return Some(format!("{}{}", &content[..start], &content[end..]));
+    }
+
+    let legacy = format!("\n{}\n", script);
+    content
+        .contains(&legacy)
+        .then(|| content.replacen(&legacy, "", 1))
+}
+
+/// Detect the shell navr is running under
+///
+/// `$NAVR_SHELL` wins, then the parent process, then the login shell in
+/// `$SHELL`.
+pub fn detect_current_shell() -> Result<Shell> {
+    let navr_shell = std::env::var("NAVR_SHELL").ok();
+    let parent = parent_process_name();
+    let login_shell = std::env::var("SHELL").ok();
+
+    detect_shell_from(navr_shell.as_deref(), parent.as_deref(), login_shell.as_deref())
+        .or_else(|| cfg!(windows).then_some(Shell::PowerShell))
+        .context("Could not detect the current shell; pass it explicitly (bash, zsh, fish, powershell or elvish) or set NAVR_SHELL")
+}
+
+fn detect_shell_from(
+    navr_shell: Option<&str>,
+    parent: Option<&str>,
+    login_shell: Option<&str>,
+) -> Option<Shell> {
+    [navr_shell, parent, login_shell]
+        .into_iter()
+        .flatten()
+        .find_map(shell_from_name)
+}
+
+/// Map a shell program name or path (`/usr/bin/zsh`, `-bash`, `pwsh.exe`) to a shell
+fn shell_from_name(name: &str) -> Option<Shell> {
+    let program = name.rsplit(['/', '\\']).next()?.trim_start_matches('-').to_lowercase();
+    let program = program.strip_suffix(".exe").unwrap_or(&program);
+    match program {
+        "bash" => Some(Shell::Bash),
+        "zsh" => Some(Shell::Zsh),
+        "fish" => Some(Shell::Fish),
+        "elvish" => Some(Shell::Elvish),
+        "pwsh" | "powershell" => Some(Shell::PowerShell),
+        _ => None,
+    }
+}
+
+/// Name of the process that started navr, where the platform exposes it
+fn parent_process_name() -> Option<String> {
+    #[cfg(target_os = "linux")]
+    {
+        let ppid = std::os::unix::process::parent_id();
+        std::fs::read_to_string(format!("/proc/{}/comm", ppid))
+            .ok()
+            .map(|name| name.trim().to_string())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// Check whether a shell startup file already contains the navr integration
+pub fn is_integration_installed(content: &str) -> bool {
+    content.contains(integration::INTEGRATION_MARKER)
+}
+
 /// Print initialization script for manual installation
 pub fn print_init_script(shell: Shell) -> Result<()> {
     let script = generate_integration_script(shell)?;
@@ -114,14 +410,232 @@ pub fn print_init_script(shell: Shell) -> Result<()> {
     Ok(())
 }
 
+/// Write the initialization script into `dir`, or next to the shell's
+/// startup file (fish: its `conf.d`), as `navr.<extension>`
+pub fn write_init_script(shell: Shell, dir: Option<&Path>, config: &AppConfig) -> Result<()> {
+    let dir = match dir {
+        Some(dir) => dir.to_path_buf(),
+        None => shell_init_dir(&shell_name(shell))?,
+    };
+    let script = generate_integration_script(shell)?;
+    let Some(file) = write_script(&dir, init_file_name(shell)?, &format!("{}\n", script), config)? else {
+        return Ok(());
+    };
+
+    println!(
+        "{} Wrote {:?} integration to {}",
+        "✓".if_supports_color(Stdout, |t| t.green()),
+        shell,
+        file.display()
+    );
+    match shell {
+        Shell::Fish if dir.ends_with("conf.d") => println!("  fish loads it in new shells"),
+        Shell::PowerShell => println!("  Load it from your profile: . \"{}\"", file.display()),
+        Shell::Elvish => println!("  Load it from rc.elv: eval (slurp < {})", file.display()),
+        _ => println!("  Load it from your startup file: source {}", file.display()),
+    }
+    Ok(())
+}
+
+fn init_file_name(shell: Shell) -> Result<&'static str> {
+    Ok(match shell {
+        Shell::Bash => "navr.bash",
+        Shell::Zsh => "navr.zsh",
+        Shell::Fish => "navr.fish",
+        Shell::PowerShell => "navr.ps1",
+        Shell::Elvish => "navr.elv",
+        _ => anyhow::bail!("Unsupported shell: {:?}", shell),
+    })
+}
+
 /// Generate the appropriate integration script for the shell
+///
+/// With `--profile`, the script also selects that profile for the shell.
 fn generate_integration_script(shell: Shell) -> Result<String> {
-    match shell {
-        Shell::Bash => Ok(integration::BASH_INTEGRATION.to_string()),
-        Shell::Zsh => Ok(integration::ZSH_INTEGRATION.to_string()),
-        Shell::Fish => Ok(integration::FISH_INTEGRATION.to_string()),
-        Shell::PowerShell => Ok(integration::POWERSHELL_INTEGRATION.to_string()),
-        Shell::Elvish => Ok(integration::ELVISH_INTEGRATION.to_string()),
+    let mut script = match shell {
+        Shell::Bash => integration::BASH_INTEGRATION.to_string(),
+        Shell::Zsh => integration::ZSH_INTEGRATION.to_string(),
+        Shell::Fish => integration::FISH_INTEGRATION.to_string(),
+        Shell::PowerShell => integration::POWERSHELL_INTEGRATION.to_string(),
+        Shell::Elvish => integration::ELVISH_INTEGRATION.to_string(),
         _ => anyhow::bail!("Unsupported shell: {:?}", shell),
+    };
+    if let Some(profile) = paths::profile_flag() {
+        script.push_str(&profile_export(shell, profile));
+    }
+    Ok(script)
+}
+
+/// Script lines exporting `NAVR_PROFILE`; profile names need no escaping
+fn profile_export(shell: Shell, profile: &str) -> String {
+    let line = match shell {
+        Shell::Fish => format!("set -gx {} \"{}\"", paths::PROFILE_ENV, profile),
+        Shell::PowerShell => format!("$env:{} = \"{}\"", paths::PROFILE_ENV, profile),
+        Shell::Elvish => format!("set-env {} \"{}\"", paths::PROFILE_ENV, profile),
+        _ => format!("export {}=\"{}\"", paths::PROFILE_ENV, profile),
+    };
+    format!("\n# Use the navr profile this script was generated for\n{}\n", line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scripts_are_detected_as_installed() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::PowerShell, Shell::Elvish] {
+            let script = generate_integration_script(shell).unwrap();
+            assert!(is_integration_installed(&script), "{:?} lacks the marker", shell);
+            assert!(script.contains("NAVR_ACTIVE"), "{:?} does not set NAVR_ACTIVE", shell);
+        }
+        assert!(!is_integration_installed("alias navr-ls='ls'"));
+    }
+
+    #[test]
+    fn test_cd_wrappers_handle_jump_menu() {
+        use crate::commands::jump::JUMP_MENU_MARKER;
+
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+            let script = generate_integration_script(shell).unwrap();
+            assert!(
+                script.contains("navr jump --no-track --menu"),
+                "{:?} does not ask for an untracked menu",
+                shell
+            );
+            assert!(script.contains(JUMP_MENU_MARKER), "{:?} does not parse the menu", shell);
+        }
+    }
+
+    #[test]
+    fn test_powershell_aliases_point_at_functions() {
+        let script = integration::POWERSHELL_INTEGRATION;
+
+        // `eval` is not a PowerShell command
+        assert!(!script.lines().any(|l| l.trim_start().starts_with("eval ")));
+
+        // Set-Alias can only bind a single command name, so each alias must
+        // target a wrapper function defined by the script
+        for line in script.lines().filter(|l| l.starts_with("Set-Alias")) {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let value = words
+                .iter()
+                .position(|w| *w == "-Value")
+                .and_then(|i| words.get(i + 1))
+                .unwrap_or_else(|| panic!("alias without -Value: {}", line));
+            assert!(
+                script.contains(&format!("function global:{} ", value))
+                    || script.contains(&format!("function global:{}(", value)),
+                "{} is not a function defined by the script",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn test_detect_shell_from_env() {
+        assert_eq!(detect_shell_from(None, None, Some("/usr/bin/zsh")), Some(Shell::Zsh));
+
+        // The parent process is preferred over the login shell
+        assert_eq!(detect_shell_from(None, Some("fish"), Some("/usr/bin/zsh")), Some(Shell::Fish));
+        assert_eq!(detect_shell_from(None, Some("cargo"), Some("/usr/bin/zsh")), Some(Shell::Zsh));
+
+        // NAVR_SHELL overrides everything
+        assert_eq!(detect_shell_from(Some("bash"), Some("fish"), None), Some(Shell::Bash));
+        assert_eq!(detect_shell_from(None, None, None), None);
+    }
+
+    #[test]
+    fn test_shell_from_name() {
+        assert_eq!(shell_from_name("-bash"), Some(Shell::Bash));
+        assert_eq!(shell_from_name("C:\\Program Files\\PowerShell\\7\\pwsh.exe"), Some(Shell::PowerShell));
+        assert_eq!(shell_from_name("/bin/sh"), None);
+    }
+
+    #[test]
+    fn test_install_then_uninstall_round_trips() {
+        let dir = std::env::temp_dir().join(format!("navr-uninstall-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let rc = dir.join(".bashrc");
+        let original = "export EDITOR=vim\nalias ll='ls -l'\n";
+        fs::write(&rc, original).unwrap();
+
+        let script = generate_integration_script(Shell::Bash).unwrap();
+        append_integration(&rc, &script).unwrap();
+        let installed = fs::read_to_string(&rc).unwrap();
+        assert!(is_integration_installed(&installed));
+        assert!(installed.contains(integration::BLOCK_START));
+
+        assert_eq!(remove_integration(&installed, &script).as_deref(), Some(original));
+
+        // Snippets appended by older versions had no markers
+        let legacy = format!("{}\n{}\n", original, script);
+        assert_eq!(remove_integration(&legacy, &script).as_deref(), Some(original));
+        assert_eq!(remove_integration(original, &script), None);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_reinstall_updates_block_in_place() {
+        let script = generate_integration_script(Shell::Bash).unwrap();
+        let stale = format!(
+            "export A=1\n\n{}\n# an older navr script\n{}\nexport B=2\n",
+            integration::BLOCK_START,
+            integration::BLOCK_END
+        );
+
+        let updated = replace_block(&stale, &script).unwrap();
+        assert_eq!(updated, format!("export A=1\n\n{}\nexport B=2\n", render_block(&script)));
+        assert!(!updated.contains("an older navr script"));
+
+        // Reinstalling an up-to-date block changes nothing
+        assert_eq!(replace_block(&updated, &script).unwrap(), updated);
+
+        // A stray mention of navr does not count as an installation
+        assert_eq!(replace_block("# try navr later\n", &script), None);
+        assert!(!is_integration_installed("# try navr later\n"));
+    }
+
+    #[test]
+    fn test_completions_written_to_output_dir() {
+        let dir = std::env::temp_dir()
+            .join(format!("navr-completions-{}", std::process::id()))
+            .join("completions");
+
+        let mut config = AppConfig::default();
+        config.set_dry_run(true);
+        write_completions(Shell::Bash, false, Some(&dir), &config).unwrap();
+        assert!(!dir.exists());
+
+        config.set_dry_run(false);
+        write_completions(Shell::Bash, false, Some(&dir), &config).unwrap();
+        let file = dir.join("navr");
+        assert!(fs::metadata(&file).unwrap().len() > 0);
+
+        write_init_script(Shell::Fish, Some(&dir), &config).unwrap();
+        assert!(fs::read_to_string(dir.join("navr.fish")).unwrap().contains("navr"));
+
+        fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_dynamic_completions_extend_static_script() {
+        for shell in [Shell::Bash, Shell::Fish] {
+            let static_script = completion_script(shell, false).unwrap();
+            let dynamic_script = completion_script(shell, true).unwrap();
+            assert!(dynamic_script.starts_with(&static_script));
+            assert!(dynamic_script[static_script.len()..].contains("navr _complete shortcuts"));
+        }
+
+        // zsh dispatches to the wrapper, which is defined before that
+        let dynamic_script = completion_script(Shell::Zsh, true).unwrap();
+        let wrapper = dynamic_script.find("_navr_dynamic() {").unwrap();
+        let dispatch = dynamic_script.rfind(completions::ZSH_DISPATCH).unwrap();
+        assert!(wrapper < dispatch);
+        assert!(dynamic_script[dispatch..].contains("_navr_dynamic \"$@\""));
+        assert!(!dynamic_script.contains("compdef _navr navr"));
+
+        assert!(completion_script(Shell::PowerShell, false).is_ok());
+        assert!(completion_script(Shell::PowerShell, true).is_err());
     }
 }
@@ -4,11 +4,7 @@ use std::process::Command;
 use std::path::PathBuf;
 
 fn get_binary_path() -> PathBuf {
-    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    path.push("target");
-    path.push("debug");
-    path.push("quicknav");
-    path
+    PathBuf::from(env!("CARGO_BIN_EXE_navr"))
 }
 
 #[test]
@@ -111,3 +107,469 @@ fn test_quick_flag() {
     let _stdout = String::from_utf8_lossy(&output.stdout);
     let _stderr = String::from_utf8_lossy(&output.stderr);
 }
+
+#[test]
+fn test_no_color_env() {
+    let dir = std::env::temp_dir().join(format!("navr-no-color-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let run = |args: &[&str], no_color: bool| {
+        let mut cmd = Command::new(get_binary_path());
+        cmd.args(args)
+            .env("NAVR_CONFIG_DIR", &dir)
+            .env_remove("NO_COLOR")
+            // Color output even though stdout is a pipe
+            .env("FORCE_COLOR", "1");
+        if no_color {
+            cmd.env("NO_COLOR", "1");
+        }
+        cmd.output().expect("Failed to execute navr")
+    };
+
+    let target = dir.to_string_lossy().to_string();
+    assert!(run(&["jump", "--add", "proj", &target], true).status.success());
+    for args in [&["jump", "--list"][..], &["config", "show"][..]] {
+        let styled = run(args, false);
+        let plain = run(args, true);
+        assert!(styled.status.success() && plain.status.success());
+
+        let styled = String::from_utf8_lossy(&styled.stdout);
+        let plain = String::from_utf8_lossy(&plain.stdout);
+        assert!(styled.contains('\x1b'), "no escape sequence in {:?}", args);
+        assert!(!plain.contains('\x1b'), "escape sequence in {:?}", args);
+        assert!(plain.contains("proj"));
+        assert_eq!(strip_escapes(&styled), plain);
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Remove ANSI SGR sequences such as `\x1b[1;36m`
+fn strip_escapes(text: &str) -> String {
+    let mut plain = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|&c| c == 'm');
+        } else {
+            plain.push(c);
+        }
+    }
+    plain
+}
+
+#[test]
+fn test_exit_codes_follow_error_kind() {
+    let dir = std::env::temp_dir().join(format!("navr-exit-codes-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let run = |args: &[&str]| {
+        Command::new(get_binary_path())
+            .args(args)
+            .env("NAVR_CONFIG_DIR", &dir)
+            .output()
+            .expect("Failed to execute navr")
+    };
+
+    let missing = dir.join("missing").to_string_lossy().to_string();
+    assert_eq!(run(&["jump", &missing]).status.code(), Some(3));
+    assert_eq!(run(&["jump", "--rename", "nope", "other"]).status.code(), Some(3));
+
+    std::fs::write(dir.join("config.toml"), "[shortcuts\n").unwrap();
+    assert_eq!(run(&["jump", "--list"]).status.code(), Some(6));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_filtered_listing_fails_when_nothing_matches() {
+    let dir = std::env::temp_dir().join(format!("navr-list-filter-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let run = |args: &[&str]| {
+        Command::new(get_binary_path())
+            .args(args)
+            .env("NAVR_CONFIG_DIR", &dir)
+            .output()
+            .expect("Failed to execute navr")
+    };
+
+    assert!(run(&["jump", "--add", "proj", "--add-path", &dir.to_string_lossy()]).status.success());
+    let output = run(&["jump", "--list", "--filter", "proj", "--json"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("\"proj\""));
+
+    for mode in ["--json", "--paths-only", "--names-only"] {
+        let output = run(&["jump", "--list", "--filter", "zzz", mode]);
+        assert!(!output.status.success(), "{} succeeded", mode);
+        assert!(output.stdout.is_empty());
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_jump_dash_returns_to_previous_directory() {
+    let dir = std::env::temp_dir().join(format!("navr-jump-dash-{}", std::process::id()));
+    let (first, second) = (dir.join("first"), dir.join("second"));
+    std::fs::create_dir_all(&first).unwrap();
+    std::fs::create_dir_all(&second).unwrap();
+    let first = first.canonicalize().unwrap();
+    let second = second.canonicalize().unwrap();
+
+    let jump = |from: &PathBuf, target: &str| {
+        let output = Command::new(get_binary_path())
+            .args(["jump", target])
+            .current_dir(from)
+            .env("NAVR_CONFIG_DIR", dir.join("config"))
+            .output()
+            .expect("Failed to execute navr");
+        assert!(output.status.success());
+        PathBuf::from(String::from_utf8_lossy(&output.stdout).trim())
+    };
+
+    assert_eq!(jump(&first, &second.to_string_lossy()), second);
+    assert_eq!(jump(&second, "-"), first);
+    // A second `-` toggles back, like `cd -`
+    assert_eq!(jump(&first, "-"), second);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_import_reads_config_from_stdin() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let dir = std::env::temp_dir().join(format!("navr-import-stdin-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let mut child = Command::new(get_binary_path())
+        .args(["import", "-", "--merge"])
+        .env("NAVR_CONFIG_DIR", &dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .expect("Failed to execute navr");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(br#"{"shortcuts": {"piped": "/tmp"}}"#)
+        .unwrap();
+    assert!(child.wait().unwrap().success());
+
+    let config = std::fs::read_to_string(dir.join("config.toml")).unwrap();
+    assert!(config.contains("piped = \"/tmp\""));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_jump_keeps_symlink_when_not_following() {
+    let dir = std::env::temp_dir().join(format!("navr-jump-symlink-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("real")).unwrap();
+    let dir = dir.canonicalize().unwrap();
+    let link = dir.join("link");
+    let _ = std::fs::remove_file(&link);
+    std::os::unix::fs::symlink(dir.join("real"), &link).unwrap();
+
+    let run = |args: &[&str]| {
+        let output = Command::new(get_binary_path())
+            .args(args)
+            .env("NAVR_CONFIG_DIR", dir.join("config"))
+            .output()
+            .expect("Failed to execute navr");
+        assert!(output.status.success());
+        PathBuf::from(String::from_utf8_lossy(&output.stdout).trim())
+    };
+
+    let target = link.to_string_lossy();
+    assert_eq!(run(&["jump", &target]), dir.join("real"));
+    run(&["config", "set", "behavior.follow_symlinks", "false"]);
+    assert_eq!(run(&["jump", &target]), link);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_list_paths_only_is_bare() {
+    let dir = std::env::temp_dir().join(format!("navr-paths-only-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("config.toml"),
+        "[shortcuts]\nwork = \"/srv/work\"\nw = \"@work\"\nnotes = \"/home/user/notes\"\n",
+    )
+    .unwrap();
+    let list = |flag: &str| {
+        let output = Command::new(get_binary_path())
+            .args(["jump", "--list", flag])
+            .env("NAVR_CONFIG_DIR", &dir)
+            .output()
+            .expect("Failed to execute navr");
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    let paths = list("--paths-only");
+    assert!(!paths.contains('\x1b'));
+    let mut lines: Vec<&str> = paths.lines().collect();
+    lines.sort();
+    assert_eq!(lines, ["/home/user/notes", "/srv/work"]);
+
+    assert_eq!(list("--names-only"), "notes\nw\nwork\n");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_export_to_stdout() {
+    let dir = std::env::temp_dir().join(format!("navr-export-stdout-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("config.toml"), "[shortcuts]\nwork = \"/srv/work\"\n").unwrap();
+
+    let output = Command::new(get_binary_path())
+        .args(["export", "--format", "json", "--output", "-", "--only", "shortcuts"])
+        .env("NAVR_CONFIG_DIR", &dir)
+        .output()
+        .expect("Failed to execute navr");
+    assert!(output.status.success());
+
+    let exported: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(exported["shortcuts"]["work"], "/srv/work");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("to stdout"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_resolution_order_and_sigil() {
+    let dir = std::env::temp_dir().join(format!("navr-resolution-{}", std::process::id()));
+    let (local, elsewhere) = (dir.join("work").join("src"), dir.join("elsewhere"));
+    std::fs::create_dir_all(&local).unwrap();
+    std::fs::create_dir_all(&elsewhere).unwrap();
+    let (local, elsewhere) = (local.canonicalize().unwrap(), elsewhere.canonicalize().unwrap());
+
+    let run = |args: &[&str]| {
+        let output = Command::new(get_binary_path())
+            .args(args)
+            .current_dir(dir.join("work"))
+            .env("NAVR_CONFIG_DIR", dir.join("config"))
+            .output()
+            .expect("Failed to execute navr");
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        PathBuf::from(String::from_utf8_lossy(&output.stdout).trim())
+    };
+
+    run(&["jump", "--add", "src", "--add-path", &elsewhere.to_string_lossy()]);
+    assert_eq!(run(&["jump", "src"]), elsewhere);
+
+    run(&["config", "set", "behavior.resolution_order", "path_first"]);
+    assert_eq!(run(&["jump", "src"]), local);
+
+    // The sigil always means the shortcut
+    run(&["config", "set", "behavior.shortcut_sigil", "@"]);
+    assert_eq!(run(&["jump", "@src"]), elsewhere);
+    assert_eq!(run(&["jump", "src"]), local);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_add_git_root_from_nested_directory() {
+    let dir = std::env::temp_dir().join(format!("navr-git-root-{}", std::process::id()));
+    let repo = dir.join("My Repo");
+    let nested = repo.join("src").join("deep");
+    std::fs::create_dir_all(repo.join(".git")).unwrap();
+    std::fs::create_dir_all(&nested).unwrap();
+    let outside = dir.join("outside");
+    std::fs::create_dir_all(&outside).unwrap();
+
+    let run = |cwd: &PathBuf, args: &[&str]| {
+        Command::new(get_binary_path())
+            .args(args)
+            .current_dir(cwd)
+            .env("NAVR_CONFIG_DIR", dir.join("config"))
+            .output()
+            .expect("Failed to execute navr")
+    };
+
+    let output = run(&nested, &["jump", "--add", "--git-root"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let output = run(&outside, &["jump", "--resolve", "my-repo"]);
+    let resolved = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    assert_eq!(resolved, repo.canonicalize().unwrap());
+
+    let output = run(&outside, &["jump", "--add", "away", "--git-root"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No git repository found"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_jump_stdout_is_only_the_path() {
+    let dir = std::env::temp_dir().join(format!("navr-jump-stdout-{}", std::process::id()));
+    let target = dir.join("work");
+    std::fs::create_dir_all(&target).unwrap();
+    let target = target.canonicalize().unwrap();
+
+    let run = |args: &[&str]| {
+        let output = Command::new(get_binary_path())
+            .args(args)
+            .current_dir(&dir)
+            .env("NAVR_CONFIG_DIR", dir.join("config"))
+            .output()
+            .expect("Failed to execute navr");
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        output
+    };
+
+    run(&["jump", "--add", "work", "--add-path", &target.to_string_lossy()]);
+    for verbose in [false, true] {
+        let args: &[&str] = if verbose { &["--verbose", "jump", "work"] } else { &["jump", "work"] };
+        let output = run(args);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let lines: Vec<&str> = stdout.lines().collect();
+        assert_eq!(lines, [target.to_string_lossy()], "verbose: {}", verbose);
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert_eq!(stderr.contains("Jumping to"), verbose);
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_open_records_history() {
+    let dir = std::env::temp_dir().join(format!("navr-open-history-{}", std::process::id()));
+    let (tracked, untracked) = (dir.join("tracked"), dir.join("untracked"));
+    std::fs::create_dir_all(&tracked).unwrap();
+    std::fs::create_dir_all(&untracked).unwrap();
+    let tracked = tracked.canonicalize().unwrap();
+
+    // `true` stands in for a file manager
+    let open = |target: &PathBuf, extra: &[&str]| {
+        let output = Command::new(get_binary_path())
+            .args(["open", "--with", "true"])
+            .args(extra)
+            .arg(target)
+            .env("NAVR_CONFIG_DIR", dir.join("config"))
+            .output()
+            .expect("Failed to execute navr");
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    };
+    open(&tracked, &[]);
+    open(&untracked, &["--no-track"]);
+
+    let history = std::fs::read_to_string(dir.join("config").join("history.txt")).unwrap();
+    let paths: Vec<&str> = history.lines().filter_map(|line| line.split_once('\t')).map(|(_, p)| p).collect();
+    assert_eq!(paths, [tracked.to_string_lossy()]);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_wrapper_jump_records_history_once() {
+    let dir = std::env::temp_dir().join(format!("navr-wrapper-history-{}", std::process::id()));
+    let target = dir.join("work");
+    std::fs::create_dir_all(&target).unwrap();
+    let target = target.canonicalize().unwrap();
+
+    let run = |args: &[&str]| {
+        let output = Command::new(get_binary_path())
+            .args(args)
+            .env("NAVR_CONFIG_DIR", dir.join("config"))
+            .output()
+            .expect("Failed to execute navr");
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        output
+    };
+    run(&["jump", "--add", "work", "--add-path", &target.to_string_lossy()]);
+
+    // Replay what the bash `cd` wrapper runs: its resolving jump, then the hook
+    let script = String::from_utf8(run(&["shell", "init", "bash"]).stdout).unwrap();
+    let jump = script
+        .lines()
+        .find_map(|line| line.split_once("$(navr ")?.1.split_once(" \"$target\"").map(|(a, _)| a))
+        .expect("no resolving jump in the bash wrapper");
+    let mut args: Vec<&str> = jump.split_whitespace().collect();
+    args.push("work");
+    let resolved = String::from_utf8(run(&args).stdout).unwrap();
+    assert_eq!(resolved.trim(), target.to_string_lossy());
+    // The hook records the move, so the jump itself must not
+    assert!(!dir.join("config").join("history.txt").exists());
+    run(&["history", "add", "--from", &dir.to_string_lossy(), "--", resolved.trim()]);
+
+    let history = std::fs::read_to_string(dir.join("config").join("history.txt")).unwrap();
+    let paths: Vec<&str> = history.lines().filter_map(|line| line.split_once('\t')).map(|(_, p)| p).collect();
+    assert_eq!(paths, [target.to_string_lossy()]);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_history_keeps_symlink_without_follow_symlinks() {
+    let dir = std::env::temp_dir().join(format!("navr-history-link-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("real")).unwrap();
+    let dir = dir.canonicalize().unwrap();
+    let link = dir.join("link");
+    std::os::unix::fs::symlink(dir.join("real"), &link).unwrap();
+
+    let run = |args: &[&str]| {
+        let output = Command::new(get_binary_path())
+            .args(args)
+            .env("NAVR_CONFIG_DIR", dir.join("config"))
+            .output()
+            .expect("Failed to execute navr");
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+    run(&["config", "set", "behavior.follow_symlinks", "false"]);
+    assert_eq!(run(&["jump", &link.to_string_lossy()]), link.to_string_lossy());
+
+    let history = std::fs::read_to_string(dir.join("config").join("history.txt")).unwrap();
+    let paths: Vec<&str> = history.lines().filter_map(|line| line.split_once('\t')).map(|(_, p)| p).collect();
+    assert_eq!(paths, [link.to_string_lossy()]);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_which_reports_shortcut_and_fails_on_miss() {
+    let dir = std::env::temp_dir().join(format!("navr-which-{}", std::process::id()));
+    let project = dir.join("project");
+    std::fs::create_dir_all(&project).unwrap();
+    let project = project.canonicalize().unwrap();
+
+    let run = |args: &[&str]| {
+        Command::new(get_binary_path())
+            .args(args)
+            .current_dir(&dir)
+            .env("NAVR_CONFIG_DIR", dir.join("config"))
+            .env("NO_COLOR", "1")
+            .output()
+            .expect("Failed to execute navr")
+    };
+
+    assert!(run(&["jump", "--add", "proj", "--add-path", &project.to_string_lossy()]).status.success());
+    let output = run(&["which", "proj"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with(&*project.to_string_lossy()), "{}", stdout);
+    assert!(stdout.contains("(shortcut 'proj')"), "{}", stdout);
+
+    let output = run(&["which", "nowhere-at-all"]);
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+
+    // A jump would create missing directories, but which still reports a miss
+    assert!(run(&["config", "set", "behavior.create_missing", "true"]).status.success());
+    for target in ["nowhere-at-all", "proj/missing"] {
+        let output = run(&["which", target]);
+        assert!(!output.status.success(), "{}", target);
+        assert!(output.stdout.is_empty(), "{}", target);
+    }
+    assert!(!dir.join("nowhere-at-all").exists() && !project.join("missing").exists());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}